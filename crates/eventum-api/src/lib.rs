@@ -0,0 +1,174 @@
+//! HTTP API over the indexer database. Market data comes from the
+//! [`Store`] that `eventum-indexer` fills; quotes run the program's own
+//! pricing code (through `eventum-client`) on the stored market state, so
+//! every front-end and partner sees the numbers `buy_outcomes` would charge.
+//!
+//! - `GET /markets`
+//! - `GET /markets/{id}`
+//! - `GET /markets/{id}/quote?side=yes&qty=10[&slippage_bps=50]`
+//! - `GET /users/{pk}/positions`
+//!
+//! Amounts are raw token units and lamports; prices are scaled by
+//! `PRICE_SCALE`. Errors are `{"error": "..."}` with a 4xx/5xx status.
+
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use eventum_client::{ClientError, Side};
+use eventum_indexer::store::{MarketRow, PositionRow, Volume};
+use eventum_indexer::{IndexerError, Store};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+
+pub type AppState = Arc<Mutex<Store>>;
+
+/// The API's routes over `store`.
+pub fn router(store: Store) -> Router {
+    Router::new()
+        .route("/markets", get(markets))
+        .route("/markets/{id}", get(market))
+        .route("/markets/{id}/quote", get(quote))
+        .route("/users/{pk}/positions", get(positions))
+        .with_state(Arc::new(Mutex::new(store)))
+}
+
+#[derive(Debug)]
+pub enum ApiError {
+    BadRequest(String),
+    NotFound(String),
+    /// The program would reject the trade (resolved market, trade limits, ...).
+    Rejected(String),
+    Internal(String),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            ApiError::BadRequest(m) => (StatusCode::BAD_REQUEST, m),
+            ApiError::NotFound(m) => (StatusCode::NOT_FOUND, m),
+            ApiError::Rejected(m) => (StatusCode::UNPROCESSABLE_ENTITY, m),
+            ApiError::Internal(m) => (StatusCode::INTERNAL_SERVER_ERROR, m),
+        };
+        (status, Json(json!({ "error": message }))).into_response()
+    }
+}
+
+impl From<IndexerError> for ApiError {
+    fn from(e: IndexerError) -> Self {
+        ApiError::Internal(e.to_string())
+    }
+}
+
+impl From<ClientError> for ApiError {
+    fn from(e: ClientError) -> Self {
+        match e {
+            ClientError::Program(e) => ApiError::Rejected(e.to_string()),
+            e => ApiError::Internal(e.to_string()),
+        }
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+fn pubkey(s: &str) -> Result<Pubkey, ApiError> {
+    Pubkey::from_str(s).map_err(|_| ApiError::BadRequest(format!("invalid address {s}")))
+}
+
+fn with_store<T>(state: &AppState, f: impl FnOnce(&Store) -> Result<T, ApiError>) -> Result<T, ApiError> {
+    let store = state
+        .lock()
+        .map_err(|_| ApiError::Internal("store lock poisoned".into()))?;
+    f(&store)
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// A market with its all-time trade volume.
+#[derive(Serialize)]
+pub struct MarketDetail {
+    #[serde(flatten)]
+    pub market: MarketRow,
+    pub volume: Volume,
+}
+
+#[derive(Deserialize)]
+pub struct QuoteParams {
+    #[serde(with = "eventum_client::names::side")]
+    pub side: Side,
+    /// Whole tokens, as `buy_outcomes` takes them.
+    pub qty: u64,
+    #[serde(default)]
+    pub slippage_bps: u16,
+}
+
+#[derive(Serialize)]
+pub struct QuoteView {
+    pub market: String,
+    #[serde(with = "eventum_client::names::side")]
+    pub side: Side,
+    pub qty: u64,
+    pub tokens: u64,
+    pub cost: u64,
+    pub fee: u64,
+    pub total: u64,
+    /// `max_cost` to send allowing `slippage_bps`.
+    pub max_cost: u64,
+    pub price_yes_before: u64,
+    pub price_yes_after: u64,
+}
+
+async fn markets(State(state): State<AppState>) -> ApiResult<Vec<MarketRow>> {
+    with_store(&state, |store| Ok(store.markets()?)).map(Json)
+}
+
+async fn market(State(state): State<AppState>, Path(id): Path<String>) -> ApiResult<MarketDetail> {
+    let address = pubkey(&id)?;
+    with_store(&state, |store| {
+        let market = store
+            .market(&address)?
+            .ok_or_else(|| ApiError::NotFound(format!("market {id} is not indexed")))?;
+        let volume = store.volume(Some(&address), None)?;
+        Ok(MarketDetail { market, volume })
+    })
+    .map(Json)
+}
+
+/// Quotes at the server's clock, which only matters to time-increasing fees.
+async fn quote(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<QuoteParams>,
+) -> ApiResult<QuoteView> {
+    let address = pubkey(&id)?;
+    let market = with_store(&state, |store| Ok(store.market_state(&address)?))?
+        .ok_or_else(|| ApiError::NotFound(format!("market {id} has no indexed account state")))?;
+    let quote = market.quote_buy(params.side, params.qty, unix_now())?;
+    Ok(Json(QuoteView {
+        market: id,
+        side: params.side,
+        qty: params.qty,
+        tokens: quote.tokens,
+        cost: quote.cost,
+        fee: quote.fee,
+        total: quote.total,
+        max_cost: quote.max_cost_with_slippage(params.slippage_bps),
+        price_yes_before: quote.price_yes_before,
+        price_yes_after: quote.price_yes_after,
+    }))
+}
+
+async fn positions(State(state): State<AppState>, Path(pk): Path<String>) -> ApiResult<Vec<PositionRow>> {
+    let user = pubkey(&pk)?;
+    with_store(&state, |store| Ok(store.positions(&user)?)).map(Json)
+}
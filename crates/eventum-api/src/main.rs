@@ -0,0 +1,37 @@
+//! `eventum-api`: serve the indexer database over HTTP. Run
+//! `eventum-indexer run` against the same `--db` to keep it current.
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use eventum_indexer::Store;
+use tower_http::cors::CorsLayer;
+
+#[derive(Parser)]
+#[command(name = "eventum-api", version, about)]
+struct Cli {
+    /// SQLite database written by eventum-indexer
+    #[arg(long, env = "EVENTUM_INDEXER_DB", default_value = "eventum.db")]
+    db: PathBuf,
+
+    /// Address to listen on
+    #[arg(long, env = "EVENTUM_API_LISTEN", default_value = "127.0.0.1:8080")]
+    listen: SocketAddr,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let store = Store::open(&cli.db).with_context(|| format!("opening {}", cli.db.display()))?;
+    // read-only and public: browsers on any origin may call it
+    let app = eventum_api::router(store).layer(CorsLayer::permissive());
+
+    let listener = tokio::net::TcpListener::bind(cli.listen)
+        .await
+        .with_context(|| format!("binding {}", cli.listen))?;
+    eprintln!("listening on http://{}", cli.listen);
+    axum::serve(listener, app).await?;
+    Ok(())
+}
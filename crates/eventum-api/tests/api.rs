@@ -0,0 +1,159 @@
+//! Requests against the router over an in-memory store.
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use eventum_client::eventum::events::OutcomesBought;
+use eventum_client::{pda, FeeModel, Market, MarketState, PricingMode, Side, TradeLimits};
+use eventum_indexer::decode::{ProgramEvent, TransactionRecord};
+use eventum_indexer::Store;
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+use tower::ServiceExt;
+
+const ONE_TOKEN: u64 = 1_000_000_000;
+
+fn market_state(creator: Pubkey, resolved: bool) -> MarketState {
+    let address = pda::market_address(&creator, 0).0;
+    MarketState {
+        address,
+        market: Market {
+            creator,
+            oracle_authority: creator,
+            unique_market_id: 0,
+            start_time: 0,
+            end_time: i64::MAX,
+            resolved,
+            winning_outcome: false,
+            question: "Will it rain?".into(),
+            vault_bump: pda::vault_address(&address).1,
+            fee: 100,
+            total_liquidity: 0,
+            yes_mint: pda::yes_mint_address(&address).0,
+            no_mint: pda::no_mint_address(&address).0,
+            yes_tokens: 0,
+            no_tokens: 0,
+            bump: pda::market_address(&creator, 0).1,
+            approved_creator: false,
+            fee_model: FeeModel::Flat,
+            referral_share_bps: 0,
+            pricing_mode: PricingMode::Lmsr { b: 1000 },
+            yes_reserve: 0,
+            no_reserve: 0,
+            limits: TradeLimits::default(),
+            price_oracle: Pubkey::default(),
+            fees_collected: 0,
+        },
+        yes_supply: 20 * ONE_TOKEN,
+        no_supply: 5 * ONE_TOKEN,
+        decimals: 9,
+    }
+}
+
+async fn get(store: Store, uri: &str) -> (StatusCode, Value) {
+    let response = eventum_api::router(store)
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+fn store_with(states: &[MarketState]) -> Store {
+    let mut store = Store::open_in_memory().unwrap();
+    store.sync_markets(states).unwrap();
+    store
+}
+
+/// A store with `state` and one 20-token YES buy by `user` in it.
+fn seeded(state: &MarketState, user: Pubkey) -> Store {
+    let mut store = store_with(std::slice::from_ref(state));
+    let trade = OutcomesBought {
+        market: state.address,
+        user,
+        side: Side::Yes,
+        tokens: 20 * ONE_TOKEN,
+        cost: 11 * ONE_TOKEN,
+        fee: ONE_TOKEN / 10,
+        yes_supply: state.yes_supply,
+        no_supply: state.no_supply,
+        price_yes: state.price_yes().unwrap(),
+    };
+    let record = TransactionRecord {
+        signature: "buy".into(),
+        slot: 1,
+        block_time: Some(1),
+        creations: Vec::new(),
+        events: vec![ProgramEvent::Bought(trade)],
+    };
+    store.apply(&record).unwrap();
+    store
+}
+
+#[tokio::test]
+async fn lists_and_shows_markets() {
+    let state = market_state(Pubkey::new_unique(), false);
+    let user = Pubkey::new_unique();
+
+    let (status, markets) = get(seeded(&state, user), "/markets").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(markets.as_array().unwrap().len(), 1);
+    assert_eq!(markets[0]["address"], state.address.to_string());
+
+    let (status, market) = get(seeded(&state, user), &format!("/markets/{}", state.address)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(market["question"], "Will it rain?");
+    assert_eq!(market["price_yes"], state.price_yes().unwrap());
+    assert_eq!(market["volume"]["trades"], 1);
+    assert_eq!(market["volume"]["volume"], 11 * ONE_TOKEN);
+
+    let (status, _) = get(Store::open_in_memory().unwrap(), &format!("/markets/{}", state.address)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, error) = get(Store::open_in_memory().unwrap(), "/markets/not-a-key").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["error"], "invalid address not-a-key");
+}
+
+#[tokio::test]
+async fn quotes_match_the_client() {
+    let state = market_state(Pubkey::new_unique(), false);
+    let expected = state.quote_buy(Side::No, 7, 0).unwrap();
+
+    let uri = format!("/markets/{}/quote?side=no&qty=7&slippage_bps=100", state.address);
+    let (status, quote) = get(store_with(std::slice::from_ref(&state)), &uri).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(quote["tokens"], 7 * ONE_TOKEN);
+    assert_eq!(quote["cost"], expected.cost);
+    assert_eq!(quote["fee"], expected.fee);
+    assert_eq!(quote["total"], expected.total);
+    assert_eq!(quote["max_cost"], expected.max_cost_with_slippage(100));
+    assert_eq!(quote["price_yes_after"], expected.price_yes_after);
+
+    let uri = format!("/markets/{}/quote?side=maybe&qty=7", state.address);
+    let (status, _) = get(store_with(std::slice::from_ref(&state)), &uri).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // buy_outcomes refuses buys in a resolved market
+    let resolved = market_state(Pubkey::new_unique(), true);
+    let uri = format!("/markets/{}/quote?side=yes&qty=1", resolved.address);
+    let (status, error) = get(store_with(&[resolved]), &uri).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(error["error"].as_str().unwrap().contains("MarketResolved"), "{error}");
+}
+
+#[tokio::test]
+async fn lists_user_positions() {
+    let state = market_state(Pubkey::new_unique(), false);
+    let user = Pubkey::new_unique();
+
+    let (status, positions) = get(seeded(&state, user), &format!("/users/{user}/positions")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(positions.as_array().unwrap().len(), 1);
+    assert_eq!(positions[0]["market"], state.address.to_string());
+    assert_eq!(positions[0]["yes_bought"], 20 * ONE_TOKEN);
+    assert_eq!(positions[0]["spent"], 11 * ONE_TOKEN + ONE_TOKEN / 10);
+
+    let stranger = Pubkey::new_unique();
+    let (_, positions) = get(seeded(&state, user), &format!("/users/{stranger}/positions")).await;
+    assert_eq!(positions, Value::Array(Vec::new()));
+}
@@ -0,0 +1,107 @@
+//! Fetches state, runs the strategy and submits (or simulates) the trades.
+
+use eventum_client::instructions::{self, BuyOptions};
+use eventum_client::names::side_name;
+use eventum_client::{BuyOutcomesArgs, EventumClient};
+use serde::Serialize;
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::error::Result;
+use crate::signal::FairValues;
+use crate::strategy::{decide, Order, Params};
+
+/// What happened to one market in a [`Bot::tick`]. Markets without enough
+/// edge produce no action.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Action {
+    Buy {
+        market: String,
+        side: &'static str,
+        number_of_tokens: u64,
+        price_yes: u64,
+        fair: u64,
+        edge_bps: u64,
+        total: u64,
+        max_cost: u64,
+        result: TradeResult,
+    },
+    Error {
+        market: String,
+        error: String,
+    },
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TradeResult {
+    Sent { signature: String },
+    /// Dry run: the signed transaction was simulated, not sent.
+    Simulated { err: Option<Value>, units_consumed: Option<u64> },
+    Failed { error: String },
+}
+
+pub struct Bot {
+    pub client: EventumClient,
+    pub keypair: Keypair,
+    pub params: Params,
+    /// Simulate trades against the node instead of sending them.
+    pub dry_run: bool,
+}
+
+impl Bot {
+    /// One pass over every market in `fair`, in address order.
+    pub fn tick(&self, fair: &FairValues) -> Result<Vec<Action>> {
+        let now = self.client.rpc.get_unix_timestamp()?;
+        let mut markets: Vec<(&Pubkey, &u64)> = fair.iter().collect();
+        markets.sort();
+        Ok(markets
+            .into_iter()
+            .filter_map(|(market, &fair)| match self.trade(market, fair, now) {
+                Ok(action) => action,
+                Err(e) => Some(Action::Error { market: market.to_string(), error: e.to_string() }),
+            })
+            .collect())
+    }
+
+    fn trade(&self, market: &Pubkey, fair: u64, now: i64) -> Result<Option<Action>> {
+        let state = self.client.market_state(market)?;
+        let Some(order) = decide(&state, fair, &self.params, now) else {
+            return Ok(None);
+        };
+        let Order { side, number_of_tokens, quote, edge_bps } = order;
+        let max_cost = order.max_cost(&self.params);
+        let args = BuyOutcomesArgs { number_of_tokens, side, max_cost };
+        let ix = instructions::buy_outcomes(
+            &self.keypair.pubkey(),
+            market,
+            &state.market,
+            args,
+            BuyOptions::default(),
+        );
+        let result = if self.dry_run {
+            match self.client.simulate(&[ix], &self.keypair, &[]) {
+                Ok(sim) => TradeResult::Simulated { err: sim.err, units_consumed: sim.units_consumed },
+                Err(e) => TradeResult::Failed { error: e.to_string() },
+            }
+        } else {
+            match self.client.send(&[ix], &self.keypair, &[]) {
+                Ok(signature) => TradeResult::Sent { signature: signature.to_string() },
+                Err(e) => TradeResult::Failed { error: e.to_string() },
+            }
+        };
+        Ok(Some(Action::Buy {
+            market: market.to_string(),
+            side: side_name(side),
+            number_of_tokens,
+            price_yes: quote.price_yes_before,
+            fair,
+            edge_bps,
+            total: quote.total,
+            max_cost,
+            result,
+        }))
+    }
+}
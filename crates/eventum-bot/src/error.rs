@@ -0,0 +1,15 @@
+use eventum_client::ClientError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum BotError {
+    #[error(transparent)]
+    Client(#[from] ClientError),
+
+    #[error("reading signal: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("signal line {line}: {message}")]
+    Signal { line: usize, message: String },
+}
+
+pub type Result<T> = std::result::Result<T, BotError>;
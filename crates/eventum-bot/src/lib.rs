@@ -0,0 +1,20 @@
+//! Market-making on Eventum against an external fair value.
+//!
+//! - [`signal`] reads fair YES probabilities per market from a file or stdin.
+//! - [`strategy`] compares them with the market's LMSR price and sizes a buy
+//!   whose expected value beats its cost, fees included, by a margin.
+//! - [`Bot`] fetches market state, runs the strategy and sends the trades,
+//!   or only simulates them in dry-run mode.
+//!
+//! The program only buys, so the bot takes the other side of an overpriced
+//! YES by buying NO instead of selling YES.
+
+pub mod bot;
+pub mod error;
+pub mod signal;
+pub mod strategy;
+
+pub use bot::{Action, Bot, TradeResult};
+pub use error::{BotError, Result};
+pub use signal::{FairValues, FileSignal, Signal, StdinSignal};
+pub use strategy::{decide, Order, Params};
@@ -0,0 +1,96 @@
+//! `eventum-bot`: trade Eventum markets toward an external fair value.
+//! Prints one JSON line per action.
+
+use std::io;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use eventum_bot::{signal, Bot, FileSignal, Params, Signal, StdinSignal};
+use eventum_client::EventumClient;
+use solana_sdk::signature::read_keypair_file;
+
+#[derive(Parser)]
+#[command(name = "eventum-bot", version, about)]
+struct Cli {
+    /// RPC URL
+    #[arg(long, short = 'u', env = "EVENTUM_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    url: String,
+
+    /// Keypair file paying for and receiving the trades [default: ~/.config/solana/id.json]
+    #[arg(long, short = 'k')]
+    keypair: Option<PathBuf>,
+
+    /// Fair values, `<MARKET> <PROBABILITY>` per line; `-` reads stdin
+    #[arg(long, default_value = "-")]
+    signal: String,
+
+    /// Least expected profit, in bps of the amount paid, to trade for
+    #[arg(long, default_value_t = Params::default().min_edge_bps)]
+    min_edge_bps: u16,
+
+    /// Most whole tokens bought per trade
+    #[arg(long, default_value_t = Params::default().max_tokens)]
+    max_tokens: u64,
+
+    /// Price movement tolerated between quote and execution
+    #[arg(long, default_value_t = Params::default().slippage_bps)]
+    slippage_bps: u16,
+
+    /// Seconds between passes
+    #[arg(long, default_value_t = 5)]
+    interval: u64,
+
+    /// Simulate trades against the node instead of sending them
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Make a single pass and exit
+    #[arg(long)]
+    once: bool,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let keypair_path = match cli.keypair {
+        Some(path) => path,
+        None => PathBuf::from(std::env::var("HOME")?).join(".config/solana/id.json"),
+    };
+    let keypair = read_keypair_file(&keypair_path)
+        .map_err(|e| anyhow!("reading keypair {}: {e}", keypair_path.display()))?;
+    let mut signal: Box<dyn Signal> = match cli.signal.as_str() {
+        // a single pass needs the whole signal before it starts
+        "-" if cli.once => Box::new(signal::parse(&io::read_to_string(io::stdin())?)?),
+        "-" => Box::new(StdinSignal::spawn()),
+        path => Box::new(FileSignal { path: path.into() }),
+    };
+    let bot = Bot {
+        client: EventumClient::new(cli.url),
+        keypair,
+        params: Params {
+            min_edge_bps: cli.min_edge_bps,
+            max_tokens: cli.max_tokens,
+            slippage_bps: cli.slippage_bps,
+        },
+        dry_run: cli.dry_run,
+    };
+
+    loop {
+        let fair = signal.fair_values()?;
+        match bot.tick(&fair) {
+            Ok(actions) => {
+                for action in actions {
+                    println!("{}", serde_json::to_string(&action)?);
+                }
+            }
+            Err(e) if !cli.once => eprintln!("pass failed: {e}"),
+            Err(e) => return Err(e.into()),
+        }
+        if cli.once {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_secs(cli.interval));
+    }
+}
@@ -0,0 +1,106 @@
+//! Fair-value signals. Both sources take lines of `<MARKET> <PROBABILITY>`,
+//! the fair probability of YES in `0..=1`; blank lines and lines starting
+//! with `#` are ignored, and a later line for a market replaces an earlier one.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use eventum_client::eventum::states::PRICE_SCALE;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::error::{BotError, Result};
+
+/// Fair YES price per market, scaled by `PRICE_SCALE`.
+pub type FairValues = HashMap<Pubkey, u64>;
+
+pub trait Signal {
+    /// The latest fair value of every market the signal covers.
+    fn fair_values(&mut self) -> Result<FairValues>;
+}
+
+/// Parses one signal line; `None` for blank and comment lines.
+pub fn parse_line(line: &str) -> std::result::Result<Option<(Pubkey, u64)>, String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+    let mut fields = line.split_whitespace();
+    let (Some(market), Some(probability), None) = (fields.next(), fields.next(), fields.next()) else {
+        return Err(format!("expected `<MARKET> <PROBABILITY>`, got `{line}`"));
+    };
+    let market = Pubkey::from_str(market).map_err(|_| format!("invalid market {market}"))?;
+    let probability: f64 = probability
+        .parse()
+        .map_err(|_| format!("invalid probability {probability}"))?;
+    if !(0.0..=1.0).contains(&probability) {
+        return Err(format!("probability {probability} is outside 0..=1"));
+    }
+    Ok(Some((market, (probability * PRICE_SCALE as f64).round() as u64)))
+}
+
+/// Parses a whole signal.
+pub fn parse(text: &str) -> Result<FairValues> {
+    let mut values = FairValues::new();
+    for (i, line) in text.lines().enumerate() {
+        let parsed = parse_line(line).map_err(|message| BotError::Signal { line: i + 1, message })?;
+        values.extend(parsed);
+    }
+    Ok(values)
+}
+
+/// Fixed values, e.g. a signal read once up front.
+impl Signal for FairValues {
+    fn fair_values(&mut self) -> Result<FairValues> {
+        Ok(self.clone())
+    }
+}
+
+/// A file re-read on every poll, for signals written by another process.
+pub struct FileSignal {
+    pub path: PathBuf,
+}
+
+impl Signal for FileSignal {
+    fn fair_values(&mut self) -> Result<FairValues> {
+        parse(&fs::read_to_string(&self.path)?)
+    }
+}
+
+/// Lines streamed on stdin, read on a background thread. Each poll applies
+/// the lines that arrived since the last one.
+pub struct StdinSignal {
+    lines: Receiver<io::Result<String>>,
+    values: FairValues,
+    read: usize,
+}
+
+impl StdinSignal {
+    pub fn spawn() -> Self {
+        let (tx, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines() {
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Self { lines, values: FairValues::new(), read: 0 }
+    }
+}
+
+impl Signal for StdinSignal {
+    fn fair_values(&mut self) -> Result<FairValues> {
+        // a closed stdin keeps the last values
+        while let Ok(line) = self.lines.try_recv() {
+            self.read += 1;
+            let parsed = parse_line(&line?).map_err(|message| BotError::Signal { line: self.read, message })?;
+            self.values.extend(parsed);
+        }
+        Ok(self.values.clone())
+    }
+}
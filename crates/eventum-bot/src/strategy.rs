@@ -0,0 +1,83 @@
+//! When and how much to trade. A winning token pays `PRICE_SCALE` lamports
+//! per whole token, so `n` tokens of a side whose fair probability is `p`
+//! (scaled) are worth `n * p` lamports in expectation. The strategy buys
+//! the side the market underprices, as many tokens as keep that value above
+//! the quoted total (cost plus fee) by `min_edge_bps`.
+
+use eventum_client::eventum::states::PRICE_SCALE;
+use eventum_client::{MarketState, Quote, Side};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Params {
+    /// Least expected profit, in bps of the total paid, worth trading for.
+    pub min_edge_bps: u16,
+    /// Most whole tokens bought in one trade.
+    pub max_tokens: u64,
+    /// How far the price may move against the bot before its trade lands.
+    pub slippage_bps: u16,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self { min_edge_bps: 100, max_tokens: 100, slippage_bps: 50 }
+    }
+}
+
+/// A buy the strategy wants to make.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Order {
+    pub side: Side,
+    pub number_of_tokens: u64,
+    pub quote: Quote,
+    /// Expected profit in bps of `quote.total`.
+    pub edge_bps: u64,
+}
+
+impl Order {
+    pub fn max_cost(&self, params: &Params) -> u64 {
+        self.quote.max_cost_with_slippage(params.slippage_bps)
+    }
+}
+
+/// Expected profit of a fill in bps of what it costs, or `None` if it loses.
+fn edge_bps(fair_side: u64, quote: &Quote) -> Option<u64> {
+    let value = quote.tokens as u128 * fair_side as u128 / PRICE_SCALE as u128;
+    let total = quote.total as u128;
+    (total > 0 && value > total).then(|| ((value - total) * 10000 / total) as u64)
+}
+
+/// The buy to make in `state` given a fair YES price `fair` (scaled by
+/// `PRICE_SCALE`) at cluster time `now`, if any. Resolved and expired
+/// markets are left alone.
+pub fn decide(state: &MarketState, fair: u64, params: &Params, now: i64) -> Option<Order> {
+    if state.market.resolved || now >= state.market.end_time || fair > PRICE_SCALE {
+        return None;
+    }
+    let price = state.price_yes().ok()?;
+    let (side, fair_side) = match fair.cmp(&price) {
+        std::cmp::Ordering::Greater => (Side::Yes, fair),
+        std::cmp::Ordering::Less => (Side::No, PRICE_SCALE - fair),
+        std::cmp::Ordering::Equal => return None,
+    };
+
+    // the average price paid only rises with size, so once a size stops
+    // clearing the edge no larger one will
+    let mut best = None;
+    for number_of_tokens in 1..=params.max_tokens {
+        let Ok(quote) = state.quote_buy(side, number_of_tokens, now) else {
+            // below the market's minimum size, keep growing; past its
+            // maximum or price-move limit, stop
+            if best.is_some() {
+                break;
+            }
+            continue;
+        };
+        match edge_bps(fair_side, &quote) {
+            Some(edge) if edge >= params.min_edge_bps as u64 => {
+                best = Some(Order { side, number_of_tokens, quote, edge_bps: edge });
+            }
+            _ => break,
+        }
+    }
+    best
+}
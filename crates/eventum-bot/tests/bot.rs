@@ -0,0 +1,137 @@
+//! Strategy and signal tests, offline.
+
+use eventum_bot::signal::{parse, parse_line};
+use eventum_bot::{decide, BotError, FileSignal, Params, Signal};
+use eventum_client::eventum::states::PRICE_SCALE;
+use eventum_client::{pda, FeeModel, Market, MarketState, PricingMode, Side, TradeLimits};
+use solana_sdk::pubkey::Pubkey;
+
+const NOW: i64 = 100;
+
+fn market_state() -> MarketState {
+    let creator = Pubkey::new_unique();
+    let address = pda::market_address(&creator, 0).0;
+    MarketState {
+        address,
+        market: Market {
+            creator,
+            oracle_authority: creator,
+            unique_market_id: 0,
+            start_time: 0,
+            end_time: 1_000,
+            resolved: false,
+            winning_outcome: false,
+            question: "Will it rain?".into(),
+            vault_bump: pda::vault_address(&address).1,
+            fee: 100,
+            total_liquidity: 0,
+            yes_mint: pda::yes_mint_address(&address).0,
+            no_mint: pda::no_mint_address(&address).0,
+            yes_tokens: 0,
+            no_tokens: 0,
+            bump: pda::market_address(&creator, 0).1,
+            approved_creator: false,
+            fee_model: FeeModel::Flat,
+            referral_share_bps: 0,
+            pricing_mode: PricingMode::Lmsr { b: 1000 },
+            yes_reserve: 0,
+            no_reserve: 0,
+            limits: TradeLimits::default(),
+            price_oracle: Pubkey::default(),
+            fees_collected: 0,
+        },
+        yes_supply: 0,
+        no_supply: 0,
+        decimals: 9,
+    }
+}
+
+fn fair(probability: f64) -> u64 {
+    (probability * PRICE_SCALE as f64) as u64
+}
+
+#[test]
+fn buys_the_underpriced_side_up_to_the_edge() {
+    let state = market_state();
+    let params = Params { min_edge_bps: 100, max_tokens: 1_000, slippage_bps: 50 };
+
+    let order = decide(&state, fair(0.6), &params, NOW).unwrap();
+    assert_eq!(order.side, Side::Yes);
+    assert!(order.edge_bps >= 100);
+    assert!(order.number_of_tokens > 1 && order.number_of_tokens < 1_000);
+    // one more token would no longer clear the edge
+    let next = state.quote_buy(Side::Yes, order.number_of_tokens + 1, NOW).unwrap();
+    let value = next.tokens as u128 * fair(0.6) as u128 / PRICE_SCALE as u128;
+    assert!(value * 10000 < next.total as u128 * 10100);
+    assert_eq!(order.max_cost(&params), order.quote.max_cost_with_slippage(50));
+
+    let order = decide(&state, fair(0.4), &params, NOW).unwrap();
+    assert_eq!(order.side, Side::No);
+    // same distance from the price on the other side, same size
+    assert_eq!(order.number_of_tokens, decide(&state, fair(0.6), &params, NOW).unwrap().number_of_tokens);
+}
+
+#[test]
+fn size_is_capped_by_max_tokens() {
+    let state = market_state();
+    let params = Params { max_tokens: 5, ..Params::default() };
+    let order = decide(&state, fair(0.9), &params, NOW).unwrap();
+    assert_eq!(order.number_of_tokens, 5);
+    assert_eq!(order.quote.tokens, 5 * PRICE_SCALE);
+}
+
+#[test]
+fn no_trade_without_edge() {
+    let state = market_state();
+    let params = Params::default();
+    // the 1% fee eats a half-percent mispricing
+    assert_eq!(decide(&state, fair(0.505), &params, NOW), None);
+    assert_eq!(decide(&state, fair(0.5), &params, NOW), None);
+
+    let mut resolved = state.clone();
+    resolved.market.resolved = true;
+    assert_eq!(decide(&resolved, fair(0.9), &params, NOW), None);
+    assert_eq!(decide(&state, fair(0.9), &params, state.market.end_time), None);
+}
+
+#[test]
+fn trade_limits_bound_the_size() {
+    let mut state = market_state();
+    state.market.limits = TradeLimits { min_tokens: 3 * PRICE_SCALE, max_tokens: 8 * PRICE_SCALE, max_price_move_bps: 0 };
+    let order = decide(&state, fair(0.9), &Params::default(), NOW).unwrap();
+    assert_eq!(order.number_of_tokens, 8);
+}
+
+#[test]
+fn signals_parse_lines() {
+    let a = Pubkey::new_unique();
+    let b = Pubkey::new_unique();
+    assert_eq!(parse_line("  # comment").unwrap(), None);
+    assert_eq!(parse_line("").unwrap(), None);
+    assert_eq!(parse_line(&format!("{a} 0.25")).unwrap(), Some((a, PRICE_SCALE / 4)));
+    assert!(parse_line(&format!("{a} 1.5")).is_err());
+    assert!(parse_line(&format!("{a}")).is_err());
+    assert!(parse_line("nope 0.5").is_err());
+
+    let values = parse(&format!("{a} 0.1\n\n{b} 0.2\n{a} 0.3\n")).unwrap();
+    assert_eq!(values.len(), 2);
+    assert_eq!(values[&a], fair(0.3));
+    assert_eq!(values[&b], fair(0.2));
+
+    let err = parse(&format!("{a} 0.1\n{b} x\n")).unwrap_err();
+    assert!(matches!(err, BotError::Signal { line: 2, .. }), "{err}");
+}
+
+#[test]
+fn file_signals_are_reread() {
+    let market = Pubkey::new_unique();
+    let path = std::env::temp_dir().join(format!("eventum-bot-signal-{market}"));
+    let mut signal = FileSignal { path: path.clone() };
+
+    std::fs::write(&path, format!("{market} 0.7\n")).unwrap();
+    assert_eq!(signal.fair_values().unwrap()[&market], fair(0.7));
+    std::fs::write(&path, format!("{market} 0.2\n")).unwrap();
+    assert_eq!(signal.fair_values().unwrap()[&market], fair(0.2));
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(signal.fair_values(), Err(BotError::Io(_))));
+}
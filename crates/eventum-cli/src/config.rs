@@ -0,0 +1,55 @@
+//! Defaults from the Solana CLI config (`solana config set ...`), so the
+//! tool talks to the same cluster with the same wallet as `solana`.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+const DEFAULT_RPC_URL: &str = "http://127.0.0.1:8899";
+
+#[derive(Default, Deserialize)]
+struct SolanaConfig {
+    json_rpc_url: Option<String>,
+    keypair_path: Option<String>,
+}
+
+pub struct Config {
+    pub rpc_url: String,
+    pub keypair_path: PathBuf,
+}
+
+fn home() -> PathBuf {
+    std::env::var_os("HOME").map(PathBuf::from).unwrap_or_default()
+}
+
+fn default_config_file() -> PathBuf {
+    home().join(".config/solana/cli/config.yml")
+}
+
+impl Config {
+    /// Reads `config_file` (or the Solana CLI default), then applies the
+    /// command-line overrides. A missing default file is not an error.
+    pub fn load(
+        config_file: Option<PathBuf>,
+        url: Option<String>,
+        keypair: Option<PathBuf>,
+    ) -> Result<Self> {
+        let explicit = config_file.is_some();
+        let path = config_file.unwrap_or_else(default_config_file);
+        let solana: SolanaConfig = match std::fs::read_to_string(&path) {
+            Ok(text) => serde_yaml::from_str(&text)
+                .with_context(|| format!("parsing {}", path.display()))?,
+            Err(_) if !explicit => SolanaConfig::default(),
+            Err(e) => return Err(e).with_context(|| format!("reading {}", path.display())),
+        };
+        Ok(Self {
+            rpc_url: url
+                .or(solana.json_rpc_url)
+                .unwrap_or_else(|| DEFAULT_RPC_URL.into()),
+            keypair_path: keypair
+                .or(solana.keypair_path.map(PathBuf::from))
+                .unwrap_or_else(|| home().join(".config/solana/id.json")),
+        })
+    }
+}
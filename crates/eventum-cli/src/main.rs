@@ -0,0 +1,370 @@
+//! `eventum-cli`: create, inspect, trade, resolve and close Eventum markets
+//! from the shell, using the wallet and cluster of the Solana CLI config.
+
+mod config;
+mod output;
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use eventum_client::instructions::{self, BuyOptions};
+use eventum_client::names::{parse_fee_model, parse_pricing_mode, parse_side, side_name};
+use eventum_client::{
+    pda, BuyOutcomesArgs, EventumClient, FeeModel, InitializeMarketArgs, PricingMode, Side,
+    TradeLimits,
+};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+
+use config::Config;
+use output::{print, print_rows, Format, MarketRow, MarketView, QuoteView, Sent};
+
+#[derive(Parser)]
+#[command(name = "eventum-cli", version, about)]
+struct Cli {
+    /// Solana CLI config file [default: ~/.config/solana/cli/config.yml]
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// RPC URL, overriding the config's `json_rpc_url`
+    #[arg(long, short = 'u', global = true)]
+    url: Option<String>,
+
+    /// Signer keypair file, overriding the config's `keypair_path`
+    #[arg(long, short = 'k', global = true)]
+    keypair: Option<PathBuf>,
+
+    /// Output format
+    #[arg(long, short = 'o', global = true, value_enum, default_value_t)]
+    output: Format,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a market with the signer as creator
+    Create {
+        #[arg(long)]
+        question: String,
+        /// Close time as a unix timestamp
+        #[arg(long, conflicts_with = "duration", required_unless_present = "duration")]
+        end_time: Option<i64>,
+        /// Close time relative to now, e.g. `90m`, `12h`, `3d`
+        #[arg(long, value_parser = parse_duration)]
+        duration: Option<i64>,
+        /// Fee in bps
+        #[arg(long, default_value_t = 100)]
+        fee: u32,
+        /// `flat`, `price-dependent` or `time-increasing`
+        #[arg(long, value_parser = parse_fee_model, default_value = "flat")]
+        fee_model: FeeModel,
+        /// `lmsr:<b>`, `ls-lmsr:<alpha_bps>:<min_b>` or `cpmm:<seed lamports>`;
+        /// `b` and `min_b` are in whole tokens and the creator deposits
+        /// `b · ln 2` SOL (`min_b · ln 2` for ls-lmsr)
+        #[arg(long, value_parser = parse_pricing_mode, default_value = "lmsr:10")]
+        pricing: PricingMode,
+        /// Share of fees paid to referrers, in bps
+        #[arg(long, default_value_t = 0)]
+        referral_share_bps: u16,
+        /// Who may resolve the market [default: the signer]
+        #[arg(long)]
+        oracle: Option<Pubkey>,
+        /// Smallest buy, in whole tokens (0 = no limit)
+        #[arg(long, default_value_t = 0)]
+        min_tokens: u64,
+        /// Largest buy, in whole tokens (0 = no limit)
+        #[arg(long, default_value_t = 0)]
+        max_tokens: u64,
+        /// Largest YES price move one trade may cause, in bps (0 = no limit)
+        #[arg(long, default_value_t = 0)]
+        max_price_move_bps: u16,
+        /// Pass the signer's creator approval (needed while permissioned)
+        #[arg(long)]
+        approved: bool,
+    },
+    /// Show one market with its supplies and current price
+    Show { market: Pubkey },
+    /// Price a buy without sending it
+    Quote {
+        market: Pubkey,
+        /// `yes` or `no`
+        #[arg(value_parser = parse_side)]
+        side: Side,
+        /// Whole tokens
+        tokens: u64,
+    },
+    /// Buy outcome tokens at the quoted price plus a slippage allowance
+    Buy {
+        market: Pubkey,
+        /// `yes` or `no`
+        #[arg(value_parser = parse_side)]
+        side: Side,
+        /// Whole tokens
+        tokens: u64,
+        #[arg(long, default_value_t = 50)]
+        slippage_bps: u16,
+        /// Record the buy on the signer's (already opened) position
+        #[arg(long)]
+        position: bool,
+        /// Authority of a registered referrer
+        #[arg(long)]
+        referrer: Option<Pubkey>,
+    },
+    /// Resolve a market; the signer must be its oracle authority
+    Resolve {
+        market: Pubkey,
+        /// `yes` or `no`
+        #[arg(value_parser = parse_side)]
+        outcome: Side,
+    },
+    /// Redeem the signer's winning tokens
+    Claim {
+        market: Pubkey,
+        /// Record the payout on the signer's position
+        #[arg(long)]
+        position: bool,
+    },
+    /// Close a resolved market and sweep its vault; the signer must be the creator
+    Close { market: Pubkey },
+    /// List markets, optionally by creator
+    List {
+        #[arg(long)]
+        creator: Option<Pubkey>,
+    },
+}
+
+fn parse_duration(s: &str) -> Result<i64, String> {
+    let unit_at = s.char_indices().last().map_or(0, |(i, _)| i);
+    let (digits, unit) = s.split_at(unit_at);
+    let scale = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(format!("`{s}`: expected a number followed by s, m, h or d")),
+    };
+    // unsigned, so a duration can't put the end time in the past
+    let n: u64 = digits.parse().map_err(|e| format!("`{s}`: {e}"))?;
+    i64::try_from(n)
+        .ok()
+        .and_then(|n| n.checked_mul(scale))
+        .ok_or_else(|| format!("`{s}` is too long"))
+}
+
+struct Ctx {
+    client: EventumClient,
+    keypair_path: PathBuf,
+    format: Format,
+}
+
+impl Ctx {
+    fn signer(&self) -> Result<Keypair> {
+        read_keypair_file(&self.keypair_path)
+            .map_err(|e| anyhow::anyhow!("reading keypair {}: {e}", self.keypair_path.display()))
+    }
+
+    fn sent(&self, signature: solana_sdk::signature::Signature, market: Option<&Pubkey>) -> Result<()> {
+        print(
+            self.format,
+            &Sent {
+                signature: signature.to_string(),
+                market: market.map(Pubkey::to_string),
+            },
+        )
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let config = Config::load(cli.config, cli.url, cli.keypair)?;
+    let ctx = Ctx {
+        client: EventumClient::new(config.rpc_url),
+        keypair_path: config.keypair_path,
+        format: cli.output,
+    };
+    let client = &ctx.client;
+
+    match cli.command {
+        Command::Create {
+            question,
+            end_time,
+            duration,
+            fee,
+            fee_model,
+            pricing,
+            referral_share_bps,
+            oracle,
+            min_tokens,
+            max_tokens,
+            max_price_move_bps,
+            approved,
+        } => {
+            let signer = ctx.signer()?;
+            let creator = signer.pubkey();
+            let end_time = match (end_time, duration) {
+                (Some(end_time), _) => end_time,
+                (None, Some(duration)) => client.rpc.get_unix_timestamp()? + duration,
+                (None, None) => bail!("one of --end-time or --duration is required"),
+            };
+            // outcome mints use 9 decimals, like SOL
+            let raw = |tokens: u64| tokens.checked_mul(1_000_000_000).context("token amount too large");
+            let args = InitializeMarketArgs {
+                unique_market_id: client.next_market_id(&creator)?,
+                end_time,
+                fee,
+                fee_model,
+                referral_share_bps,
+                pricing_mode: pricing,
+                limits: TradeLimits {
+                    min_tokens: raw(min_tokens)?,
+                    max_tokens: raw(max_tokens)?,
+                    max_price_move_bps,
+                },
+                question,
+            };
+            let market = pda::market_address(&creator, args.unique_market_id).0;
+            let oracle = oracle.unwrap_or(creator);
+            let ix = instructions::initialize_market(&creator, &oracle, approved, args);
+            let signature = client.send(&[ix], &signer, &[])?;
+            ctx.sent(signature, Some(&market))
+        }
+        Command::Show { market } => {
+            let state = client.market_state(&market)?;
+            let vault = client.rpc.get_balance(&pda::vault_address(&market).0)?;
+            print(ctx.format, &MarketView::new(&state, state.price_yes()?, vault))
+        }
+        Command::Quote { market, side, tokens } => {
+            let state = client.market_state(&market)?;
+            let now = client.rpc.get_unix_timestamp()?;
+            let quote = state.quote_buy(side, tokens, now)?;
+            print(ctx.format, &QuoteView::new(side_name(side), &quote))
+        }
+        Command::Buy { market, side, tokens, slippage_bps, position, referrer } => {
+            let signer = ctx.signer()?;
+            let state = client.market_state(&market)?;
+            let now = client.rpc.get_unix_timestamp()?;
+            let quote = state.quote_buy(side, tokens, now)?;
+            let args = BuyOutcomesArgs {
+                number_of_tokens: tokens,
+                side,
+                max_cost: quote.max_cost_with_slippage(slippage_bps),
+            };
+            let options = BuyOptions { position, referrer };
+            let ix = instructions::buy_outcomes(&signer.pubkey(), &market, &state.market, args, options);
+            let signature = client.send(&[ix], &signer, &[])?;
+            ctx.sent(signature, Some(&market))
+        }
+        Command::Resolve { market, outcome } => {
+            let signer = ctx.signer()?;
+            let ix = instructions::resolve_market(&signer.pubkey(), &market, outcome);
+            let signature = client.send(&[ix], &signer, &[])?;
+            ctx.sent(signature, Some(&market))
+        }
+        Command::Claim { market, position } => {
+            let signer = ctx.signer()?;
+            let ix = instructions::claim_winnings(&signer.pubkey(), &market, position);
+            let signature = client.send(&[ix], &signer, &[])?;
+            ctx.sent(signature, Some(&market))
+        }
+        Command::Close { market } => {
+            let signer = ctx.signer()?;
+            let state = client.market(&market)?;
+            if state.creator != signer.pubkey() {
+                bail!("only the creator {} can close this market", state.creator);
+            }
+            let ix = instructions::close_market(&signer.pubkey(), &market);
+            let signature = client.send(&[ix], &signer, &[])?;
+            ctx.sent(signature, Some(&market))
+        }
+        Command::List { creator } => {
+            let mut markets = client.markets(creator.as_ref())?;
+            markets.sort_by_key(|(_, m)| (m.creator, m.unique_market_id));
+            let rows: Vec<MarketRow> = markets.iter().map(|(a, m)| MarketRow::new(a, m)).collect();
+            print_rows(ctx.format, &rows)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create(extra: &[&str]) -> Result<Command, clap::Error> {
+        let args = ["eventum-cli", "create", "--question", "Will it rain?", "--duration", "1d"];
+        Cli::try_parse_from(args.iter().chain(extra)).map(|cli| cli.command)
+    }
+
+    fn pricing(extra: &[&str]) -> Result<PricingMode, clap::Error> {
+        match create(extra)? {
+            Command::Create { pricing, .. } => Ok(pricing),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn durations_scale_by_unit() {
+        assert_eq!(parse_duration("45s"), Ok(45));
+        assert_eq!(parse_duration("90m"), Ok(90 * 60));
+        assert_eq!(parse_duration("12h"), Ok(12 * 60 * 60));
+        assert_eq!(parse_duration("7d"), Ok(7 * 24 * 60 * 60));
+        assert_eq!(parse_duration("0s"), Ok(0));
+    }
+
+    #[test]
+    fn durations_reject_bad_input() {
+        for bad in ["", "d", "7", "7w", "7 d", "7D", "-7d", "1.5h", "7µ", "µ"] {
+            assert!(parse_duration(bad).is_err(), "{bad}");
+        }
+        // overflows i64 once scaled, and u64 before that
+        assert!(parse_duration("106751991167301d").is_err());
+        assert!(parse_duration("99999999999999999999s").is_err());
+        assert_eq!(parse_duration("106751991167300d"), Ok(106_751_991_167_300 * 86_400));
+    }
+
+    #[test]
+    fn pricing_defaults_to_lmsr_with_b_of_ten() {
+        assert_eq!(pricing(&[]).unwrap(), PricingMode::Lmsr { b: 10 });
+    }
+
+    #[test]
+    fn pricing_takes_each_mode_with_its_parameters() {
+        assert_eq!(pricing(&["--pricing", "lmsr:250"]).unwrap(), PricingMode::Lmsr { b: 250 });
+        assert_eq!(
+            pricing(&["--pricing", "ls-lmsr:500:20"]).unwrap(),
+            PricingMode::LsLmsr { alpha_bps: 500, min_b: 20 }
+        );
+        assert_eq!(
+            pricing(&["--pricing", "cpmm:1000000000"]).unwrap(),
+            PricingMode::Cpmm { seed_liquidity: 1_000_000_000 }
+        );
+    }
+
+    #[test]
+    fn pricing_rejects_missing_fields_and_overflow() {
+        for bad in [
+            "lmsr",
+            "lmsr:",
+            "ls-lmsr",
+            "ls-lmsr:500",
+            "ls-lmsr:500:",
+            "ls-lmsr::20",
+            "cpmm",
+            "lmsr:18446744073709551616",
+            "ls-lmsr:65536:20",
+            "lmsr:-1",
+            "amm:10",
+        ] {
+            assert!(pricing(&["--pricing", bad]).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn end_time_and_duration_are_exclusive() {
+        assert!(create(&["--end-time", "1700000000"]).is_err());
+        let args = ["eventum-cli", "create", "--question", "Will it rain?"];
+        assert!(Cli::try_parse_from(args).is_err());
+    }
+}
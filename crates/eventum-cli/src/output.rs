@@ -0,0 +1,178 @@
+//! What each command prints, as JSON or as a plain table.
+
+use clap::ValueEnum;
+use eventum_client::eventum::states::PRICE_SCALE;
+use eventum_client::names::{fee_model_name, outcome_name, pricing_mode_name};
+use eventum_client::{Market, MarketState, Quote};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum Format {
+    #[default]
+    Table,
+    Json,
+}
+
+fn price(scaled: u64) -> f64 {
+    scaled as f64 / PRICE_SCALE as f64
+}
+
+#[derive(Serialize)]
+pub struct MarketView {
+    pub address: String,
+    pub id: u64,
+    pub question: String,
+    pub creator: String,
+    pub oracle_authority: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub resolved: bool,
+    pub outcome: Option<&'static str>,
+    pub fee_bps: u32,
+    pub fee_model: &'static str,
+    pub pricing_mode: String,
+    pub yes_supply: u64,
+    pub no_supply: u64,
+    pub price_yes: f64,
+    pub vault_lamports: u64,
+}
+
+impl MarketView {
+    pub fn new(state: &MarketState, price_yes: u64, vault_lamports: u64) -> Self {
+        let m = &state.market;
+        Self {
+            address: state.address.to_string(),
+            id: m.unique_market_id,
+            question: m.question.clone(),
+            creator: m.creator.to_string(),
+            oracle_authority: m.oracle_authority.to_string(),
+            start_time: m.start_time,
+            end_time: m.end_time,
+            resolved: m.resolved,
+            outcome: outcome_name(m),
+            fee_bps: m.fee,
+            fee_model: fee_model_name(m.fee_model),
+            pricing_mode: pricing_mode_name(m.pricing_mode),
+            yes_supply: state.yes_supply,
+            no_supply: state.no_supply,
+            price_yes: price(price_yes),
+            vault_lamports,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct MarketRow {
+    pub address: String,
+    pub id: u64,
+    pub end_time: i64,
+    pub outcome: Option<&'static str>,
+    pub question: String,
+}
+
+impl MarketRow {
+    pub fn new(address: &Pubkey, m: &Market) -> Self {
+        Self {
+            address: address.to_string(),
+            id: m.unique_market_id,
+            end_time: m.end_time,
+            outcome: outcome_name(m),
+            question: m.question.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct QuoteView {
+    pub side: &'static str,
+    pub tokens: u64,
+    pub cost: u64,
+    pub fee: u64,
+    pub total: u64,
+    pub price_yes_before: f64,
+    pub price_yes_after: f64,
+}
+
+impl QuoteView {
+    pub fn new(side: &'static str, quote: &Quote) -> Self {
+        Self {
+            side,
+            tokens: quote.tokens,
+            cost: quote.cost,
+            fee: quote.fee,
+            total: quote.total,
+            price_yes_before: price(quote.price_yes_before),
+            price_yes_after: price(quote.price_yes_after),
+        }
+    }
+}
+
+/// Result of a command that sent a transaction.
+#[derive(Serialize)]
+pub struct Sent {
+    pub signature: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market: Option<String>,
+}
+
+/// Prints one record: pretty JSON, or `key  value` lines.
+pub fn print<T: Serialize>(format: Format, value: &T) -> anyhow::Result<()> {
+    let json = serde_json::to_value(value)?;
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(&json)?),
+        Format::Table => {
+            let fields = json.as_object().into_iter().flatten();
+            let width = fields.clone().map(|(k, _)| k.len()).max().unwrap_or(0);
+            for (key, value) in fields {
+                println!("{key:width$}  {}", cell(value));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Prints many records: a JSON array, or a table with a header row.
+pub fn print_rows<T: Serialize>(format: Format, rows: &[T]) -> anyhow::Result<()> {
+    let json = serde_json::to_value(rows)?;
+    let rows = json.as_array().cloned().unwrap_or_default();
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(&rows)?),
+        Format::Table => {
+            let Some(first) = rows.first().and_then(|r| r.as_object()) else {
+                return Ok(());
+            };
+            let headers: Vec<&String> = first.keys().collect();
+            let cells: Vec<Vec<String>> = rows
+                .iter()
+                .map(|row| headers.iter().map(|h| cell(&row[h.as_str()])).collect())
+                .collect();
+            let widths: Vec<usize> = headers
+                .iter()
+                .enumerate()
+                .map(|(i, h)| cells.iter().map(|r| r[i].len()).max().unwrap_or(0).max(h.len()))
+                .collect();
+            let line = |values: Vec<&str>| {
+                let padded: Vec<String> = values
+                    .iter()
+                    .zip(&widths)
+                    .map(|(v, w)| format!("{v:w$}"))
+                    .collect();
+                println!("{}", padded.join("  ").trim_end());
+            };
+            line(headers.iter().map(|h| h.as_str()).collect());
+            for row in &cells {
+                line(row.iter().map(String::as_str).collect());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn cell(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => "-".into(),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,193 @@
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anchor_spl::token::Mint;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+use eventum::pda;
+use eventum::states::{Market, MarketMetadata, OptimisticResolution, Position, ResolutionVotes};
+
+use crate::error::{ClientError, Result};
+use crate::quote::MarketState;
+use crate::rpc::{RpcClient, Simulation};
+
+/// Offset of `Market.creator`, the first field after the discriminator.
+const MARKET_CREATOR_OFFSET: usize = 8;
+
+/// Offset of `Market.oracle_authority`, right after `creator`.
+const MARKET_ORACLE_OFFSET: usize = MARKET_CREATOR_OFFSET + 32;
+
+/// Reads Eventum accounts and submits transactions over JSON-RPC.
+pub struct EventumClient {
+    pub rpc: RpcClient,
+}
+
+fn decode<T: AccountDeserialize>(address: &Pubkey, data: Option<Vec<u8>>) -> Result<T> {
+    let data = data.ok_or(ClientError::AccountNotFound(*address))?;
+    Ok(T::try_deserialize(&mut data.as_slice())?)
+}
+
+fn memcmp(offset: usize, bytes: &[u8]) -> Value {
+    json!({ "memcmp": { "offset": offset, "bytes": BASE64_STANDARD.encode(bytes), "encoding": "base64" } })
+}
+
+impl EventumClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { rpc: RpcClient::new(url) }
+    }
+
+    pub fn market(&self, address: &Pubkey) -> Result<Market> {
+        decode(address, self.rpc.get_account_data(address)?)
+    }
+
+    /// The market and both mint supplies, fetched in one round trip.
+    pub fn market_state(&self, address: &Pubkey) -> Result<MarketState> {
+        let yes_mint = pda::yes_mint_address(address).0;
+        let no_mint = pda::no_mint_address(address).0;
+        let mut accounts = self
+            .rpc
+            .get_multiple_accounts(&[*address, yes_mint, no_mint])?
+            .into_iter();
+        let market: Market = decode(address, accounts.next().flatten())?;
+        let yes: Mint = decode(&yes_mint, accounts.next().flatten())?;
+        let no: Mint = decode(&no_mint, accounts.next().flatten())?;
+        Ok(MarketState {
+            address: *address,
+            market,
+            yes_supply: yes.supply,
+            no_supply: no.supply,
+            decimals: yes.decimals,
+        })
+    }
+
+    pub fn metadata(&self, market: &Pubkey) -> Result<Option<MarketMetadata>> {
+        let address = pda::metadata_address(market).0;
+        self.rpc
+            .get_account_data(&address)?
+            .map(|data| decode(&address, Some(data)))
+            .transpose()
+    }
+
+    pub fn position(&self, market: &Pubkey, owner: &Pubkey) -> Result<Option<Position>> {
+        let address = pda::position_address(market, owner).0;
+        self.rpc
+            .get_account_data(&address)?
+            .map(|data| decode(&address, Some(data)))
+            .transpose()
+    }
+
+    /// The market's resolver committee, if it has one.
+    pub fn resolution_votes(&self, market: &Pubkey) -> Result<Option<ResolutionVotes>> {
+        let address = pda::resolution_votes_address(market).0;
+        self.rpc
+            .get_account_data(&address)?
+            .map(|data| decode(&address, Some(data)))
+            .transpose()
+    }
+
+    /// The market's optimistic resolution settings and proposal, if it has them.
+    pub fn optimistic_resolution(&self, market: &Pubkey) -> Result<Option<OptimisticResolution>> {
+        let address = pda::optimistic_resolution_address(market).0;
+        self.rpc
+            .get_account_data(&address)?
+            .map(|data| decode(&address, Some(data)))
+            .transpose()
+    }
+
+    /// Every market, optionally only those created by `creator`.
+    pub fn markets(&self, creator: Option<&Pubkey>) -> Result<Vec<(Pubkey, Market)>> {
+        let mut filters = vec![memcmp(0, Market::DISCRIMINATOR)];
+        if let Some(creator) = creator {
+            filters.push(memcmp(MARKET_CREATOR_OFFSET, creator.as_ref()));
+        }
+        self.rpc
+            .get_program_accounts(&eventum::ID, filters)?
+            .into_iter()
+            .map(|(address, data)| Ok((address, decode(&address, Some(data))?)))
+            .collect()
+    }
+
+    /// Every market `oracle` resolves.
+    pub fn markets_for_oracle(&self, oracle: &Pubkey) -> Result<Vec<(Pubkey, Market)>> {
+        let filters = vec![
+            memcmp(0, Market::DISCRIMINATOR),
+            memcmp(MARKET_ORACLE_OFFSET, oracle.as_ref()),
+        ];
+        self.rpc
+            .get_program_accounts(&eventum::ID, filters)?
+            .into_iter()
+            .map(|(address, data)| Ok((address, decode(&address, Some(data))?)))
+            .collect()
+    }
+
+    /// `markets` with their mint supplies, for quoting many markets at once.
+    pub fn market_states(&self, creator: Option<&Pubkey>) -> Result<Vec<MarketState>> {
+        let markets = self.markets(creator)?;
+        let mints: Vec<Pubkey> = markets
+            .iter()
+            .flat_map(|(_, market)| [market.yes_mint, market.no_mint])
+            .collect();
+        let mut accounts = self.rpc.get_multiple_accounts(&mints)?.into_iter();
+        markets
+            .into_iter()
+            .map(|(address, market)| {
+                let yes: Mint = decode(&market.yes_mint, accounts.next().flatten())?;
+                let no: Mint = decode(&market.no_mint, accounts.next().flatten())?;
+                Ok(MarketState {
+                    address,
+                    market,
+                    yes_supply: yes.supply,
+                    no_supply: no.supply,
+                    decimals: yes.decimals,
+                })
+            })
+            .collect()
+    }
+
+    /// The id `creator`'s next market must use.
+    pub fn next_market_id(&self, creator: &Pubkey) -> Result<u64> {
+        let address = pda::creator_profile_address(creator).0;
+        match self.rpc.get_account_data(&address)? {
+            Some(data) => {
+                let profile: eventum::states::CreatorProfile = decode(&address, Some(data))?;
+                Ok(profile.market_count)
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// Signs `instructions` with `payer` plus any extra `signers` and waits
+    /// for confirmation.
+    pub fn send(
+        &self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> Result<Signature> {
+        let transaction = self.sign(instructions, payer, signers)?;
+        self.rpc.send_and_confirm_transaction(&transaction)
+    }
+
+    /// Signs `instructions` like `send` and simulates them instead.
+    pub fn simulate(
+        &self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> Result<Simulation> {
+        let transaction = self.sign(instructions, payer, signers)?;
+        self.rpc.simulate_transaction(&transaction)
+    }
+
+    fn sign(&self, instructions: &[Instruction], payer: &Keypair, signers: &[&Keypair]) -> Result<Transaction> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let mut all: Vec<&Keypair> = vec![payer];
+        all.extend(signers.iter().copied().filter(|s| s.pubkey() != payer.pubkey()));
+        let mut transaction = Transaction::new_with_payer(instructions, Some(&payer.pubkey()));
+        transaction.try_sign(&all, blockhash)?;
+        Ok(transaction)
+    }
+}
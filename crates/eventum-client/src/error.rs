@@ -0,0 +1,34 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::SignerError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("http request failed: {0}")]
+    Http(#[from] ureq::Error),
+
+    #[error("rpc error {code}: {message}")]
+    Rpc { code: i64, message: String },
+
+    #[error("unexpected rpc response: {0}")]
+    Decode(String),
+
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+
+    /// An account failed to deserialize, or a quote was rejected by the
+    /// same checks `buy_outcomes` runs on-chain.
+    #[error(transparent)]
+    Program(#[from] anchor_lang::error::Error),
+
+    #[error("transaction {signature} failed: {err}")]
+    TransactionFailed { signature: Signature, err: String },
+
+    #[error("transaction {0} was not confirmed in time")]
+    Timeout(Signature),
+
+    #[error(transparent)]
+    Signer(#[from] SignerError),
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
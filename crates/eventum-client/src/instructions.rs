@@ -0,0 +1,465 @@
+//! Instruction builders. Each derives the PDAs and ATAs the program checks,
+//! so callers only supply the signers and the arguments.
+
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::token;
+use eventum::states::Market;
+use eventum::{
+    accounts, instruction, pda, BuyOutcomesArgs, InitializeMarketArgs, SetMarketMetadataArgs,
+    Side, TradeLeg,
+};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: eventum::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Optional accounts for `buy_outcomes`. The market's price oracle is not
+/// listed here: it is passed whenever the market has one, since the program
+/// then requires it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BuyOptions {
+    /// Update the buyer's `Position` (it must have been opened).
+    pub position: bool,
+    /// Authority of a registered referrer to share the fee with.
+    pub referrer: Option<Pubkey>,
+}
+
+/// `initialize_config`; `admin` must be the program's upgrade authority.
+pub fn initialize_config(admin: &Pubkey, permissioned: bool) -> Instruction {
+    let program_data =
+        Pubkey::find_program_address(&[eventum::ID.as_ref()], &bpf_loader_upgradeable::ID).0;
+    build(
+        accounts::InitializeConfig {
+            admin: *admin,
+            config: pda::config_address().0,
+            program: eventum::ID,
+            program_data,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeConfig { permissioned },
+    )
+}
+
+pub fn set_permissioned(admin: &Pubkey, permissioned: bool) -> Instruction {
+    build(
+        accounts::SetPermissioned {
+            admin: *admin,
+            config: pda::config_address().0,
+        },
+        instruction::SetPermissioned { permissioned },
+    )
+}
+
+pub fn approve_creator(admin: &Pubkey, creator: &Pubkey) -> Instruction {
+    build(
+        accounts::ApproveCreator {
+            admin: *admin,
+            config: pda::config_address().0,
+            creator: *creator,
+            creator_approval: pda::approval_address(creator).0,
+            system_program: system_program::ID,
+        },
+        instruction::ApproveCreator {},
+    )
+}
+
+pub fn revoke_creator(admin: &Pubkey, creator: &Pubkey) -> Instruction {
+    build(
+        accounts::RevokeCreator {
+            admin: *admin,
+            config: pda::config_address().0,
+            creator: *creator,
+            creator_approval: pda::approval_address(creator).0,
+        },
+        instruction::RevokeCreator {},
+    )
+}
+
+/// `initialize_market`. Set `approved` when the creator holds a
+/// `CreatorApproval`, which is required while the config is permissioned.
+/// `args.unique_market_id` must be the creator's `CreatorProfile.market_count`.
+pub fn initialize_market(
+    creator: &Pubkey,
+    oracle_authority: &Pubkey,
+    approved: bool,
+    args: InitializeMarketArgs,
+) -> Instruction {
+    let market = pda::market_address(creator, args.unique_market_id).0;
+    build(
+        accounts::InitializeMarket {
+            creator: *creator,
+            config: pda::config_address().0,
+            creator_approval: approved.then(|| pda::approval_address(creator).0),
+            oracle_authority: *oracle_authority,
+            creator_profile: pda::creator_profile_address(creator).0,
+            market,
+            yes_mint: pda::yes_mint_address(&market).0,
+            no_mint: pda::no_mint_address(&market).0,
+            vault: pda::vault_address(&market).0,
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        instruction::InitializeMarket { args },
+    )
+}
+
+pub fn set_market_metadata(creator: &Pubkey, address: &Pubkey, args: SetMarketMetadataArgs) -> Instruction {
+    build(
+        accounts::SetMarketMetadata {
+            creator: *creator,
+            market: *address,
+            metadata: pda::metadata_address(address).0,
+            system_program: system_program::ID,
+        },
+        instruction::SetMarketMetadata { args },
+    )
+}
+
+pub fn open_position(user: &Pubkey, address: &Pubkey) -> Instruction {
+    build(
+        accounts::OpenPosition {
+            user: *user,
+            market: *address,
+            position: pda::position_address(address, user).0,
+            system_program: system_program::ID,
+        },
+        instruction::OpenPosition {},
+    )
+}
+
+pub fn init_price_oracle(creator: &Pubkey, address: &Pubkey) -> Instruction {
+    build(
+        accounts::InitPriceOracle {
+            creator: *creator,
+            market: *address,
+            yes_mint: pda::yes_mint_address(address).0,
+            no_mint: pda::no_mint_address(address).0,
+            price_oracle: pda::price_oracle_address(address).0,
+            system_program: system_program::ID,
+        },
+        instruction::InitPriceOracle {},
+    )
+}
+
+/// `buy_outcomes` on the market at `address`, whose current state is
+/// `market`.
+pub fn buy_outcomes(
+    user: &Pubkey,
+    address: &Pubkey,
+    market: &Market,
+    args: BuyOutcomesArgs,
+    options: BuyOptions,
+) -> Instruction {
+    build(
+        accounts::BuyOutcomes {
+            user: *user,
+            market: *address,
+            yes_mint: market.yes_mint,
+            no_mint: market.no_mint,
+            vault: pda::vault_address(address).0,
+            user_yes_ata: get_associated_token_address(user, &market.yes_mint),
+            user_no_ata: get_associated_token_address(user, &market.no_mint),
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            position: options.position.then(|| pda::position_address(address, user).0),
+            referrer: options.referrer.map(|authority| pda::referrer_address(&authority).0),
+            price_oracle: (market.price_oracle != Pubkey::default()).then_some(market.price_oracle),
+        },
+        instruction::BuyOutcomes { args },
+    )
+}
+
+/// `batch_trade` over `(address, market, leg)` triples, in order. The
+/// user's ATA for each bought side must already exist.
+pub fn batch_trade(
+    user: &Pubkey,
+    legs: &[(Pubkey, &Market, TradeLeg)],
+    referrer: Option<Pubkey>,
+) -> Instruction {
+    let mut ix = build(
+        accounts::BatchTrade {
+            user: *user,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            referrer: referrer.map(|authority| pda::referrer_address(&authority).0),
+        },
+        instruction::BatchTrade {
+            legs: legs.iter().map(|(_, _, leg)| leg.clone()).collect(),
+        },
+    );
+    for (address, market, leg) in legs {
+        let mint = if leg.side.is_yes() { market.yes_mint } else { market.no_mint };
+        let oracle = if market.price_oracle == Pubkey::default() {
+            eventum::ID
+        } else {
+            market.price_oracle
+        };
+        ix.accounts.extend([
+            AccountMeta::new(*address, false),
+            AccountMeta::new(market.yes_mint, false),
+            AccountMeta::new(market.no_mint, false),
+            AccountMeta::new(pda::vault_address(address).0, false),
+            AccountMeta::new(get_associated_token_address(user, &mint), false),
+            AccountMeta::new(oracle, false),
+            AccountMeta::new(pda::position_address(address, user).0, false),
+        ]);
+    }
+    ix
+}
+
+/// `get_twap`; the price comes back as the transaction's return data.
+pub fn get_twap(market: &Pubkey, window: i64) -> Instruction {
+    build(
+        accounts::GetTwap {
+            price_oracle: pda::price_oracle_address(market).0,
+        },
+        instruction::GetTwap { window },
+    )
+}
+
+pub fn resolve_market(oracle_authority: &Pubkey, address: &Pubkey, outcome: Side) -> Instruction {
+    build(
+        accounts::ResolveMarket {
+            market: *address,
+            oracle_authority: *oracle_authority,
+        },
+        instruction::ResolveMarket { outcome },
+    )
+}
+
+/// `init_resolution_votes`, signed by the market's current oracle, which
+/// hands resolution to `threshold` of `resolvers`.
+pub fn init_resolution_votes(
+    oracle_authority: &Pubkey,
+    address: &Pubkey,
+    resolvers: Vec<Pubkey>,
+    threshold: u8,
+) -> Instruction {
+    build(
+        accounts::InitResolutionVotes {
+            oracle_authority: *oracle_authority,
+            market: *address,
+            resolution_votes: pda::resolution_votes_address(address).0,
+            system_program: system_program::ID,
+        },
+        instruction::InitResolutionVotes { resolvers, threshold },
+    )
+}
+
+pub fn vote_resolution(resolver: &Pubkey, address: &Pubkey, outcome: Side) -> Instruction {
+    build(
+        accounts::VoteResolution {
+            resolver: *resolver,
+            market: *address,
+            resolution_votes: pda::resolution_votes_address(address).0,
+        },
+        instruction::VoteResolution { outcome },
+    )
+}
+
+/// `init_optimistic_resolution`, signed by the market's current oracle,
+/// which hands resolution to bonded proposals with `arbiter` settling
+/// disputes within `arbitration_period` seconds.
+pub fn init_optimistic_resolution(
+    oracle_authority: &Pubkey,
+    address: &Pubkey,
+    arbiter: &Pubkey,
+    bond: u64,
+    liveness: i64,
+    reward_bps: u16,
+    arbitration_period: i64,
+) -> Instruction {
+    build(
+        accounts::InitOptimisticResolution {
+            oracle_authority: *oracle_authority,
+            market: *address,
+            optimistic_resolution: pda::optimistic_resolution_address(address).0,
+            system_program: system_program::ID,
+        },
+        instruction::InitOptimisticResolution {
+            arbiter: *arbiter,
+            bond,
+            liveness,
+            reward_bps,
+            arbitration_period,
+        },
+    )
+}
+
+pub fn propose_outcome(proposer: &Pubkey, address: &Pubkey, outcome: Side) -> Instruction {
+    build(
+        accounts::ProposeOutcome {
+            proposer: *proposer,
+            market: *address,
+            optimistic_resolution: pda::optimistic_resolution_address(address).0,
+            system_program: system_program::ID,
+        },
+        instruction::ProposeOutcome { outcome },
+    )
+}
+
+pub fn challenge_proposal(challenger: &Pubkey, address: &Pubkey) -> Instruction {
+    build(
+        accounts::ChallengeProposal {
+            challenger: *challenger,
+            market: *address,
+            optimistic_resolution: pda::optimistic_resolution_address(address).0,
+            system_program: system_program::ID,
+        },
+        instruction::ChallengeProposal {},
+    )
+}
+
+/// `settle_proposal`; needs no signer beyond the fee payer. Pass the
+/// `challenger` when settling a dispute the arbiter let lapse.
+pub fn settle_proposal(address: &Pubkey, proposer: &Pubkey, challenger: Option<&Pubkey>) -> Instruction {
+    build(
+        accounts::SettleProposal {
+            market: *address,
+            optimistic_resolution: pda::optimistic_resolution_address(address).0,
+            proposer: *proposer,
+            challenger: challenger.copied(),
+            vault: pda::vault_address(address).0,
+            system_program: system_program::ID,
+        },
+        instruction::SettleProposal {},
+    )
+}
+
+pub fn arbitrate(
+    arbiter: &Pubkey,
+    address: &Pubkey,
+    proposer: &Pubkey,
+    challenger: &Pubkey,
+    outcome: Side,
+) -> Instruction {
+    build(
+        accounts::Arbitrate {
+            arbiter: *arbiter,
+            market: *address,
+            optimistic_resolution: pda::optimistic_resolution_address(address).0,
+            proposer: *proposer,
+            challenger: *challenger,
+        },
+        instruction::Arbitrate { outcome },
+    )
+}
+
+/// `claim_winnings`; set `position` to also record the payout on the
+/// user's `Position`.
+pub fn claim_winnings(user: &Pubkey, address: &Pubkey, position: bool) -> Instruction {
+    let yes_mint = pda::yes_mint_address(address).0;
+    let no_mint = pda::no_mint_address(address).0;
+    build(
+        accounts::ClaimWinnings {
+            market: *address,
+            user: *user,
+            user_yes_ata: get_associated_token_address(user, &yes_mint),
+            user_no_ata: get_associated_token_address(user, &no_mint),
+            vault: pda::vault_address(address).0,
+            yes_mint,
+            no_mint,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            position: position.then(|| pda::position_address(address, user).0),
+        },
+        instruction::ClaimWinnings {},
+    )
+}
+
+/// `claim_for`: redeems `holder`'s delegated winning tokens, paying
+/// `cranker` `tip_bps` of the payout.
+pub fn claim_for(
+    cranker: &Pubkey,
+    holder: &Pubkey,
+    address: &Pubkey,
+    market: &Market,
+    tip_bps: u16,
+    position: bool,
+) -> Instruction {
+    let winning_mint = if market.winning_outcome { market.yes_mint } else { market.no_mint };
+    build(
+        accounts::ClaimFor {
+            cranker: *cranker,
+            holder: *holder,
+            market: *address,
+            winning_mint,
+            holder_ata: get_associated_token_address(holder, &winning_mint),
+            vault: pda::vault_address(address).0,
+            position: position.then(|| pda::position_address(address, holder).0),
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        instruction::ClaimFor { tip_bps },
+    )
+}
+
+/// `claim_many` over resolved `(address, market)` pairs. Each market's
+/// `Position` is updated whenever it has been opened.
+pub fn claim_many(user: &Pubkey, markets: &[(Pubkey, &Market)]) -> Instruction {
+    let mut ix = build(
+        accounts::ClaimMany {
+            user: *user,
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        instruction::ClaimMany {},
+    );
+    for (address, market) in markets {
+        let winning_mint = if market.winning_outcome { market.yes_mint } else { market.no_mint };
+        ix.accounts.extend([
+            AccountMeta::new_readonly(*address, false),
+            AccountMeta::new(winning_mint, false),
+            AccountMeta::new(get_associated_token_address(user, &winning_mint), false),
+            AccountMeta::new(pda::vault_address(address).0, false),
+            AccountMeta::new(pda::position_address(address, user).0, false),
+        ]);
+    }
+    ix
+}
+
+pub fn close_market(creator: &Pubkey, address: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseMarket {
+            creator: *creator,
+            market: *address,
+            yes_mint: pda::yes_mint_address(address).0,
+            no_mint: pda::no_mint_address(address).0,
+            vault: pda::vault_address(address).0,
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        instruction::CloseMarket {},
+    )
+}
+
+pub fn register_referrer(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::RegisterReferrer {
+            authority: *authority,
+            referrer: pda::referrer_address(authority).0,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterReferrer {},
+    )
+}
+
+pub fn claim_referral_fees(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimReferralFees {
+            authority: *authority,
+            referrer: pda::referrer_address(authority).0,
+        },
+        instruction::ClaimReferralFees {},
+    )
+}
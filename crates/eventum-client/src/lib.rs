@@ -0,0 +1,32 @@
+//! Rust client for the Eventum program.
+//!
+//! - [`instructions`] builds every instruction with its PDAs and ATAs filled in.
+//! - [`EventumClient`] fetches and decodes program accounts and sends transactions.
+//! - [`quote`] prices trades off-chain with the program's own pricing code, so
+//!   a quote matches what `buy_outcomes` will charge for the same state.
+//! - [`rpc`] is the small blocking JSON-RPC client underneath.
+//! - [`names`] spells sides, fee models and pricing modes as text and parses
+//!   them back, the same way in every tool.
+
+pub mod client;
+pub mod error;
+pub mod instructions;
+pub mod names;
+pub mod quote;
+pub mod rpc;
+
+pub use client::EventumClient;
+pub use error::{ClientError, Result};
+pub use quote::{MarketState, Quote};
+pub use rpc::{ConfirmedTransaction, RpcClient, SignatureInfo, Simulation};
+
+// program types callers need to build arguments and read accounts
+pub use eventum;
+pub use eventum::pda;
+pub use eventum::states::{
+    Market, MarketMetadata, OptimisticResolution, Position, PriceOracle, ResolutionVotes,
+};
+pub use eventum::{
+    BuyOutcomesArgs, FeeModel, InitializeMarketArgs, MarketCategory, PricingMode,
+    SetMarketMetadataArgs, Side, TradeLimits, ID as PROGRAM_ID,
+};
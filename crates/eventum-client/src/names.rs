@@ -0,0 +1,104 @@
+//! How sides, outcomes, fee models and pricing modes are spelled outside the
+//! program: in CLI arguments and output, indexer rows, API responses and the
+//! resolver's outcome sources. Each `*_name` is the inverse of its `parse_*`.
+
+use serde::{Deserialize, Deserializer, Serializer};
+
+use eventum::states::Market;
+use eventum::{FeeModel, PricingMode, Side};
+
+/// A name that none of the `parse_*` functions recognise.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct ParseError(String);
+
+/// `"yes"` or `"no"`.
+pub fn side_name(side: Side) -> &'static str {
+    match side {
+        Side::Yes => "yes",
+        Side::No => "no",
+    }
+}
+
+pub fn parse_side(s: &str) -> Result<Side, ParseError> {
+    match s {
+        "yes" => Ok(Side::Yes),
+        "no" => Ok(Side::No),
+        _ => Err(ParseError(format!("`{s}`: expected yes or no"))),
+    }
+}
+
+/// The winning side's name once `market` is resolved.
+pub fn outcome_name(market: &Market) -> Option<&'static str> {
+    market
+        .resolved
+        .then_some(side_name(if market.winning_outcome { Side::Yes } else { Side::No }))
+}
+
+pub fn fee_model_name(model: FeeModel) -> &'static str {
+    match model {
+        FeeModel::Flat => "flat",
+        FeeModel::PriceDependent => "price-dependent",
+        FeeModel::TimeIncreasing => "time-increasing",
+    }
+}
+
+pub fn parse_fee_model(s: &str) -> Result<FeeModel, ParseError> {
+    match s {
+        "flat" => Ok(FeeModel::Flat),
+        "price-dependent" => Ok(FeeModel::PriceDependent),
+        "time-increasing" => Ok(FeeModel::TimeIncreasing),
+        _ => Err(ParseError(format!(
+            "`{s}`: expected flat, price-dependent or time-increasing"
+        ))),
+    }
+}
+
+/// `lmsr:<b>`, `ls-lmsr:<alpha_bps>:<min_b>` or `cpmm:<seed_liquidity>`.
+pub fn pricing_mode_name(mode: PricingMode) -> String {
+    match mode {
+        PricingMode::Lmsr { b } => format!("lmsr:{b}"),
+        PricingMode::LsLmsr { alpha_bps, min_b } => format!("ls-lmsr:{alpha_bps}:{min_b}"),
+        PricingMode::Cpmm { seed_liquidity } => format!("cpmm:{seed_liquidity}"),
+    }
+}
+
+pub fn parse_pricing_mode(s: &str) -> Result<PricingMode, ParseError> {
+    let (name, param) = s.split_once(':').unwrap_or((s, ""));
+    match (name, param) {
+        ("lmsr", b) => b
+            .parse()
+            .map(|b| PricingMode::Lmsr { b })
+            .map_err(|e| ParseError(format!("lmsr b: {e}"))),
+        ("ls-lmsr", params) => {
+            let (alpha, min_b) = params
+                .split_once(':')
+                .ok_or_else(|| ParseError(format!("`{s}`: expected ls-lmsr:<alpha_bps>:<min_b>")))?;
+            let alpha_bps = alpha.parse().map_err(|e| ParseError(format!("ls-lmsr alpha: {e}")))?;
+            let min_b = min_b.parse().map_err(|e| ParseError(format!("ls-lmsr min_b: {e}")))?;
+            Ok(PricingMode::LsLmsr { alpha_bps, min_b })
+        }
+        ("cpmm", seed) => seed
+            .parse()
+            .map(|seed_liquidity| PricingMode::Cpmm { seed_liquidity })
+            .map_err(|e| ParseError(format!("cpmm seed liquidity: {e}"))),
+        _ => Err(ParseError(format!(
+            "`{s}`: expected lmsr:<b>, ls-lmsr:<alpha_bps>:<min_b> or cpmm:<lamports>"
+        ))),
+    }
+}
+
+/// Serde adapter writing a `Side` by its name:
+/// `#[serde(with = "eventum_client::names::side")]`.
+pub mod side {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(side: &Side, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(side_name(*side))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Side, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        parse_side(&s).map_err(serde::de::Error::custom)
+    }
+}
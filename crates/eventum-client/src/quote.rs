@@ -0,0 +1,83 @@
+//! Off-chain pricing. Quotes run `buy_outcomes::quote_buy` and the
+//! pricing engines from the program crate itself, so they agree with
+//! `buy_outcomes` for the same market state and clock.
+
+use anchor_lang::prelude::Pubkey;
+use eventum::instructions::buy_outcomes::quote_buy;
+use eventum::pricing::{engine_for, scaled_price};
+use eventum::states::Market;
+use eventum::Side;
+
+use crate::error::{ClientError, Result};
+
+/// A market together with the mint supplies its price depends on.
+#[derive(Clone)]
+pub struct MarketState {
+    pub address: Pubkey,
+    pub market: Market,
+    pub yes_supply: u64,   // raw token units
+    pub no_supply: u64,
+    pub decimals: u8,
+}
+
+/// What buying `tokens` of one side would cost right now.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quote {
+    pub tokens: u64,            // raw token units
+    pub cost: u64,              // lamports paid to the pool
+    pub fee: u64,               // lamports of fee on top of `cost`
+    pub total: u64,             // `cost + fee`; the least `max_cost` that fills
+    pub price_yes_before: u64,  // scaled by `eventum::states::PRICE_SCALE`
+    pub price_yes_after: u64,
+}
+
+impl MarketState {
+    /// Raw units for `number_of_tokens` whole tokens.
+    pub fn raw_tokens(&self, number_of_tokens: u64) -> Result<u64> {
+        number_of_tokens
+            .checked_mul(10_u64.pow(self.decimals as u32))
+            .ok_or_else(|| ClientError::Program(eventum::error::ErrorCode::MathOverflow.into()))
+    }
+
+    /// Current YES price, scaled by `PRICE_SCALE`.
+    pub fn price_yes(&self) -> Result<u64> {
+        let engine = engine_for(&self.market, self.yes_supply, self.no_supply, self.decimals);
+        Ok(scaled_price(engine.price_yes()?)?)
+    }
+
+    /// Quotes a buy of `number_of_tokens` whole tokens at cluster time `now`.
+    /// Fails with the program's own error when the buy would be rejected
+    /// (resolved market, trade limits, ...).
+    pub fn quote_buy(&self, side: Side, number_of_tokens: u64, now: i64) -> Result<Quote> {
+        let tokens = self.raw_tokens(number_of_tokens)?;
+        let yes = side.is_yes();
+        let (total, fee) = quote_buy(
+            &self.market,
+            self.yes_supply,
+            self.no_supply,
+            self.decimals,
+            yes,
+            tokens,
+            now,
+        )?;
+        let cost = total - fee;
+        let engine = engine_for(&self.market, self.yes_supply, self.no_supply, self.decimals);
+        Ok(Quote {
+            tokens,
+            cost,
+            fee,
+            total,
+            price_yes_before: scaled_price(engine.price_yes()?)?,
+            price_yes_after: scaled_price(engine.price_yes_after_buy(yes, tokens, cost)?)?,
+        })
+    }
+}
+
+impl Quote {
+    /// `max_cost` allowing the price to move `slippage_bps` against the
+    /// buyer before the transaction lands.
+    pub fn max_cost_with_slippage(&self, slippage_bps: u16) -> u64 {
+        let extra = (self.total as u128) * slippage_bps as u128 / 10000;
+        self.total.saturating_add(extra as u64)
+    }
+}
@@ -0,0 +1,398 @@
+//! Blocking JSON-RPC client covering the handful of methods Eventum tools
+//! need. Account data is always requested base64-encoded.
+
+use std::str::FromStr;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use base64::prelude::{Engine, BASE64_STANDARD};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::sysvar;
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+
+use crate::error::{ClientError, Result};
+
+/// `getMultipleAccounts` accepts at most this many keys per call.
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// `getSignaturesForAddress` returns at most this many signatures per call.
+pub const MAX_SIGNATURES: usize = 1000;
+
+/// How long `confirm_transaction` polls before giving up.
+pub const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize)]
+struct Response<T> {
+    result: Option<T>,
+    error: Option<ErrorObject>,
+}
+
+#[derive(Deserialize)]
+struct ErrorObject {
+    code: i64,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct WithContext<T> {
+    value: T,
+}
+
+#[derive(Deserialize)]
+struct UiAccount {
+    data: (String, String),
+}
+
+#[derive(Deserialize)]
+struct KeyedUiAccount {
+    pubkey: String,
+    account: UiAccount,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Blockhash {
+    blockhash: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignatureStatus {
+    confirmation_status: Option<String>,
+    err: Option<Value>,
+}
+
+/// One entry of `getSignaturesForAddress`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureInfo {
+    pub signature: String,
+    pub slot: u64,
+    /// Set when the transaction failed.
+    pub err: Option<Value>,
+    pub block_time: Option<i64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiTransaction {
+    slot: u64,
+    block_time: Option<i64>,
+    transaction: (String, String),
+    meta: Option<UiTransactionMeta>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiTransactionMeta {
+    err: Option<Value>,
+    #[serde(default)]
+    log_messages: Option<Vec<String>>,
+    #[serde(default)]
+    loaded_addresses: Option<UiLoadedAddresses>,
+}
+
+#[derive(Deserialize)]
+struct UiLoadedAddresses {
+    writable: Vec<String>,
+    readonly: Vec<String>,
+}
+
+/// The outcome of `simulateTransaction`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Simulation {
+    /// Set when the transaction would fail.
+    pub err: Option<Value>,
+    #[serde(default)]
+    pub logs: Option<Vec<String>>,
+    pub units_consumed: Option<u64>,
+}
+
+/// A confirmed transaction with the parts of its status metadata that
+/// tools replaying program activity need.
+#[derive(Clone, Debug)]
+pub struct ConfirmedTransaction {
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub transaction: VersionedTransaction,
+    /// Every account the message refers to, in index order: its static keys
+    /// followed by the writable and read-only address-table lookups.
+    pub account_keys: Vec<Pubkey>,
+    pub log_messages: Vec<String>,
+    /// Set when the transaction failed.
+    pub err: Option<Value>,
+}
+
+pub struct RpcClient {
+    url: String,
+    agent: ureq::Agent,
+    commitment: String,
+}
+
+impl RpcClient {
+    /// A client using `confirmed` commitment.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::new_with_commitment(url, "confirmed")
+    }
+
+    pub fn new_with_commitment(url: impl Into<String>, commitment: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            agent: ureq::Agent::new_with_defaults(),
+            commitment: commitment.into(),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Sends one JSON-RPC request and decodes its `result`.
+    pub fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        self.call_nullable(method, params)?
+            .ok_or_else(|| ClientError::Decode(format!("{method}: no result")))
+    }
+
+    /// `call` for methods whose `result` may legitimately be `null`.
+    pub fn call_nullable<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<Option<T>> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Response<T> = self
+            .agent
+            .post(&self.url)
+            .send_json(&request)?
+            .into_body()
+            .read_json()?;
+        match response.error {
+            Some(e) => Err(ClientError::Rpc { code: e.code, message: e.message }),
+            None => Ok(response.result),
+        }
+    }
+
+    /// Raw data of an account, or `None` if it does not exist.
+    pub fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let response: WithContext<Option<UiAccount>> = self.call(
+            "getAccountInfo",
+            json!([address.to_string(), { "encoding": "base64", "commitment": self.commitment }]),
+        )?;
+        response.value.map(|account| decode_data(&account.data)).transpose()
+    }
+
+    /// Raw data of several accounts, in the order asked for.
+    pub fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> Result<Vec<Option<Vec<u8>>>> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let keys: Vec<String> = chunk.iter().map(Pubkey::to_string).collect();
+            let response: WithContext<Vec<Option<UiAccount>>> = self.call(
+                "getMultipleAccounts",
+                json!([keys, { "encoding": "base64", "commitment": self.commitment }]),
+            )?;
+            for account in response.value {
+                accounts.push(account.map(|a| decode_data(&a.data)).transpose()?);
+            }
+        }
+        Ok(accounts)
+    }
+
+    /// Accounts owned by `program` matching every filter (`memcmp`/`dataSize`
+    /// objects as the RPC expects them).
+    pub fn get_program_accounts(
+        &self,
+        program: &Pubkey,
+        filters: Vec<Value>,
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+        let accounts: Vec<KeyedUiAccount> = self.call(
+            "getProgramAccounts",
+            json!([program.to_string(), {
+                "encoding": "base64",
+                "commitment": self.commitment,
+                "filters": filters,
+            }]),
+        )?;
+        accounts
+            .into_iter()
+            .map(|keyed| Ok((parse_pubkey(&keyed.pubkey)?, decode_data(&keyed.account.data)?)))
+            .collect()
+    }
+
+    pub fn get_balance(&self, address: &Pubkey) -> Result<u64> {
+        let response: WithContext<u64> = self.call(
+            "getBalance",
+            json!([address.to_string(), { "commitment": self.commitment }]),
+        )?;
+        Ok(response.value)
+    }
+
+    pub fn get_latest_blockhash(&self) -> Result<Hash> {
+        let response: WithContext<Blockhash> = self.call(
+            "getLatestBlockhash",
+            json!([{ "commitment": self.commitment }]),
+        )?;
+        Hash::from_str(&response.value.blockhash)
+            .map_err(|e| ClientError::Decode(format!("blockhash: {e}")))
+    }
+
+    /// Cluster time from the clock sysvar, which is what on-chain fee and
+    /// expiry checks see.
+    pub fn get_unix_timestamp(&self) -> Result<i64> {
+        let data = self
+            .get_account_data(&sysvar::clock::ID)?
+            .ok_or(ClientError::AccountNotFound(sysvar::clock::ID))?;
+        // Clock { slot, epoch_start_timestamp, epoch, leader_schedule_epoch, unix_timestamp }
+        let bytes: [u8; 8] = data
+            .get(32..40)
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| ClientError::Decode("clock sysvar too short".into()))?;
+        Ok(i64::from_le_bytes(bytes))
+    }
+
+    pub fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        let wire = bincode::serialize(transaction)
+            .map_err(|e| ClientError::Decode(format!("transaction: {e}")))?;
+        let signature: String = self.call(
+            "sendTransaction",
+            json!([BASE64_STANDARD.encode(wire), {
+                "encoding": "base64",
+                "preflightCommitment": self.commitment,
+            }]),
+        )?;
+        parse_signature(&signature)
+    }
+
+    /// Runs a signed `transaction` against the node's current state without
+    /// submitting it.
+    pub fn simulate_transaction(&self, transaction: &Transaction) -> Result<Simulation> {
+        let wire = bincode::serialize(transaction)
+            .map_err(|e| ClientError::Decode(format!("transaction: {e}")))?;
+        let response: WithContext<Simulation> = self.call(
+            "simulateTransaction",
+            json!([BASE64_STANDARD.encode(wire), {
+                "encoding": "base64",
+                "commitment": self.commitment,
+                "sigVerify": true,
+            }]),
+        )?;
+        Ok(response.value)
+    }
+
+    /// Polls until `signature` reaches this client's commitment, failing if
+    /// the transaction errored or `CONFIRM_TIMEOUT` passes.
+    pub fn confirm_transaction(&self, signature: &Signature) -> Result<()> {
+        let started = Instant::now();
+        loop {
+            let response: WithContext<Vec<Option<SignatureStatus>>> = self.call(
+                "getSignatureStatuses",
+                json!([[signature.to_string()]]),
+            )?;
+            if let Some(Some(status)) = response.value.into_iter().next() {
+                if let Some(err) = status.err {
+                    return Err(ClientError::TransactionFailed {
+                        signature: *signature,
+                        err: err.to_string(),
+                    });
+                }
+                let reached = match status.confirmation_status.as_deref() {
+                    Some("finalized") => true,
+                    Some("confirmed") => self.commitment != "finalized",
+                    Some(_) => self.commitment == "processed",
+                    None => false,
+                };
+                if reached {
+                    return Ok(());
+                }
+            }
+            if started.elapsed() > CONFIRM_TIMEOUT {
+                return Err(ClientError::Timeout(*signature));
+            }
+            sleep(Duration::from_millis(500));
+        }
+    }
+
+    pub fn send_and_confirm_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        let signature = self.send_transaction(transaction)?;
+        self.confirm_transaction(&signature)?;
+        Ok(signature)
+    }
+
+    /// Signatures of transactions that touched `address`, newest first:
+    /// those older than `before` (if given) and newer than `until` (if
+    /// given), at most `limit` of them (capped at `MAX_SIGNATURES`).
+    pub fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<&str>,
+        until: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<SignatureInfo>> {
+        let mut config = json!({ "limit": limit.min(MAX_SIGNATURES), "commitment": self.commitment });
+        if let Some(before) = before {
+            config["before"] = json!(before);
+        }
+        if let Some(until) = until {
+            config["until"] = json!(until);
+        }
+        self.call("getSignaturesForAddress", json!([address.to_string(), config]))
+    }
+
+    /// A confirmed transaction, or `None` if the node does not have it.
+    pub fn get_transaction(&self, signature: &str) -> Result<Option<ConfirmedTransaction>> {
+        let response: Option<UiTransaction> = self.call_nullable(
+            "getTransaction",
+            json!([signature, {
+                "encoding": "base64",
+                "commitment": self.commitment,
+                "maxSupportedTransactionVersion": 0,
+            }]),
+        )?;
+        let Some(ui) = response else {
+            return Ok(None);
+        };
+        let wire = decode_data(&ui.transaction)?;
+        let transaction: VersionedTransaction = bincode::deserialize(&wire)
+            .map_err(|e| ClientError::Decode(format!("transaction {signature}: {e}")))?;
+        let meta = ui
+            .meta
+            .ok_or_else(|| ClientError::Decode(format!("transaction {signature}: no status meta")))?;
+        let mut account_keys = transaction.message.static_account_keys().to_vec();
+        if let Some(loaded) = meta.loaded_addresses {
+            for key in loaded.writable.iter().chain(&loaded.readonly) {
+                account_keys.push(parse_pubkey(key)?);
+            }
+        }
+        Ok(Some(ConfirmedTransaction {
+            slot: ui.slot,
+            block_time: ui.block_time,
+            transaction,
+            account_keys,
+            log_messages: meta.log_messages.unwrap_or_default(),
+            err: meta.err,
+        }))
+    }
+
+    pub fn request_airdrop(&self, to: &Pubkey, lamports: u64) -> Result<Signature> {
+        let signature: String = self.call("requestAirdrop", json!([to.to_string(), lamports]))?;
+        parse_signature(&signature)
+    }
+}
+
+fn decode_data((data, encoding): &(String, String)) -> Result<Vec<u8>> {
+    if encoding != "base64" {
+        return Err(ClientError::Decode(format!("account encoding {encoding}")));
+    }
+    BASE64_STANDARD
+        .decode(data)
+        .map_err(|e| ClientError::Decode(format!("account data: {e}")))
+}
+
+pub(crate) fn parse_pubkey(s: &str) -> Result<Pubkey> {
+    Pubkey::from_str(s).map_err(|e| ClientError::Decode(format!("pubkey {s}: {e}")))
+}
+
+fn parse_signature(s: &str) -> Result<Signature> {
+    Signature::from_str(s).map_err(|e| ClientError::Decode(format!("signature {s}: {e}")))
+}
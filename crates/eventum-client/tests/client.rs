@@ -0,0 +1,226 @@
+//! Builder and quote tests run offline. `local_validator_round_trip` needs
+//! a validator with the program freshly deployed (`anchor localnet`), or one
+//! whose config is not permissioned, and is ignored by default:
+//! `cargo test -p eventum-client -- --ignored`.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use anchor_spl::associated_token::get_associated_token_address;
+use eventum_client::instructions::{self, BuyOptions};
+use eventum_client::names;
+use eventum_client::{
+    pda, BuyOutcomesArgs, EventumClient, FeeModel, InitializeMarketArgs, Market, MarketState,
+    PricingMode, Side, TradeLimits, PROGRAM_ID,
+};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const ONE_TOKEN: u64 = 1_000_000_000;
+
+fn market(creator: Pubkey, pricing_mode: PricingMode, limits: TradeLimits) -> Market {
+    let address = pda::market_address(&creator, 0).0;
+    Market {
+        creator,
+        oracle_authority: creator,
+        unique_market_id: 0,
+        start_time: 0,
+        end_time: 1_000,
+        resolved: false,
+        winning_outcome: false,
+        question: "Will it rain?".into(),
+        vault_bump: pda::vault_address(&address).1,
+        fee: 100,
+        total_liquidity: 0,
+        yes_mint: pda::yes_mint_address(&address).0,
+        no_mint: pda::no_mint_address(&address).0,
+        yes_tokens: 0,
+        no_tokens: 0,
+        bump: pda::market_address(&creator, 0).1,
+        approved_creator: false,
+        fee_model: FeeModel::Flat,
+        referral_share_bps: 0,
+        pricing_mode,
+        yes_reserve: 0,
+        no_reserve: 0,
+        limits,
+        price_oracle: Pubkey::default(),
+        fees_collected: 0,
+    }
+}
+
+fn state(market: Market, yes_supply: u64, no_supply: u64) -> MarketState {
+    MarketState {
+        address: pda::market_address(&market.creator, 0).0,
+        market,
+        yes_supply,
+        no_supply,
+        decimals: 9,
+    }
+}
+
+#[test]
+fn buy_outcomes_derives_market_accounts() {
+    let creator = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let m = market(creator, PricingMode::Lmsr { b: 1000 }, TradeLimits::default());
+    let address = pda::market_address(&creator, 0).0;
+    let args = BuyOutcomesArgs { number_of_tokens: 5, side: Side::No, max_cost: 42 };
+
+    let ix = instructions::buy_outcomes(&user, &address, &m, args, BuyOptions::default());
+    assert_eq!(ix.program_id, PROGRAM_ID);
+    let keys: Vec<Pubkey> = ix.accounts.iter().map(|a| a.pubkey).collect();
+    assert_eq!(keys[0], user);
+    assert!(ix.accounts[0].is_signer);
+    assert_eq!(keys[1], address);
+    assert_eq!(keys[2], m.yes_mint);
+    assert_eq!(keys[3], m.no_mint);
+    assert_eq!(keys[4], pda::vault_address(&address).0);
+    assert_eq!(keys[5], get_associated_token_address(&user, &m.yes_mint));
+    assert_eq!(keys[6], get_associated_token_address(&user, &m.no_mint));
+    // absent optional accounts are passed as the program id
+    assert_eq!(&keys[10..], &[PROGRAM_ID; 3]);
+
+    let (discriminator, body) = ix.data.split_at(8);
+    assert_eq!(discriminator, eventum::instruction::BuyOutcomes::DISCRIMINATOR);
+    let decoded = eventum::instruction::BuyOutcomes::try_from_slice(body).unwrap();
+    assert_eq!(decoded.args.number_of_tokens, 5);
+    assert_eq!(decoded.args.side, Side::No);
+    assert_eq!(decoded.args.max_cost, 42);
+}
+
+#[test]
+fn buy_outcomes_passes_price_oracle_when_market_has_one() {
+    let creator = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let referrer = Pubkey::new_unique();
+    let address = pda::market_address(&creator, 0).0;
+    let mut m = market(creator, PricingMode::Lmsr { b: 1000 }, TradeLimits::default());
+    m.price_oracle = pda::price_oracle_address(&address).0;
+    let args = BuyOutcomesArgs { number_of_tokens: 1, side: Side::Yes, max_cost: 1 };
+    let options = BuyOptions { position: true, referrer: Some(referrer) };
+
+    let ix = instructions::buy_outcomes(&user, &address, &m, args, options);
+    let keys: Vec<Pubkey> = ix.accounts.iter().map(|a| a.pubkey).collect();
+    assert_eq!(keys[10], pda::position_address(&address, &user).0);
+    assert_eq!(keys[11], pda::referrer_address(&referrer).0);
+    assert_eq!(keys[12], m.price_oracle);
+}
+
+#[test]
+fn quote_includes_flat_fee_and_moves_price() {
+    let m = market(Pubkey::new_unique(), PricingMode::Lmsr { b: 1000 }, TradeLimits::default());
+    let quote = state(m, 0, 0).quote_buy(Side::Yes, 10, 0).unwrap();
+
+    assert_eq!(quote.tokens, 10 * ONE_TOKEN);
+    assert_eq!(quote.fee, quote.cost / 100);
+    assert_eq!(quote.total, quote.cost + quote.fee);
+    assert_eq!(quote.price_yes_before, ONE_TOKEN / 2);
+    assert!(quote.price_yes_after > quote.price_yes_before);
+    // buying on an empty LMSR book costs more than half a lamport per unit
+    assert!(quote.cost > quote.tokens / 2);
+}
+
+#[test]
+fn quote_is_symmetric_between_sides() {
+    let m = market(Pubkey::new_unique(), PricingMode::Lmsr { b: 1000 }, TradeLimits::default());
+    let yes = state(m.clone(), 3 * ONE_TOKEN, 0).quote_buy(Side::Yes, 4, 0).unwrap();
+    let no = state(m, 0, 3 * ONE_TOKEN).quote_buy(Side::No, 4, 0).unwrap();
+    assert_eq!(yes.cost, no.cost);
+}
+
+#[test]
+fn quote_rejects_what_the_program_rejects() {
+    let limits = TradeLimits { min_tokens: 2 * ONE_TOKEN, max_tokens: 0, max_price_move_bps: 0 };
+    let m = market(Pubkey::new_unique(), PricingMode::Lmsr { b: 1000 }, limits);
+    assert!(state(m.clone(), 0, 0).quote_buy(Side::Yes, 1, 0).is_err());
+    assert!(state(m.clone(), 0, 0).quote_buy(Side::Yes, 2, 0).is_ok());
+
+    let mut resolved = m;
+    resolved.resolved = true;
+    assert!(state(resolved, 0, 0).quote_buy(Side::Yes, 2, 0).is_err());
+}
+
+#[test]
+fn slippage_allowance_is_added_to_total() {
+    let m = market(Pubkey::new_unique(), PricingMode::Lmsr { b: 1000 }, TradeLimits::default());
+    let quote = state(m, 0, 0).quote_buy(Side::Yes, 1, 0).unwrap();
+    assert_eq!(quote.max_cost_with_slippage(0), quote.total);
+    assert_eq!(quote.max_cost_with_slippage(100), quote.total + quote.total / 100);
+}
+
+#[test]
+fn names_parse_back_to_what_they_name() {
+    for side in [Side::Yes, Side::No] {
+        assert_eq!(names::parse_side(names::side_name(side)).unwrap(), side);
+    }
+    for model in [FeeModel::Flat, FeeModel::PriceDependent, FeeModel::TimeIncreasing] {
+        assert_eq!(names::parse_fee_model(names::fee_model_name(model)).unwrap(), model);
+    }
+    for mode in [
+        PricingMode::Lmsr { b: 10 },
+        PricingMode::LsLmsr { alpha_bps: 500, min_b: 20 },
+        PricingMode::Cpmm { seed_liquidity: ONE_TOKEN },
+    ] {
+        assert_eq!(names::parse_pricing_mode(&names::pricing_mode_name(mode)).unwrap(), mode);
+    }
+    assert_eq!(names::pricing_mode_name(PricingMode::LsLmsr { alpha_bps: 500, min_b: 20 }), "ls-lmsr:500:20");
+
+    for bad in ["Yes", "maybe", ""] {
+        assert!(names::parse_side(bad).is_err());
+    }
+    assert!(names::parse_fee_model("time-decaying").is_err());
+    for bad in ["lmsr", "lmsr:", "lmsr:-1", "ls-lmsr:500", "ls-lmsr:70000:1", "cpmm:x", "amm:1"] {
+        assert!(names::parse_pricing_mode(bad).is_err(), "{bad}");
+    }
+}
+
+#[test]
+#[ignore = "needs a local validator with eventum deployed"]
+fn local_validator_round_trip() {
+    let url = std::env::var("EVENTUM_RPC_URL").unwrap_or_else(|_| "http://127.0.0.1:8899".into());
+    let client = EventumClient::new(url);
+    let creator = Keypair::new();
+    let user = Keypair::new();
+    for kp in [&creator, &user] {
+        let sig = client.rpc.request_airdrop(&kp.pubkey(), 10 * ONE_TOKEN).unwrap();
+        client.rpc.confirm_transaction(&sig).unwrap();
+    }
+
+    // a fresh deployment has no config, so anyone may create markets; the
+    // creator's 10 SOL covers the `b · ln 2` ≈ 6.9 SOL subsidy at b = 10
+    let id = client.next_market_id(&creator.pubkey()).unwrap();
+    let now = client.rpc.get_unix_timestamp().unwrap();
+    let args = InitializeMarketArgs {
+        unique_market_id: id,
+        end_time: now + 3600,
+        fee: 100,
+        fee_model: FeeModel::Flat,
+        referral_share_bps: 0,
+        pricing_mode: PricingMode::Lmsr { b: 10 },
+        limits: TradeLimits::default(),
+        question: "Round trip?".into(),
+    };
+    let ix = instructions::initialize_market(&creator.pubkey(), &creator.pubkey(), false, args);
+    client.send(&[ix], &creator, &[]).unwrap();
+
+    let address = pda::market_address(&creator.pubkey(), id).0;
+    let before = client.market_state(&address).unwrap();
+    let quote = before.quote_buy(Side::Yes, 3, now).unwrap();
+    let vault = pda::vault_address(&address).0;
+    let vault_before = client.rpc.get_balance(&vault).unwrap();
+
+    let args = BuyOutcomesArgs { number_of_tokens: 3, side: Side::Yes, max_cost: quote.total };
+    let ix = instructions::buy_outcomes(&user.pubkey(), &address, &before.market, args, BuyOptions::default());
+    client.send(&[ix], &user, &[]).unwrap();
+
+    let after = client.market_state(&address).unwrap();
+    assert_eq!(after.yes_supply, quote.tokens);
+    assert_eq!(client.rpc.get_balance(&vault).unwrap() - vault_before, quote.total);
+    let listed = client.markets(Some(&creator.pubkey())).unwrap();
+    assert!(listed.iter().any(|(key, _)| *key == address));
+
+    let ix = instructions::resolve_market(&creator.pubkey(), &address, Side::Yes);
+    client.send(&[ix], &creator, &[]).unwrap();
+    let ix = instructions::claim_winnings(&user.pubkey(), &address, false);
+    client.send(&[ix], &user, &[]).unwrap();
+    assert_eq!(client.market_state(&address).unwrap().yes_supply, 0);
+}
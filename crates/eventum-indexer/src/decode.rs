@@ -0,0 +1,126 @@
+//! Decoding of replayed transactions with the program's own types: events
+//! from `Program data:` log lines, market creations from instruction data.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::prelude::{Engine, BASE64_STANDARD};
+use eventum_client::eventum::events::{MarketResolved, OutcomesBought, WinningsClaimed};
+use eventum_client::eventum::instruction::InitializeMarket;
+use eventum_client::{pda, ConfirmedTransaction, InitializeMarketArgs};
+use solana_sdk::pubkey::Pubkey;
+
+/// A program event the store keeps.
+#[derive(Clone, Debug)]
+pub enum ProgramEvent {
+    Bought(OutcomesBought),
+    Resolved(MarketResolved),
+    Claimed(WinningsClaimed),
+}
+
+/// A market created by a top-level `initialize_market`.
+#[derive(Clone)]
+pub struct MarketCreation {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub args: InitializeMarketArgs,
+}
+
+/// Everything one transaction contributes to the store.
+#[derive(Clone)]
+pub struct TransactionRecord {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub creations: Vec<MarketCreation>,
+    pub events: Vec<ProgramEvent>,
+}
+
+impl TransactionRecord {
+    /// Decodes what `program` did in `tx`. Failed transactions had no
+    /// effect and decode to an empty record.
+    pub fn decode(program: &Pubkey, signature: &str, tx: &ConfirmedTransaction) -> Self {
+        let (creations, events) = match tx.err {
+            Some(_) => (Vec::new(), Vec::new()),
+            None => (market_creations(program, tx), events_from_logs(program, &tx.log_messages)),
+        };
+        Self {
+            signature: signature.to_string(),
+            slot: tx.slot,
+            block_time: tx.block_time,
+            creations,
+            events,
+        }
+    }
+}
+
+fn parse<T: AnchorDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    let mut rest = data.strip_prefix(T::DISCRIMINATOR)?;
+    T::deserialize(&mut rest).ok()
+}
+
+/// The event serialized in `data` (discriminator first), if it is one the
+/// store keeps.
+pub fn decode_event(data: &[u8]) -> Option<ProgramEvent> {
+    parse(data)
+        .map(ProgramEvent::Bought)
+        .or_else(|| parse(data).map(ProgramEvent::Resolved))
+        .or_else(|| parse(data).map(ProgramEvent::Claimed))
+}
+
+/// Events `program` emitted, in order. Each `Program data:` line is
+/// attributed to the program on top of the `invoke`/`success`/`failed`
+/// stack, so data logged by other programs in the same transaction is
+/// skipped, while events from Eventum called through CPI are kept.
+pub fn events_from_logs(program: &Pubkey, logs: &[String]) -> Vec<ProgramEvent> {
+    let program = program.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+    for line in logs {
+        if let Some(fields) = line.strip_prefix("Program data: ") {
+            if stack.last() != Some(&program.as_str()) {
+                continue;
+            }
+            // sol_log_data logs each field base64-encoded, space separated
+            let data: Option<Vec<u8>> = fields
+                .split(' ')
+                .map(|field| BASE64_STANDARD.decode(field).ok())
+                .try_fold(Vec::new(), |mut data, field| {
+                    data.extend(field?);
+                    Some(data)
+                });
+            events.extend(data.as_deref().and_then(decode_event));
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut words = rest.split(' ');
+            match (words.next(), words.next()) {
+                (Some(id), Some("invoke")) => stack.push(id),
+                (Some(_), Some("success" | "failed:")) => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
+
+/// `initialize_market` instructions `tx` sent to `program` directly. Markets
+/// created through CPI are not in the message; the store still picks them
+/// up from their accounts, without a creation slot.
+pub fn market_creations(program: &Pubkey, tx: &ConfirmedTransaction) -> Vec<MarketCreation> {
+    let keys = &tx.account_keys;
+    tx.transaction
+        .message
+        .instructions()
+        .iter()
+        .filter(|ix| keys.get(ix.program_id_index as usize) == Some(program))
+        .filter_map(|ix| {
+            let ix_data: InitializeMarket = parse(&ix.data)?;
+            // the creator signs first
+            let creator = *keys.get(*ix.accounts.first()? as usize)?;
+            Some(MarketCreation {
+                market: pda::market_address(&creator, ix_data.args.unique_market_id).0,
+                creator,
+                args: ix_data.args,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,15 @@
+use eventum_client::ClientError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum IndexerError {
+    #[error(transparent)]
+    Client(#[from] ClientError),
+
+    #[error("database: {0}")]
+    Db(#[from] rusqlite::Error),
+
+    #[error("unexpected data: {0}")]
+    Decode(String),
+}
+
+pub type Result<T> = std::result::Result<T, IndexerError>;
@@ -0,0 +1,99 @@
+//! Replays the program's transactions over JSON-RPC into a [`Store`].
+
+use eventum_client::rpc::MAX_SIGNATURES;
+use eventum_client::{EventumClient, PROGRAM_ID};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::decode::{ProgramEvent, TransactionRecord};
+use crate::error::Result;
+use crate::store::Store;
+
+/// What one [`Indexer::sync`] added.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SyncReport {
+    pub transactions: usize,
+    pub creations: usize,
+    pub trades: usize,
+    pub resolutions: usize,
+    pub claims: usize,
+    /// Market accounts refreshed.
+    pub markets: usize,
+}
+
+impl SyncReport {
+    fn add(&mut self, record: &TransactionRecord) {
+        self.transactions += 1;
+        self.creations += record.creations.len();
+        for event in &record.events {
+            match event {
+                ProgramEvent::Bought(_) => self.trades += 1,
+                ProgramEvent::Resolved(_) => self.resolutions += 1,
+                ProgramEvent::Claimed(_) => self.claims += 1,
+            }
+        }
+    }
+}
+
+pub struct Indexer {
+    pub client: EventumClient,
+    pub store: Store,
+    program: Pubkey,
+}
+
+impl Indexer {
+    pub fn new(client: EventumClient, store: Store) -> Self {
+        Self::with_program(client, store, PROGRAM_ID)
+    }
+
+    /// An indexer for a deployment of the program at `program`.
+    pub fn with_program(client: EventumClient, store: Store, program: Pubkey) -> Self {
+        Self { client, store, program }
+    }
+
+    /// Applies every transaction since the store's cursor, oldest first, then
+    /// refreshes the stored state of every market from its account. Each
+    /// transaction is stored atomically with the cursor, so an interrupted
+    /// sync resumes where it stopped.
+    pub fn sync(&mut self) -> Result<SyncReport> {
+        let mut report = SyncReport::default();
+        for signature in self.pending_signatures()? {
+            // the node can list a signature before it serves the transaction;
+            // pick it up next time rather than skip it
+            let Some(tx) = self.client.rpc.get_transaction(&signature)? else {
+                break;
+            };
+            let record = TransactionRecord::decode(&self.program, &signature, &tx);
+            self.store.apply(&record)?;
+            report.add(&record);
+        }
+
+        let states = self.client.market_states(None)?;
+        self.store.sync_markets(&states)?;
+        report.markets = states.len();
+        Ok(report)
+    }
+
+    /// Signatures newer than the cursor, oldest first.
+    fn pending_signatures(&self) -> Result<Vec<String>> {
+        let cursor = self.store.cursor()?;
+        let mut signatures = Vec::new();
+        let mut before: Option<String> = None;
+        loop {
+            let page = self.client.rpc.get_signatures_for_address(
+                &self.program,
+                before.as_deref(),
+                cursor.as_deref(),
+                MAX_SIGNATURES,
+            )?;
+            let done = page.len() < MAX_SIGNATURES;
+            before = page.last().map(|info| info.signature.clone());
+            signatures.extend(page.into_iter().map(|info| info.signature));
+            if done || before.is_none() {
+                break;
+            }
+        }
+        signatures.reverse();
+        Ok(signatures)
+    }
+}
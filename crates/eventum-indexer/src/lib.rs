@@ -0,0 +1,19 @@
+//! Off-chain indexer for Eventum: the history the chain does not keep.
+//!
+//! - [`decode`] turns a confirmed transaction into rows: program events
+//!   (`OutcomesBought`, `MarketResolved`, `WinningsClaimed`) from its logs and
+//!   market creations from `initialize_market` instruction data.
+//! - [`store`] keeps markets, trades, resolutions and claims in SQLite and
+//!   answers the queries dashboards need (price history, volume, positions).
+//! - [`Indexer`] replays the program's transactions over JSON-RPC into a
+//!   store, resuming from the last signature it processed, and refreshes
+//!   the current `Market` accounts.
+
+pub mod decode;
+pub mod error;
+pub mod indexer;
+pub mod store;
+
+pub use error::{IndexerError, Result};
+pub use indexer::{Indexer, SyncReport};
+pub use store::Store;
@@ -0,0 +1,121 @@
+//! `eventum-indexer`: replay Eventum's transactions into SQLite and query
+//! the history. Query commands print JSON.
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use eventum_client::EventumClient;
+use eventum_indexer::{Indexer, Store};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Parser)]
+#[command(name = "eventum-indexer", version, about)]
+struct Cli {
+    /// RPC URL of the node to replay from
+    #[arg(long, short = 'u', global = true, env = "EVENTUM_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    url: String,
+
+    /// SQLite database file
+    #[arg(long, global = true, env = "EVENTUM_INDEXER_DB", default_value = "eventum.db")]
+    db: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Sync, then keep polling for new transactions
+    Run {
+        /// Seconds between syncs
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+    },
+    /// Sync once and print what was added
+    Sync,
+    /// List indexed markets
+    Markets,
+    /// Show one market
+    Market { market: String },
+    /// Latest trades in a market, newest first
+    Trades {
+        market: String,
+        #[arg(long, default_value_t = 100)]
+        limit: usize,
+    },
+    /// YES price candles for a market
+    History {
+        market: String,
+        /// Candle length in seconds
+        #[arg(long, default_value_t = 3600)]
+        interval: i64,
+    },
+    /// Trade volume, over all markets unless one is given
+    Volume {
+        #[arg(long)]
+        market: Option<String>,
+        /// Only trades at or after this unix timestamp
+        #[arg(long)]
+        since: Option<i64>,
+    },
+    /// Claims paid out by a market
+    Claims { market: String },
+    /// A user's activity per market
+    Positions { user: String },
+}
+
+fn pubkey(s: &str) -> Result<Pubkey> {
+    Pubkey::from_str(s).with_context(|| format!("invalid address {s}"))
+}
+
+fn print<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let store = Store::open(&cli.db).with_context(|| format!("opening {}", cli.db.display()))?;
+
+    match cli.command {
+        Command::Run { interval } => {
+            let mut indexer = Indexer::new(EventumClient::new(cli.url), store);
+            loop {
+                match indexer.sync() {
+                    Ok(report) if report.transactions > 0 => {
+                        eprintln!("{}", serde_json::to_string(&report)?)
+                    }
+                    Ok(_) => {}
+                    // a node hiccup should not stop the indexer; the cursor
+                    // only moves past stored transactions
+                    Err(e) => eprintln!("sync failed: {e}"),
+                }
+                thread::sleep(Duration::from_secs(interval));
+            }
+        }
+        Command::Sync => {
+            let mut indexer = Indexer::new(EventumClient::new(cli.url), store);
+            print(&indexer.sync()?)
+        }
+        Command::Markets => print(&store.markets()?),
+        Command::Market { market } => {
+            let row = store.market(&pubkey(&market)?)?;
+            print(&row.with_context(|| format!("market {market} is not indexed"))?)
+        }
+        Command::Trades { market, limit } => print(&store.trades(&pubkey(&market)?, limit)?),
+        Command::History { market, interval } => {
+            print(&store.price_history(&pubkey(&market)?, interval)?)
+        }
+        Command::Volume { market, since } => {
+            let market = market.as_deref().map(pubkey).transpose()?;
+            print(&store.volume(market.as_ref(), since)?)
+        }
+        Command::Claims { market } => print(&store.claims(&pubkey(&market)?)?),
+        Command::Positions { user } => print(&store.positions(&pubkey(&user)?)?),
+    }
+}
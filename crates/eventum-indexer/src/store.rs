@@ -0,0 +1,597 @@
+//! SQLite storage and the queries dashboards run against it. Amounts are
+//! raw token units and lamports; prices are scaled by `PRICE_SCALE`.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use eventum_client::eventum::states::PRICE_SCALE;
+use eventum_client::names::{fee_model_name, outcome_name, pricing_mode_name, side_name};
+use eventum_client::{Market, MarketState};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::decode::{MarketCreation, ProgramEvent, TransactionRecord};
+use crate::error::{IndexerError, Result};
+
+/// How long a query waits for another connection's write to finish.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS markets (
+    address           TEXT PRIMARY KEY,
+    creator           TEXT NOT NULL,
+    unique_market_id  INTEGER NOT NULL,
+    question          TEXT NOT NULL,
+    oracle_authority  TEXT NOT NULL,
+    pricing_mode      TEXT NOT NULL,
+    fee_bps           INTEGER NOT NULL,
+    fee_model         TEXT NOT NULL,
+    start_time        INTEGER NOT NULL,
+    end_time          INTEGER NOT NULL,
+    resolved          INTEGER NOT NULL DEFAULT 0,
+    outcome           TEXT,
+    yes_supply        INTEGER NOT NULL DEFAULT 0,
+    no_supply         INTEGER NOT NULL DEFAULT 0,
+    decimals          INTEGER,
+    price_yes         INTEGER NOT NULL,
+    created_slot      INTEGER,
+    created_signature TEXT,
+    closed            INTEGER NOT NULL DEFAULT 0,
+    account           BLOB
+);
+CREATE TABLE IF NOT EXISTS trades (
+    signature   TEXT NOT NULL,
+    seq         INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    block_time  INTEGER,
+    market      TEXT NOT NULL,
+    user        TEXT NOT NULL,
+    side        TEXT NOT NULL,
+    tokens      INTEGER NOT NULL,
+    cost        INTEGER NOT NULL,
+    fee         INTEGER NOT NULL,
+    yes_supply  INTEGER NOT NULL,
+    no_supply   INTEGER NOT NULL,
+    price_yes   INTEGER NOT NULL,
+    PRIMARY KEY (signature, seq)
+);
+CREATE INDEX IF NOT EXISTS trades_market ON trades (market, slot);
+CREATE INDEX IF NOT EXISTS trades_user ON trades (user);
+CREATE TABLE IF NOT EXISTS resolutions (
+    market      TEXT PRIMARY KEY,
+    outcome     TEXT NOT NULL,
+    signature   TEXT NOT NULL,
+    slot        INTEGER NOT NULL,
+    block_time  INTEGER
+);
+CREATE TABLE IF NOT EXISTS claims (
+    signature   TEXT NOT NULL,
+    seq         INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    block_time  INTEGER,
+    market      TEXT NOT NULL,
+    holder      TEXT NOT NULL,
+    payout      INTEGER NOT NULL,
+    PRIMARY KEY (signature, seq)
+);
+CREATE INDEX IF NOT EXISTS claims_holder ON claims (holder);
+CREATE TABLE IF NOT EXISTS cursor (
+    id          INTEGER PRIMARY KEY CHECK (id = 0),
+    signature   TEXT NOT NULL,
+    slot        INTEGER NOT NULL
+);
+";
+
+const MARKET_COLUMNS: &str = "m.address, m.creator, m.unique_market_id, m.question, m.oracle_authority,
+    m.pricing_mode, m.fee_bps, m.fee_model, m.start_time, m.end_time, m.resolved, m.outcome,
+    r.block_time, m.yes_supply, m.no_supply, m.price_yes, m.created_slot, m.created_signature, m.closed";
+
+/// `account`, `yes_supply`, `no_supply` and `decimals` of a market row.
+type StoredAccount = (Option<Vec<u8>>, u64, u64, Option<u8>);
+
+const TRADE_COLUMNS: &str =
+    "signature, slot, block_time, market, user, side, tokens, cost, fee, yes_supply, no_supply, price_yes";
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MarketRow {
+    pub address: String,
+    pub creator: String,
+    pub unique_market_id: u64,
+    pub question: String,
+    pub oracle_authority: String,
+    pub pricing_mode: String,
+    pub fee_bps: u32,
+    pub fee_model: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub resolved: bool,
+    pub outcome: Option<String>,
+    /// Block time of the indexed `resolve_market`.
+    pub resolved_at: Option<i64>,
+    pub yes_supply: u64,
+    pub no_supply: u64,
+    pub price_yes: u64,
+    pub created_slot: Option<u64>,
+    pub created_signature: Option<String>,
+    /// The `Market` account no longer exists (`close_market`).
+    pub closed: bool,
+}
+
+impl MarketRow {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            address: row.get(0)?,
+            creator: row.get(1)?,
+            unique_market_id: row.get(2)?,
+            question: row.get(3)?,
+            oracle_authority: row.get(4)?,
+            pricing_mode: row.get(5)?,
+            fee_bps: row.get(6)?,
+            fee_model: row.get(7)?,
+            start_time: row.get(8)?,
+            end_time: row.get(9)?,
+            resolved: row.get(10)?,
+            outcome: row.get(11)?,
+            resolved_at: row.get(12)?,
+            yes_supply: row.get(13)?,
+            no_supply: row.get(14)?,
+            price_yes: row.get(15)?,
+            created_slot: row.get(16)?,
+            created_signature: row.get(17)?,
+            closed: row.get(18)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TradeRow {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub market: String,
+    pub user: String,
+    pub side: String,
+    pub tokens: u64,
+    pub cost: u64,
+    pub fee: u64,
+    pub yes_supply: u64,
+    pub no_supply: u64,
+    /// YES price after the trade.
+    pub price_yes: u64,
+}
+
+impl TradeRow {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            signature: row.get(0)?,
+            slot: row.get(1)?,
+            block_time: row.get(2)?,
+            market: row.get(3)?,
+            user: row.get(4)?,
+            side: row.get(5)?,
+            tokens: row.get(6)?,
+            cost: row.get(7)?,
+            fee: row.get(8)?,
+            yes_supply: row.get(9)?,
+            no_supply: row.get(10)?,
+            price_yes: row.get(11)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ClaimRow {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub market: String,
+    pub holder: String,
+    pub payout: u64,
+}
+
+/// YES price and volume over one `interval` of time, from trades with a
+/// block time. `open` is the price before the first trade of the bucket.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Candle {
+    pub start: i64,
+    pub open: u64,
+    pub high: u64,
+    pub low: u64,
+    pub close: u64,
+    pub trades: u64,
+    pub tokens: u64,
+    /// Lamports paid into the pool, fees excluded.
+    pub volume: u64,
+    pub fees: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Volume {
+    pub trades: u64,
+    pub tokens: u64,
+    pub volume: u64,
+    pub fees: u64,
+}
+
+/// A user's activity in one market, from the indexed history: tokens bought
+/// and lamports paid and claimed. Token transfers between wallets are not
+/// indexed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct PositionRow {
+    pub market: String,
+    pub yes_bought: u64,
+    pub no_bought: u64,
+    /// Lamports paid, fees included.
+    pub spent: u64,
+    pub fees: u64,
+    pub claimed: u64,
+}
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    /// Opens (creating if needed) the database at `path`. Readers in other
+    /// processes, like the HTTP API, can query it while the indexer writes.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |_| Ok(()))?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        Self::with_connection(conn)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// The newest transaction applied, where the next sync resumes.
+    pub fn cursor(&self) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row("SELECT signature FROM cursor WHERE id = 0", [], |row| row.get(0))
+            .optional()?)
+    }
+
+    /// Stores one transaction's rows and moves the cursor to it, atomically.
+    /// Applying the same transaction twice changes nothing.
+    pub fn apply(&mut self, record: &TransactionRecord) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        for creation in &record.creations {
+            insert_creation(&tx, creation, record)?;
+        }
+        for (seq, event) in record.events.iter().enumerate() {
+            match event {
+                ProgramEvent::Bought(e) => {
+                    tx.execute(
+                        &format!(
+                            "INSERT OR IGNORE INTO trades (seq, {TRADE_COLUMNS})
+                             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"
+                        ),
+                        params![
+                            seq,
+                            record.signature,
+                            record.slot,
+                            record.block_time,
+                            e.market.to_string(),
+                            e.user.to_string(),
+                            side_name(e.side),
+                            e.tokens,
+                            e.cost,
+                            e.fee,
+                            e.yes_supply,
+                            e.no_supply,
+                            e.price_yes,
+                        ],
+                    )?;
+                    tx.execute(
+                        "UPDATE markets SET yes_supply = ?2, no_supply = ?3, price_yes = ?4 WHERE address = ?1",
+                        params![e.market.to_string(), e.yes_supply, e.no_supply, e.price_yes],
+                    )?;
+                }
+                ProgramEvent::Resolved(e) => {
+                    tx.execute(
+                        "INSERT OR IGNORE INTO resolutions (market, outcome, signature, slot, block_time)
+                         VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![
+                            e.market.to_string(),
+                            side_name(e.outcome),
+                            record.signature,
+                            record.slot,
+                            record.block_time,
+                        ],
+                    )?;
+                    tx.execute(
+                        "UPDATE markets SET resolved = 1, outcome = ?2 WHERE address = ?1",
+                        params![e.market.to_string(), side_name(e.outcome)],
+                    )?;
+                }
+                ProgramEvent::Claimed(e) => {
+                    tx.execute(
+                        "INSERT OR IGNORE INTO claims (signature, seq, slot, block_time, market, holder, payout)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                        params![
+                            record.signature,
+                            seq,
+                            record.slot,
+                            record.block_time,
+                            e.market.to_string(),
+                            e.holder.to_string(),
+                            e.payout,
+                        ],
+                    )?;
+                }
+            }
+        }
+        tx.execute(
+            "INSERT INTO cursor (id, signature, slot) VALUES (0, ?1, ?2)
+             ON CONFLICT (id) DO UPDATE SET signature = excluded.signature, slot = excluded.slot",
+            params![record.signature, record.slot],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Replaces the stored state of every market with its current account
+    /// and marks markets missing from `states` as closed.
+    pub fn sync_markets(&mut self, states: &[MarketState]) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute("UPDATE markets SET closed = 1", [])?;
+        for state in states {
+            let m = &state.market;
+            let mut account = Vec::new();
+            m.try_serialize(&mut account)
+                .map_err(|e| IndexerError::Decode(format!("market {}: {e}", state.address)))?;
+            let price_yes = state.price_yes()?;
+            tx.execute(
+                "INSERT INTO markets (address, creator, unique_market_id, question, oracle_authority,
+                     pricing_mode, fee_bps, fee_model, start_time, end_time, resolved, outcome,
+                     yes_supply, no_supply, decimals, price_yes, closed, account)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, 0, ?17)
+                 ON CONFLICT (address) DO UPDATE SET
+                     question = excluded.question, oracle_authority = excluded.oracle_authority,
+                     end_time = excluded.end_time, resolved = excluded.resolved,
+                     outcome = excluded.outcome, yes_supply = excluded.yes_supply,
+                     no_supply = excluded.no_supply, decimals = excluded.decimals, price_yes = excluded.price_yes,
+                     closed = 0, account = excluded.account",
+                params![
+                    state.address.to_string(),
+                    m.creator.to_string(),
+                    m.unique_market_id,
+                    m.question,
+                    m.oracle_authority.to_string(),
+                    pricing_mode_name(m.pricing_mode),
+                    m.fee,
+                    fee_model_name(m.fee_model),
+                    m.start_time,
+                    m.end_time,
+                    m.resolved,
+                    outcome_name(m),
+                    state.yes_supply,
+                    state.no_supply,
+                    state.decimals,
+                    price_yes,
+                    account,
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn markets(&self) -> Result<Vec<MarketRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {MARKET_COLUMNS} FROM markets m LEFT JOIN resolutions r ON r.market = m.address
+             ORDER BY m.start_time DESC, m.address"
+        ))?;
+        let rows = stmt.query_map([], MarketRow::from_row)?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn market(&self, address: &Pubkey) -> Result<Option<MarketRow>> {
+        Ok(self
+            .conn
+            .query_row(
+                &format!(
+                    "SELECT {MARKET_COLUMNS} FROM markets m LEFT JOIN resolutions r ON r.market = m.address
+                     WHERE m.address = ?1"
+                ),
+                [address.to_string()],
+                MarketRow::from_row,
+            )
+            .optional()?)
+    }
+
+    /// The market as last seen on-chain with its indexed supplies, for
+    /// quoting with the program's pricing code. `None` until an account
+    /// sync has seen the market.
+    pub fn market_state(&self, address: &Pubkey) -> Result<Option<MarketState>> {
+        let row: Option<StoredAccount> = self
+            .conn
+            .query_row(
+                "SELECT account, yes_supply, no_supply, decimals FROM markets WHERE address = ?1",
+                [address.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .optional()?;
+        let Some((Some(account), yes_supply, no_supply, Some(decimals))) = row else {
+            return Ok(None);
+        };
+        let market = Market::try_deserialize(&mut account.as_slice())
+            .map_err(|e| IndexerError::Decode(format!("market {address}: {e}")))?;
+        Ok(Some(MarketState { address: *address, market, yes_supply, no_supply, decimals }))
+    }
+
+    /// The latest `limit` trades in `market`, newest first.
+    pub fn trades(&self, market: &Pubkey, limit: usize) -> Result<Vec<TradeRow>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {TRADE_COLUMNS} FROM trades WHERE market = ?1
+             ORDER BY slot DESC, signature DESC, seq DESC LIMIT ?2"
+        ))?;
+        let rows = stmt.query_map(params![market.to_string(), limit], TradeRow::from_row)?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn claims(&self, market: &Pubkey) -> Result<Vec<ClaimRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT signature, slot, block_time, market, holder, payout FROM claims
+             WHERE market = ?1 ORDER BY slot, signature, seq",
+        )?;
+        let rows = stmt.query_map([market.to_string()], |row| {
+            Ok(ClaimRow {
+                signature: row.get(0)?,
+                slot: row.get(1)?,
+                block_time: row.get(2)?,
+                market: row.get(3)?,
+                holder: row.get(4)?,
+                payout: row.get(5)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// `market`'s YES price and volume in buckets of `interval` seconds,
+    /// oldest first. Buckets without trades are left out.
+    pub fn price_history(&self, market: &Pubkey, interval: i64) -> Result<Vec<Candle>> {
+        if interval <= 0 {
+            return Err(IndexerError::Decode(format!("interval {interval}")));
+        }
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {TRADE_COLUMNS} FROM trades WHERE market = ?1 AND block_time IS NOT NULL
+             ORDER BY slot, signature, seq"
+        ))?;
+        let trades = stmt.query_map([market.to_string()], TradeRow::from_row)?;
+
+        let mut candles: Vec<Candle> = Vec::new();
+        let mut previous = PRICE_SCALE / 2;
+        for trade in trades {
+            let trade = trade?;
+            let time = trade.block_time.unwrap_or_default();
+            let start = time - time.rem_euclid(interval);
+            match candles.last_mut() {
+                Some(candle) if candle.start == start => {
+                    candle.high = candle.high.max(trade.price_yes);
+                    candle.low = candle.low.min(trade.price_yes);
+                    candle.close = trade.price_yes;
+                    candle.trades += 1;
+                    candle.tokens += trade.tokens;
+                    candle.volume += trade.cost;
+                    candle.fees += trade.fee;
+                }
+                _ => candles.push(Candle {
+                    start,
+                    open: previous,
+                    high: previous.max(trade.price_yes),
+                    low: previous.min(trade.price_yes),
+                    close: trade.price_yes,
+                    trades: 1,
+                    tokens: trade.tokens,
+                    volume: trade.cost,
+                    fees: trade.fee,
+                }),
+            }
+            previous = trade.price_yes;
+        }
+        Ok(candles)
+    }
+
+    /// Totals over every trade (or `market`'s) with a block time at or after
+    /// `since`, if given.
+    pub fn volume(&self, market: Option<&Pubkey>, since: Option<i64>) -> Result<Volume> {
+        Ok(self.conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(tokens), 0), COALESCE(SUM(cost), 0), COALESCE(SUM(fee), 0)
+             FROM trades
+             WHERE (?1 IS NULL OR market = ?1) AND (?2 IS NULL OR block_time >= ?2)",
+            params![market.map(Pubkey::to_string), since],
+            |row| {
+                Ok(Volume {
+                    trades: row.get(0)?,
+                    tokens: row.get(1)?,
+                    volume: row.get(2)?,
+                    fees: row.get(3)?,
+                })
+            },
+        )?)
+    }
+
+    /// `user`'s activity per market, by market address.
+    pub fn positions(&self, user: &Pubkey) -> Result<Vec<PositionRow>> {
+        let user = user.to_string();
+        let mut positions: BTreeMap<String, PositionRow> = BTreeMap::new();
+        let mut stmt = self.conn.prepare(
+            "SELECT market,
+                 COALESCE(SUM(CASE WHEN side = 'yes' THEN tokens END), 0),
+                 COALESCE(SUM(CASE WHEN side = 'no' THEN tokens END), 0),
+                 SUM(cost + fee), SUM(fee)
+             FROM trades WHERE user = ?1 GROUP BY market",
+        )?;
+        let rows = stmt.query_map([&user], |row| {
+            Ok(PositionRow {
+                market: row.get(0)?,
+                yes_bought: row.get(1)?,
+                no_bought: row.get(2)?,
+                spent: row.get(3)?,
+                fees: row.get(4)?,
+                claimed: 0,
+            })
+        })?;
+        for row in rows {
+            let row = row?;
+            positions.insert(row.market.clone(), row);
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT market, SUM(payout) FROM claims WHERE holder = ?1 GROUP BY market")?;
+        let claims = stmt.query_map([&user], |row| Ok((row.get::<_, String>(0)?, row.get::<_, u64>(1)?)))?;
+        for claim in claims {
+            let (market, claimed) = claim?;
+            positions
+                .entry(market.clone())
+                .or_insert_with(|| PositionRow { market, ..PositionRow::default() })
+                .claimed = claimed;
+        }
+        Ok(positions.into_values().collect())
+    }
+}
+
+/// A market row from its `initialize_market` arguments, until an account
+/// sync fills in (or has already filled in) the rest.
+fn insert_creation(
+    tx: &rusqlite::Transaction,
+    creation: &MarketCreation,
+    record: &TransactionRecord,
+) -> Result<()> {
+    let args = &creation.args;
+    tx.execute(
+        "INSERT INTO markets (address, creator, unique_market_id, question, oracle_authority,
+             pricing_mode, fee_bps, fee_model, start_time, end_time, price_yes,
+             created_slot, created_signature)
+         VALUES (?1, ?2, ?3, ?4, ?2, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+         ON CONFLICT (address) DO UPDATE SET
+             created_slot = excluded.created_slot, created_signature = excluded.created_signature",
+        params![
+            creation.market.to_string(),
+            creation.creator.to_string(),
+            args.unique_market_id,
+            args.question,
+            pricing_mode_name(args.pricing_mode),
+            args.fee,
+            fee_model_name(args.fee_model),
+            record.block_time.unwrap_or_default(),
+            args.end_time,
+            PRICE_SCALE / 2,
+            record.slot,
+            record.signature,
+        ],
+    )?;
+    Ok(())
+}
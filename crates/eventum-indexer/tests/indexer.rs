@@ -0,0 +1,296 @@
+//! Decoding and storage tests, offline: transactions are built in memory
+//! the way `getTransaction` returns them.
+
+use anchor_lang::Event;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use eventum_client::eventum::events::{MarketResolved, OutcomesBought, WinningsClaimed};
+use eventum_client::eventum::states::PRICE_SCALE;
+use eventum_client::{
+    instructions, pda, ConfirmedTransaction, FeeModel, InitializeMarketArgs, Market, MarketState,
+    PricingMode, Side, TradeLimits, PROGRAM_ID,
+};
+use eventum_indexer::decode::{events_from_logs, ProgramEvent, TransactionRecord};
+use eventum_indexer::store::{Candle, Volume};
+use eventum_indexer::Store;
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+
+const ONE_TOKEN: u64 = 1_000_000_000;
+
+fn args(unique_market_id: u64) -> InitializeMarketArgs {
+    InitializeMarketArgs {
+        unique_market_id,
+        end_time: 10_000,
+        fee: 100,
+        fee_model: FeeModel::Flat,
+        referral_share_bps: 0,
+        pricing_mode: PricingMode::Lmsr { b: 1000 },
+        limits: TradeLimits::default(),
+        question: "Will it rain?".into(),
+    }
+}
+
+fn data_line(event: &impl Event) -> String {
+    format!("Program data: {}", BASE64_STANDARD.encode(event.data()))
+}
+
+fn confirmed(tx: Transaction, slot: u64, logs: Vec<String>) -> ConfirmedTransaction {
+    let transaction = VersionedTransaction::from(tx);
+    ConfirmedTransaction {
+        slot,
+        block_time: Some(slot as i64 * 10),
+        account_keys: transaction.message.static_account_keys().to_vec(),
+        transaction,
+        log_messages: logs,
+        err: None,
+    }
+}
+
+fn bought(market: Pubkey, user: Pubkey, side: Side, tokens: u64, price_yes: u64) -> OutcomesBought {
+    OutcomesBought {
+        market,
+        user,
+        side,
+        tokens,
+        cost: tokens / 2,
+        fee: tokens / 200,
+        yes_supply: 0,
+        no_supply: 0,
+        price_yes,
+    }
+}
+
+/// A record of `events`, at `block_time`.
+fn record(signature: &str, slot: u64, block_time: i64, events: Vec<ProgramEvent>) -> TransactionRecord {
+    TransactionRecord {
+        signature: signature.into(),
+        slot,
+        block_time: Some(block_time),
+        creations: Vec::new(),
+        events,
+    }
+}
+
+fn market_state(creator: Pubkey, yes_supply: u64, no_supply: u64) -> MarketState {
+    let address = pda::market_address(&creator, 0).0;
+    MarketState {
+        address,
+        market: Market {
+            creator,
+            oracle_authority: creator,
+            unique_market_id: 0,
+            start_time: 5,
+            end_time: 10_000,
+            resolved: false,
+            winning_outcome: false,
+            question: "Will it rain?".into(),
+            vault_bump: pda::vault_address(&address).1,
+            fee: 100,
+            total_liquidity: 0,
+            yes_mint: pda::yes_mint_address(&address).0,
+            no_mint: pda::no_mint_address(&address).0,
+            yes_tokens: 0,
+            no_tokens: 0,
+            bump: pda::market_address(&creator, 0).1,
+            approved_creator: false,
+            fee_model: FeeModel::Flat,
+            referral_share_bps: 0,
+            pricing_mode: PricingMode::Lmsr { b: 1000 },
+            yes_reserve: 0,
+            no_reserve: 0,
+            limits: TradeLimits::default(),
+            price_oracle: Pubkey::default(),
+            fees_collected: 0,
+        },
+        yes_supply,
+        no_supply,
+        decimals: 9,
+    }
+}
+
+#[test]
+fn events_are_attributed_to_the_invoking_program() {
+    let market = Pubkey::new_unique();
+    let other = Pubkey::new_unique();
+    let router = Pubkey::new_unique();
+    let logs = vec![
+        format!("Program {router} invoke [1]"),
+        // another program logging data that happens to decode
+        data_line(&MarketResolved { market, outcome: Side::No }),
+        format!("Program {PROGRAM_ID} invoke [2]"),
+        "Program log: Instruction: BuyOutcomes".into(),
+        format!("Program {other} invoke [3]"),
+        data_line(&MarketResolved { market, outcome: Side::No }),
+        format!("Program {other} success"),
+        data_line(&bought(market, router, Side::Yes, ONE_TOKEN, 600_000_000)),
+        format!("Program {PROGRAM_ID} consumed 50000 of 190000 compute units"),
+        format!("Program {PROGRAM_ID} success"),
+        format!("Program {router} success"),
+        format!("Program {PROGRAM_ID} invoke [1]"),
+        data_line(&MarketResolved { market, outcome: Side::Yes }),
+        "Program data: not-base64!".into(),
+        format!("Program {PROGRAM_ID} success"),
+    ];
+
+    let events = events_from_logs(&PROGRAM_ID, &logs);
+    assert_eq!(events.len(), 2);
+    let ProgramEvent::Bought(e) = &events[0] else { panic!("{events:?}") };
+    assert_eq!((e.market, e.user, e.tokens), (market, router, ONE_TOKEN));
+    let ProgramEvent::Resolved(e) = &events[1] else { panic!("{events:?}") };
+    assert_eq!(e.outcome, Side::Yes);
+}
+
+#[test]
+fn initialize_market_instructions_are_decoded() {
+    let creator = Pubkey::new_unique();
+    let ix = instructions::initialize_market(&creator, &creator, false, args(3));
+    let tx = confirmed(Transaction::new_with_payer(&[ix], Some(&creator)), 7, Vec::new());
+
+    let record = TransactionRecord::decode(&PROGRAM_ID, "sig", &tx);
+    assert_eq!(record.creations.len(), 1);
+    let creation = &record.creations[0];
+    assert_eq!(creation.creator, creator);
+    assert_eq!(creation.market, pda::market_address(&creator, 3).0);
+    assert_eq!(creation.args.question, "Will it rain?");
+
+    // a failed transaction changed nothing
+    let failed = ConfirmedTransaction { err: Some(json!({"InstructionError": [0, "Custom"]})), ..tx };
+    assert!(TransactionRecord::decode(&PROGRAM_ID, "sig", &failed).creations.is_empty());
+}
+
+#[test]
+fn replay_builds_markets_trades_resolutions_and_claims() {
+    let creator = Pubkey::new_unique();
+    let alice = Pubkey::new_unique();
+    let market = pda::market_address(&creator, 0).0;
+    let ix = instructions::initialize_market(&creator, &creator, false, args(0));
+    let tx = confirmed(Transaction::new_with_payer(&[ix], Some(&creator)), 1, Vec::new());
+
+    let mut store = Store::open_in_memory().unwrap();
+    assert_eq!(store.cursor().unwrap(), None);
+    store.apply(&TransactionRecord::decode(&PROGRAM_ID, "create", &tx)).unwrap();
+    let buy = record(
+        "buy",
+        2,
+        20,
+        vec![
+            ProgramEvent::Bought(bought(market, alice, Side::Yes, 4 * ONE_TOKEN, 600_000_000)),
+            ProgramEvent::Bought(bought(market, alice, Side::No, ONE_TOKEN, 580_000_000)),
+        ],
+    );
+    store.apply(&buy).unwrap();
+    // replaying is harmless
+    store.apply(&buy).unwrap();
+    store
+        .apply(&record("resolve", 3, 30, vec![ProgramEvent::Resolved(MarketResolved { market, outcome: Side::Yes })]))
+        .unwrap();
+    store
+        .apply(&record(
+            "claim",
+            4,
+            40,
+            vec![ProgramEvent::Claimed(WinningsClaimed { market, holder: alice, payout: 4 * ONE_TOKEN })],
+        ))
+        .unwrap();
+    assert_eq!(store.cursor().unwrap().as_deref(), Some("claim"));
+
+    let row = store.market(&market).unwrap().unwrap();
+    assert_eq!(row.created_signature.as_deref(), Some("create"));
+    assert_eq!((row.start_time, row.pricing_mode.as_str()), (10, "lmsr:1000"));
+    assert_eq!((row.resolved, row.outcome.as_deref(), row.resolved_at), (true, Some("yes"), Some(30)));
+    assert_eq!(row.price_yes, 580_000_000);
+
+    let trades = store.trades(&market, 10).unwrap();
+    assert_eq!(trades.len(), 2);
+    assert_eq!(trades[0].side, "no");
+
+    let volume = store.volume(Some(&market), None).unwrap();
+    assert_eq!(
+        volume,
+        Volume { trades: 2, tokens: 5 * ONE_TOKEN, volume: 5 * ONE_TOKEN / 2, fees: 5 * ONE_TOKEN / 200 }
+    );
+    assert_eq!(store.volume(None, Some(21)).unwrap(), Volume::default());
+
+    let positions = store.positions(&alice).unwrap();
+    assert_eq!(positions.len(), 1);
+    let position = &positions[0];
+    assert_eq!((position.yes_bought, position.no_bought), (4 * ONE_TOKEN, ONE_TOKEN));
+    assert_eq!(position.spent, 5 * ONE_TOKEN / 2 + 5 * ONE_TOKEN / 200);
+    assert_eq!(position.claimed, 4 * ONE_TOKEN);
+    assert_eq!(store.claims(&market).unwrap().len(), 1);
+}
+
+#[test]
+fn price_history_buckets_trades() {
+    let market = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let mut store = Store::open_in_memory().unwrap();
+    let prices = [(100, 550), (130, 520), (350, 600), (610, 400)];
+    for (i, (time, milli)) in prices.into_iter().enumerate() {
+        let price = milli * PRICE_SCALE / 1000;
+        let trade = bought(market, user, Side::Yes, ONE_TOKEN, price);
+        store.apply(&record(&format!("s{i}"), i as u64, time, vec![ProgramEvent::Bought(trade)])).unwrap();
+    }
+
+    let candles = store.price_history(&market, 300).unwrap();
+    let half = ONE_TOKEN / 2;
+    assert_eq!(
+        candles,
+        vec![
+            Candle {
+                start: 0,
+                open: 500_000_000,
+                high: 550_000_000,
+                low: 500_000_000,
+                close: 520_000_000,
+                trades: 2,
+                tokens: 2 * ONE_TOKEN,
+                volume: 2 * half,
+                fees: 2 * (ONE_TOKEN / 200),
+            },
+            Candle {
+                start: 300,
+                open: 520_000_000,
+                high: 600_000_000,
+                low: 520_000_000,
+                close: 600_000_000,
+                trades: 1,
+                tokens: ONE_TOKEN,
+                volume: half,
+                fees: ONE_TOKEN / 200,
+            },
+            Candle {
+                start: 600,
+                open: 600_000_000,
+                high: 600_000_000,
+                low: 400_000_000,
+                close: 400_000_000,
+                trades: 1,
+                tokens: ONE_TOKEN,
+                volume: half,
+                fees: ONE_TOKEN / 200,
+            },
+        ]
+    );
+}
+
+#[test]
+fn account_sync_keeps_quotable_state_and_marks_closed_markets() {
+    let creator = Pubkey::new_unique();
+    let state = market_state(creator, 3 * ONE_TOKEN, ONE_TOKEN);
+    let mut store = Store::open_in_memory().unwrap();
+    assert!(store.market_state(&state.address).unwrap().is_none());
+
+    store.sync_markets(std::slice::from_ref(&state)).unwrap();
+    let stored = store.market_state(&state.address).unwrap().unwrap();
+    assert_eq!((stored.yes_supply, stored.no_supply, stored.decimals), (3 * ONE_TOKEN, ONE_TOKEN, 9));
+    assert_eq!(stored.price_yes().unwrap(), state.price_yes().unwrap());
+    assert_eq!(stored.quote_buy(Side::No, 2, 6).unwrap(), state.quote_buy(Side::No, 2, 6).unwrap());
+    let row = store.market(&state.address).unwrap().unwrap();
+    assert_eq!(row.price_yes, state.price_yes().unwrap());
+    assert!(!row.closed);
+
+    store.sync_markets(&[]).unwrap();
+    assert!(store.market(&state.address).unwrap().unwrap().closed);
+}
@@ -0,0 +1,51 @@
+//! Append-only audit log, one JSON object per line:
+//! `{"time": <unix>, "market": "<address>", "event": "<kind>", ...}`.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::error::Result;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// The market expired but its source does not know the outcome yet.
+    Pending { source: String },
+    /// The market expired and no source is configured for it.
+    NoSource,
+    SourceFailed { source: String, error: String },
+    /// About to send `resolve_market`.
+    Resolving { source: String, outcome: String },
+    AttemptFailed { attempt: u32, error: String },
+    Resolved { outcome: String, signature: String, attempts: u32 },
+    /// Someone else resolved the market first.
+    AlreadyResolved,
+    GaveUp { attempts: u32 },
+}
+
+#[derive(Serialize)]
+struct Entry<'a> {
+    time: u64,
+    market: String,
+    #[serde(flatten)]
+    event: &'a AuditEvent,
+}
+
+pub struct AuditLog {
+    pub path: PathBuf,
+}
+
+impl AuditLog {
+    pub fn record(&self, market: &Pubkey, event: &AuditEvent) -> Result<()> {
+        let time = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        let line = serde_json::to_string(&Entry { time, market: market.to_string(), event })?;
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{line}")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+//! The resolver's JSON config: a source per market, a default for the rest,
+//! and the retry policy.
+//!
+//! ```json
+//! {
+//!   "default": { "type": "file", "path": "outcomes.json" },
+//!   "markets": {
+//!     "<MARKET>": { "type": "webhook", "url": "http://127.0.0.1:9000/outcome" },
+//!     "<MARKET>": { "type": "price_feed", "account": "<FEED>", "offset": 8, "threshold": 100000 }
+//!   },
+//!   "retry": { "max_attempts": 5, "backoff_ms": 1000 }
+//! }
+//! ```
+//!
+//! Relative file paths are relative to the config file.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use eventum_client::RpcClient;
+use serde::Deserialize;
+
+use crate::error::{ResolverError, Result};
+use crate::resolver::Sources;
+use crate::retry::RetryPolicy;
+use crate::source::{parse_pubkey, FileSource, OutcomeSource, PriceFeedSource, WebhookSource};
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SourceConfig {
+    File { path: PathBuf },
+    Webhook { url: String },
+    PriceFeed { account: String, offset: usize, threshold: i64 },
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Config {
+    /// Source for markets not listed in `markets`; without one they are
+    /// left unresolved.
+    #[serde(default)]
+    pub default: Option<SourceConfig>,
+    #[serde(default)]
+    pub markets: HashMap<String, SourceConfig>,
+    #[serde(default)]
+    pub retry: RetryPolicy,
+}
+
+impl SourceConfig {
+    fn build(&self, rpc_url: &str) -> Result<Box<dyn OutcomeSource>> {
+        Ok(match self {
+            SourceConfig::File { path } => Box::new(FileSource { path: path.clone() }),
+            SourceConfig::Webhook { url } => Box::new(WebhookSource::new(url.clone())),
+            SourceConfig::PriceFeed { account, offset, threshold } => Box::new(PriceFeedSource {
+                rpc: RpcClient::new(rpc_url),
+                account: parse_pubkey(account)?,
+                offset: *offset,
+                threshold: *threshold,
+            }),
+        })
+    }
+
+    fn relative_to(&mut self, dir: &Path) {
+        if let SourceConfig::File { path } = self {
+            if path.is_relative() {
+                *path = dir.join(&*path);
+            }
+        }
+    }
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Self> {
+        serde_json::from_str(text).map_err(|e| ResolverError::Config(e.to_string()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut config = Self::parse(&fs::read_to_string(path)?)?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        config.default.iter_mut().chain(config.markets.values_mut()).for_each(|s| s.relative_to(dir));
+        Ok(config)
+    }
+
+    /// The configured sources; price feeds are read through `rpc_url`.
+    pub fn sources(&self, rpc_url: &str) -> Result<Sources> {
+        let mut sources = Sources {
+            default: self.default.as_ref().map(|s| s.build(rpc_url)).transpose()?,
+            markets: HashMap::new(),
+        };
+        for (market, source) in &self.markets {
+            sources.markets.insert(parse_pubkey(market)?, source.build(rpc_url)?);
+        }
+        Ok(sources)
+    }
+}
@@ -0,0 +1,24 @@
+use eventum_client::ClientError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ResolverError {
+    #[error(transparent)]
+    Client(#[from] ClientError),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error("http request failed: {0}")]
+    Http(#[from] ureq::Error),
+
+    #[error("config: {0}")]
+    Config(String),
+
+    #[error("outcome source: {0}")]
+    Source(String),
+}
+
+pub type Result<T> = std::result::Result<T, ResolverError>;
@@ -0,0 +1,24 @@
+//! Automatic resolution of Eventum markets.
+//!
+//! - [`source`] answers "what was the outcome?" for a market: from a local
+//!   JSON file, a webhook, or a price feed account, or any other
+//!   [`OutcomeSource`].
+//! - [`config`] maps markets to sources.
+//! - [`Resolver`] watches the markets whose `oracle_authority` is its key,
+//!   waits for their `end_time` by cluster clock, asks their source and
+//!   sends `resolve_market`, retrying with backoff.
+//! - [`audit`] records every step as JSON lines.
+
+pub mod audit;
+pub mod config;
+pub mod error;
+pub mod resolver;
+pub mod retry;
+pub mod source;
+
+pub use audit::{AuditEvent, AuditLog};
+pub use config::Config;
+pub use error::{ResolverError, Result};
+pub use resolver::{is_due, Resolver, Sources};
+pub use retry::{retry, RetryPolicy};
+pub use source::{FileSource, OutcomeSource, PriceFeedSource, WebhookSource};
@@ -0,0 +1,74 @@
+//! `eventum-resolver`: resolve the markets this oracle key owns once they
+//! expire, from the sources in the config.
+
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use eventum_client::EventumClient;
+use eventum_resolver::{AuditLog, Config, Resolver};
+use solana_sdk::signature::{read_keypair_file, Signer};
+
+#[derive(Parser)]
+#[command(name = "eventum-resolver", version, about)]
+struct Cli {
+    /// RPC URL
+    #[arg(long, short = 'u', env = "EVENTUM_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    url: String,
+
+    /// Keypair of the markets' oracle authority, the `--oracle` they were
+    /// created with [default: ~/.config/solana/id.json]
+    #[arg(long, short = 'k')]
+    keypair: Option<PathBuf>,
+
+    /// Source config (JSON)
+    #[arg(long, short = 'c', default_value = "resolver.json")]
+    config: PathBuf,
+
+    /// Audit log, appended to as JSON lines
+    #[arg(long, default_value = "resolver-audit.jsonl")]
+    audit_log: PathBuf,
+
+    /// Seconds between passes
+    #[arg(long, default_value_t = 30)]
+    interval: u64,
+
+    /// Make a single pass and exit
+    #[arg(long)]
+    once: bool,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let keypair_path = match cli.keypair {
+        Some(path) => path,
+        None => PathBuf::from(std::env::var("HOME")?).join(".config/solana/id.json"),
+    };
+    let keypair = read_keypair_file(&keypair_path)
+        .map_err(|e| anyhow!("reading keypair {}: {e}", keypair_path.display()))?;
+    let config = Config::load(&cli.config).with_context(|| format!("loading {}", cli.config.display()))?;
+    let sources = config.sources(&cli.url)?;
+    eprintln!("resolving markets of oracle {}", keypair.pubkey());
+
+    let mut resolver = Resolver::new(
+        EventumClient::new(cli.url),
+        keypair,
+        sources,
+        AuditLog { path: cli.audit_log },
+        config.retry,
+    );
+    loop {
+        match resolver.tick() {
+            Ok(0) => {}
+            Ok(n) => eprintln!("resolved {n} market(s)"),
+            Err(e) if !cli.once => eprintln!("pass failed: {e}"),
+            Err(e) => return Err(e.into()),
+        }
+        if cli.once {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_secs(cli.interval));
+    }
+}
@@ -0,0 +1,132 @@
+//! The resolution loop.
+
+use std::collections::HashMap;
+
+use eventum_client::names::side_name;
+use eventum_client::{instructions, EventumClient, Market};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::audit::{AuditEvent, AuditLog};
+use crate::error::Result;
+use crate::retry::{retry, RetryPolicy};
+use crate::source::OutcomeSource;
+
+/// Outcome sources by market, with a fallback.
+#[derive(Default)]
+pub struct Sources {
+    pub default: Option<Box<dyn OutcomeSource>>,
+    pub markets: HashMap<Pubkey, Box<dyn OutcomeSource>>,
+}
+
+impl Sources {
+    pub fn get(&self, market: &Pubkey) -> Option<&dyn OutcomeSource> {
+        self.markets.get(market).or(self.default.as_ref()).map(|s| s.as_ref())
+    }
+}
+
+/// Whether `market` is waiting for its oracle at cluster time `now`.
+/// `resolve_market` does not check `end_time` itself, so the resolver must.
+pub fn is_due(market: &Market, now: i64) -> bool {
+    !market.resolved && now >= market.end_time
+}
+
+pub struct Resolver {
+    pub client: EventumClient,
+    /// The `oracle_authority` of the markets to resolve.
+    pub keypair: Keypair,
+    pub sources: Sources,
+    pub audit: AuditLog,
+    pub retry: RetryPolicy,
+    /// Last waiting state logged per market, so each pass does not repeat it.
+    waiting: HashMap<Pubkey, AuditEvent>,
+}
+
+impl Resolver {
+    pub fn new(client: EventumClient, keypair: Keypair, sources: Sources, audit: AuditLog, retry: RetryPolicy) -> Self {
+        Self { client, keypair, sources, audit, retry, waiting: HashMap::new() }
+    }
+
+    /// One pass over this oracle's markets; returns how many it resolved.
+    pub fn tick(&mut self) -> Result<usize> {
+        let now = self.client.rpc.get_unix_timestamp()?;
+        let mut markets = self.client.markets_for_oracle(&self.keypair.pubkey())?;
+        markets.retain(|(_, market)| is_due(market, now));
+        markets.sort_by_key(|(_, market)| market.end_time);
+
+        let mut resolved = 0;
+        for (address, _) in markets {
+            if self.resolve(&address)? {
+                resolved += 1;
+            }
+        }
+        Ok(resolved)
+    }
+
+    fn resolve(&mut self, address: &Pubkey) -> Result<bool> {
+        let Some(source) = self.sources.get(address) else {
+            self.wait(address, AuditEvent::NoSource)?;
+            return Ok(false);
+        };
+        let name = source.describe();
+        let outcome = match source.outcome(address) {
+            Ok(Some(outcome)) => outcome,
+            Ok(None) => {
+                self.wait(address, AuditEvent::Pending { source: name })?;
+                return Ok(false);
+            }
+            Err(e) => {
+                self.wait(address, AuditEvent::SourceFailed { source: name, error: e.to_string() })?;
+                return Ok(false);
+            }
+        };
+        self.waiting.remove(address);
+        self.audit.record(address, &AuditEvent::Resolving { source: name, outcome: side_name(outcome).into() })?;
+
+        let oracle = self.keypair.pubkey();
+        let ix = instructions::resolve_market(&oracle, address, outcome);
+        let mut already_resolved = false;
+        let mut attempts = 0;
+        let sent = retry(
+            &self.retry,
+            |attempt| {
+                attempts = attempt;
+                self.client.send(std::slice::from_ref(&ix), &self.keypair, &[])
+            },
+            |attempt, e| {
+                // a failed send may still have landed, or another resolver won
+                already_resolved = self.client.market(address).is_ok_and(|m| m.resolved);
+                let event = AuditEvent::AttemptFailed { attempt, error: e.to_string() };
+                self.audit.record(address, &event).is_ok() && !already_resolved
+            },
+        );
+        match sent {
+            Ok(signature) => {
+                let event = AuditEvent::Resolved {
+                    outcome: side_name(outcome).into(),
+                    signature: signature.to_string(),
+                    attempts,
+                };
+                self.audit.record(address, &event)?;
+                Ok(true)
+            }
+            Err(_) if already_resolved => {
+                self.audit.record(address, &AuditEvent::AlreadyResolved)?;
+                Ok(false)
+            }
+            Err(_) => {
+                self.audit.record(address, &AuditEvent::GaveUp { attempts })?;
+                Ok(false)
+            }
+        }
+    }
+
+    /// Logs a waiting state unless it is the one last logged for `market`.
+    fn wait(&mut self, market: &Pubkey, event: AuditEvent) -> Result<()> {
+        if self.waiting.get(market) != Some(&event) {
+            self.audit.record(market, &event)?;
+            self.waiting.insert(*market, event);
+        }
+        Ok(())
+    }
+}
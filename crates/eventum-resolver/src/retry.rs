@@ -0,0 +1,49 @@
+use std::thread;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// How often to try sending a resolution before waiting for the next pass.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    /// Wait before the second attempt; it doubles after each failure.
+    pub backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_attempts: 5, backoff_ms: 1000 }
+    }
+}
+
+impl RetryPolicy {
+    fn backoff(&self, failed_attempts: u32) -> Duration {
+        let factor = 1u64 << failed_attempts.saturating_sub(1).min(16);
+        Duration::from_millis(self.backoff_ms.saturating_mul(factor))
+    }
+}
+
+/// Runs `op` (given the 1-based attempt number) until it succeeds or
+/// `policy.max_attempts` have failed, reporting each failure to `on_error`.
+/// `on_error` returning `false` stops early, for failures retrying cannot fix.
+pub fn retry<T, E>(
+    policy: &RetryPolicy,
+    mut op: impl FnMut(u32) -> Result<T, E>,
+    mut on_error: impl FnMut(u32, &E) -> bool,
+) -> Result<T, E> {
+    let mut attempt = 1;
+    loop {
+        match op(attempt) {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                if !on_error(attempt, &e) || attempt >= policy.max_attempts {
+                    return Err(e);
+                }
+                thread::sleep(policy.backoff(attempt));
+                attempt += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Where outcomes come from. A source answers `None` while the outcome is
+//! not known yet; the resolver asks again on its next pass.
+
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::collections::HashMap;
+
+use eventum_client::names::parse_side;
+use eventum_client::{RpcClient, Side};
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::error::{ResolverError, Result};
+
+pub trait OutcomeSource {
+    /// The outcome of `market`, if it is known.
+    fn outcome(&self, market: &Pubkey) -> Result<Option<Side>>;
+
+    /// A short name for the audit log.
+    fn describe(&self) -> String;
+}
+
+/// A JSON object of market address to `"yes"`/`"no"`, re-read on every
+/// call so an operator can fill it in while the resolver runs.
+pub struct FileSource {
+    pub path: PathBuf,
+}
+
+impl OutcomeSource for FileSource {
+    fn outcome(&self, market: &Pubkey) -> Result<Option<Side>> {
+        let text = fs::read_to_string(&self.path)?;
+        let outcomes: HashMap<String, String> = serde_json::from_str(&text)
+            .map_err(|e| ResolverError::Source(format!("{}: {e}", self.path.display())))?;
+        outcomes
+            .get(&market.to_string())
+            .map(|outcome| parse_side(outcome))
+            .transpose()
+            .map_err(|e| ResolverError::Source(format!("{}: {e}", self.path.display())))
+    }
+
+    fn describe(&self) -> String {
+        format!("file:{}", self.path.display())
+    }
+}
+
+/// `GET <url>?market=<address>` answering `{"outcome": "yes" | "no" | null}`.
+pub struct WebhookSource {
+    pub url: String,
+    agent: ureq::Agent,
+}
+
+#[derive(Deserialize)]
+struct WebhookResponse {
+    outcome: Option<String>,
+}
+
+impl WebhookSource {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into(), agent: ureq::Agent::new_with_defaults() }
+    }
+}
+
+impl OutcomeSource for WebhookSource {
+    fn outcome(&self, market: &Pubkey) -> Result<Option<Side>> {
+        let response: WebhookResponse = self
+            .agent
+            .get(&self.url)
+            .query("market", market.to_string())
+            .call()?
+            .into_body()
+            .read_json()?;
+        response
+            .outcome
+            .map(|outcome| parse_side(&outcome))
+            .transpose()
+            .map_err(|e| ResolverError::Source(format!("{}: {e}", self.url)))
+    }
+
+    fn describe(&self) -> String {
+        format!("webhook:{}", self.url)
+    }
+}
+
+/// A little-endian `i64` at `offset` in a price feed account: YES if it is
+/// at least `threshold`, in the feed's own units. The feed is read once the
+/// market has expired, so it should hold the value at expiry.
+pub struct PriceFeedSource {
+    pub rpc: RpcClient,
+    pub account: Pubkey,
+    pub offset: usize,
+    pub threshold: i64,
+}
+
+/// The outcome a price feed's `data` gives.
+pub fn price_feed_outcome(data: &[u8], offset: usize, threshold: i64) -> Result<Side> {
+    let bytes: [u8; 8] = data
+        .get(offset..offset + 8)
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| ResolverError::Source(format!("no i64 at offset {offset} of {} bytes", data.len())))?;
+    Ok(if i64::from_le_bytes(bytes) >= threshold { Side::Yes } else { Side::No })
+}
+
+impl OutcomeSource for PriceFeedSource {
+    fn outcome(&self, _market: &Pubkey) -> Result<Option<Side>> {
+        let data = self
+            .rpc
+            .get_account_data(&self.account)?
+            .ok_or_else(|| ResolverError::Source(format!("price feed {} not found", self.account)))?;
+        price_feed_outcome(&data, self.offset, self.threshold).map(Some)
+    }
+
+    fn describe(&self) -> String {
+        format!("price-feed:{}", self.account)
+    }
+}
+
+/// Parses an address from a config or source file.
+pub(crate) fn parse_pubkey(s: &str) -> Result<Pubkey> {
+    Pubkey::from_str(s).map_err(|_| ResolverError::Config(format!("invalid address {s}")))
+}
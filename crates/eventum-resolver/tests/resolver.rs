@@ -0,0 +1,214 @@
+//! Source, config, retry and audit tests, offline; the webhook source runs
+//! against a local HTTP server.
+
+use std::cell::Cell;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+
+use eventum_client::{pda, FeeModel, Market, PricingMode, Side, TradeLimits};
+use eventum_resolver::config::SourceConfig;
+use eventum_resolver::source::price_feed_outcome;
+use eventum_resolver::{
+    is_due, retry, AuditEvent, AuditLog, Config, FileSource, OutcomeSource, RetryPolicy, WebhookSource,
+};
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("eventum-resolver-{name}-{}", Pubkey::new_unique()))
+}
+
+fn market(end_time: i64, resolved: bool) -> Market {
+    let creator = Pubkey::new_unique();
+    let address = pda::market_address(&creator, 0).0;
+    Market {
+        creator,
+        oracle_authority: creator,
+        unique_market_id: 0,
+        start_time: 0,
+        end_time,
+        resolved,
+        winning_outcome: false,
+        question: "Will it rain?".into(),
+        vault_bump: pda::vault_address(&address).1,
+        fee: 100,
+        total_liquidity: 0,
+        yes_mint: pda::yes_mint_address(&address).0,
+        no_mint: pda::no_mint_address(&address).0,
+        yes_tokens: 0,
+        no_tokens: 0,
+        bump: pda::market_address(&creator, 0).1,
+        approved_creator: false,
+        fee_model: FeeModel::Flat,
+        referral_share_bps: 0,
+        pricing_mode: PricingMode::Lmsr { b: 1000 },
+        yes_reserve: 0,
+        no_reserve: 0,
+        limits: TradeLimits::default(),
+        price_oracle: Pubkey::default(),
+        fees_collected: 0,
+    }
+}
+
+#[test]
+fn markets_are_due_once_expired_and_unresolved() {
+    assert!(!is_due(&market(100, false), 99));
+    assert!(is_due(&market(100, false), 100));
+    assert!(!is_due(&market(100, true), 200));
+}
+
+#[test]
+fn config_maps_markets_to_sources() {
+    let a = Pubkey::new_unique();
+    let b = Pubkey::new_unique();
+    let feed = Pubkey::new_unique();
+    let dir = temp_path("config");
+    fs::create_dir(&dir).unwrap();
+    let path = dir.join("resolver.json");
+    fs::write(
+        &path,
+        format!(
+            r#"{{
+                "default": {{ "type": "file", "path": "outcomes.json" }},
+                "markets": {{
+                    "{a}": {{ "type": "webhook", "url": "http://127.0.0.1:1/outcome" }},
+                    "{b}": {{ "type": "price_feed", "account": "{feed}", "offset": 8, "threshold": 100 }}
+                }},
+                "retry": {{ "max_attempts": 2 }}
+            }}"#
+        ),
+    )
+    .unwrap();
+
+    let config = Config::load(&path).unwrap();
+    let Some(SourceConfig::File { path: outcomes }) = &config.default else { panic!("{config:?}") };
+    assert_eq!(outcomes, &dir.join("outcomes.json"));
+    assert_eq!((config.retry.max_attempts, config.retry.backoff_ms), (2, 1000));
+
+    let sources = config.sources("http://127.0.0.1:8899").unwrap();
+    assert_eq!(sources.get(&a).unwrap().describe(), "webhook:http://127.0.0.1:1/outcome");
+    assert_eq!(sources.get(&b).unwrap().describe(), format!("price-feed:{feed}"));
+    let other = sources.get(&Pubkey::new_unique()).unwrap().describe();
+    assert_eq!(other, format!("file:{}", dir.join("outcomes.json").display()));
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(Config::parse(r#"{"markets": {"nope": {"type": "file", "path": "x"}}}"#)
+        .unwrap()
+        .sources("http://127.0.0.1:8899")
+        .is_err());
+    assert!(Config::parse(r#"{"default": {"type": "carrier_pigeon"}}"#).is_err());
+}
+
+#[test]
+fn file_source_reads_outcomes_as_they_are_written() {
+    let a = Pubkey::new_unique();
+    let b = Pubkey::new_unique();
+    let path = temp_path("outcomes");
+    let source = FileSource { path: path.clone() };
+
+    fs::write(&path, format!(r#"{{"{a}": "yes"}}"#)).unwrap();
+    assert_eq!(source.outcome(&a).unwrap(), Some(Side::Yes));
+    assert_eq!(source.outcome(&b).unwrap(), None);
+    fs::write(&path, format!(r#"{{"{a}": "yes", "{b}": "no"}}"#)).unwrap();
+    assert_eq!(source.outcome(&b).unwrap(), Some(Side::No));
+    fs::write(&path, format!(r#"{{"{a}": "maybe"}}"#)).unwrap();
+    assert!(source.outcome(&a).is_err());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn webhook_source_asks_per_market() {
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/outcome", server.server_addr().to_ip().unwrap());
+    let known = Pubkey::new_unique();
+    let handle = thread::spawn(move || {
+        for _ in 0..2 {
+            let request = server.recv().unwrap();
+            let body = if request.url().ends_with(&format!("market={known}")) {
+                r#"{"outcome": "no"}"#
+            } else {
+                r#"{"outcome": null}"#
+            };
+            request.respond(tiny_http::Response::from_string(body)).unwrap();
+        }
+    });
+
+    let source = WebhookSource::new(url);
+    assert_eq!(source.outcome(&known).unwrap(), Some(Side::No));
+    assert_eq!(source.outcome(&Pubkey::new_unique()).unwrap(), None);
+    handle.join().unwrap();
+}
+
+#[test]
+fn price_feeds_compare_against_the_threshold() {
+    let mut data = vec![0u8; 24];
+    data[8..16].copy_from_slice(&150i64.to_le_bytes());
+    assert_eq!(price_feed_outcome(&data, 8, 150).unwrap(), Side::Yes);
+    assert_eq!(price_feed_outcome(&data, 8, 151).unwrap(), Side::No);
+    assert!(price_feed_outcome(&data, 20, 0).is_err());
+}
+
+#[test]
+fn retry_backs_off_until_success_or_the_limit() {
+    let policy = RetryPolicy { max_attempts: 3, backoff_ms: 0 };
+    let failures = Cell::new(0);
+    let result = retry(
+        &policy,
+        |attempt| if attempt < 3 { Err(attempt) } else { Ok("sent") },
+        |_, _| {
+            failures.set(failures.get() + 1);
+            true
+        },
+    );
+    assert_eq!((result, failures.get()), (Ok("sent"), 2));
+
+    let calls = Cell::new(0);
+    let result: Result<(), u32> = retry(
+        &policy,
+        |attempt| {
+            calls.set(calls.get() + 1);
+            Err(attempt)
+        },
+        |_, _| true,
+    );
+    assert_eq!((result, calls.get()), (Err(3), 3));
+
+    // a failure the caller knows retrying cannot fix stops at once
+    let calls = Cell::new(0);
+    let result: Result<(), u32> = retry(
+        &policy,
+        |attempt| {
+            calls.set(calls.get() + 1);
+            Err(attempt)
+        },
+        |_, _| false,
+    );
+    assert_eq!((result, calls.get()), (Err(1), 1));
+}
+
+#[test]
+fn audit_log_appends_json_lines() {
+    let path = temp_path("audit");
+    let log = AuditLog { path: path.clone() };
+    let market = Pubkey::new_unique();
+    log.record(&market, &AuditEvent::Pending { source: "file:x".into() }).unwrap();
+    log.record(
+        &market,
+        &AuditEvent::Resolved { outcome: "yes".into(), signature: "sig".into(), attempts: 2 },
+    )
+    .unwrap();
+
+    let lines: Vec<Value> = fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["event"], "pending");
+    assert_eq!(lines[0]["market"], market.to_string());
+    assert_eq!(lines[1]["event"], "resolved");
+    assert_eq!(lines[1]["attempts"], 2);
+    assert!(lines[1]["time"].as_u64().unwrap() > 0);
+}
@@ -0,0 +1,95 @@
+//! Minimal integrating program: buys outcome tokens and claims winnings on
+//! Eventum through CPI, using the typed arguments and PDA helpers the
+//! `eventum` crate exports. Exercised by `tests/cpi.rs` and
+//! `tests/eventum-cpi.ts`.
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::Token;
+use eventum::cpi::accounts::{BuyOutcomes, ClaimWinnings};
+use eventum::program::Eventum;
+use eventum::states::Market;
+use eventum::BuyOutcomesArgs;
+
+declare_id!("6HijTAKrPr4S5d1VgP4Cb4f1Ww8zbQPQQpy3DKdXnVFb");
+
+#[program]
+pub mod eventum_cpi_example {
+    use super::*;
+
+    pub fn proxy_buy(ctx: Context<ProxyTrade>, args: BuyOutcomesArgs) -> Result<()> {
+        let accounts = BuyOutcomes {
+            user: ctx.accounts.user.to_account_info(),
+            market: ctx.accounts.market.to_account_info(),
+            yes_mint: ctx.accounts.yes_mint.to_account_info(),
+            no_mint: ctx.accounts.no_mint.to_account_info(),
+            vault: ctx.accounts.vault.to_account_info(),
+            user_yes_ata: ctx.accounts.user_yes_ata.to_account_info(),
+            user_no_ata: ctx.accounts.user_no_ata.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+            position: None,
+            referrer: None,
+            price_oracle: ctx.accounts.price_oracle.as_ref().map(|o| o.to_account_info()),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.eventum_program.to_account_info(), accounts);
+        eventum::cpi::buy_outcomes(cpi_ctx, args)
+    }
+
+    pub fn proxy_claim(ctx: Context<ProxyTrade>) -> Result<()> {
+        let accounts = ClaimWinnings {
+            market: ctx.accounts.market.to_account_info(),
+            user: ctx.accounts.user.to_account_info(),
+            user_yes_ata: ctx.accounts.user_yes_ata.to_account_info(),
+            user_no_ata: ctx.accounts.user_no_ata.to_account_info(),
+            vault: ctx.accounts.vault.to_account_info(),
+            yes_mint: ctx.accounts.yes_mint.to_account_info(),
+            no_mint: ctx.accounts.no_mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+            position: None,
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.eventum_program.to_account_info(), accounts);
+        eventum::cpi::claim_winnings(cpi_ctx)
+    }
+}
+
+#[derive(Accounts)]
+pub struct ProxyTrade<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Checked against the market by Eventum.
+    #[account(mut, address = eventum::pda::yes_mint_address(&market.key()).0)]
+    pub yes_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the market by Eventum.
+    #[account(mut, address = eventum::pda::no_mint_address(&market.key()).0)]
+    pub no_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the market by Eventum.
+    #[account(mut, address = eventum::pda::vault_address(&market.key()).0)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: The user's YES ATA; Eventum creates it if needed.
+    #[account(mut)]
+    pub user_yes_ata: UncheckedAccount<'info>,
+
+    /// CHECK: The user's NO ATA; Eventum creates it if needed.
+    #[account(mut)]
+    pub user_no_ata: UncheckedAccount<'info>,
+
+    /// CHECK: Required by Eventum only when the market records prices.
+    #[account(mut)]
+    pub price_oracle: Option<UncheckedAccount<'info>>,
+
+    pub eventum_program: Program<'info, Eventum>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
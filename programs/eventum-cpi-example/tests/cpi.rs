@@ -0,0 +1,88 @@
+//! Drives the example program on the in-process runtime, with Eventum
+//! deployed next to it, so its CPIs run Eventum's real handlers.
+
+#[path = "../../eventum/tests/common/mod.rs"]
+mod common;
+
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::token;
+use common::*;
+use eventum_client::{pda, BuyOutcomesArgs, Side};
+use eventum_cpi_example::{accounts, instruction};
+
+fn proxy_accounts(user: &Pubkey, market: &Pubkey) -> accounts::ProxyTrade {
+    let yes_mint = pda::yes_mint_address(market).0;
+    let no_mint = pda::no_mint_address(market).0;
+    accounts::ProxyTrade {
+        user: *user,
+        market: *market,
+        yes_mint,
+        no_mint,
+        vault: pda::vault_address(market).0,
+        user_yes_ata: get_associated_token_address(user, &yes_mint),
+        user_no_ata: get_associated_token_address(user, &no_mint),
+        price_oracle: None,
+        eventum_program: eventum::ID,
+        system_program: system_program::ID,
+        token_program: token::ID,
+        associated_token_program: associated_token::ID,
+    }
+}
+
+fn proxy_buy(user: &Pubkey, market: &Pubkey, args: BuyOutcomesArgs) -> Instruction {
+    Instruction {
+        program_id: eventum_cpi_example::ID,
+        accounts: proxy_accounts(user, market).to_account_metas(None),
+        data: instruction::ProxyBuy { args }.data(),
+    }
+}
+
+fn proxy_claim(user: &Pubkey, market: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: eventum_cpi_example::ID,
+        accounts: proxy_accounts(user, market).to_account_metas(None),
+        data: instruction::ProxyClaim {}.data(),
+    }
+}
+
+#[test]
+fn buys_and_claims_through_cpi() {
+    let mut env = Env::new();
+    env.svm.add_program(eventum_cpi_example::ID, eventum_cpi_example::entry);
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let user = env.wallet(10);
+
+    let quote = env.quote(&market, Side::Yes, 3);
+    let args = BuyOutcomesArgs { number_of_tokens: 3, side: Side::Yes, max_cost: quote.total };
+    let before = env.svm.lamports(&user);
+    env.send(proxy_buy(&user, &market, args), &[&user]).unwrap();
+    let m = env.market(&market);
+    assert_eq!(env.token_balance(&user, &m.yes_mint), 3 * ONE_TOKEN);
+    let ata_rent = env.svm.lamports(&get_associated_token_address(&user, &m.yes_mint));
+    assert_eq!(env.svm.lamports(&user), before - quote.total - 2 * ata_rent);
+
+    env.resolve(&market, Side::Yes);
+    let before = env.svm.lamports(&user);
+    env.send(proxy_claim(&user, &market), &[&user]).unwrap();
+    assert_eq!(env.token_balance(&user, &m.yes_mint), 0);
+    assert_eq!(env.svm.lamports(&user), before + 3 * ONE_TOKEN);
+}
+
+#[test]
+fn eventum_errors_surface_through_cpi() {
+    let mut env = Env::new();
+    env.svm.add_program(eventum_cpi_example::ID, eventum_cpi_example::entry);
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let user = env.wallet(10);
+
+    let quote = env.quote(&market, Side::No, 2);
+    let args = BuyOutcomesArgs { number_of_tokens: 2, side: Side::No, max_cost: quote.total - 1 };
+    let result = env.send(proxy_buy(&user, &market, args), &[&user]);
+    assert_error(result, eventum::error::ErrorCode::SlippageExceeded);
+}
@@ -4,7 +4,7 @@ use anchor_lang::prelude::*;
 #[error_code]
 pub enum ErrorCode {
   
-    #[msg("Market ID must be unique")]
+    #[msg("Market ID must be the creator's next sequential id")]
     DuplicateMarketId,
 
     #[msg("insufficient balance")]
@@ -129,4 +129,70 @@ pub enum ErrorCode {
 
     #[msg("check tokens supply")]
     InvalidSupply,
+
+    #[msg("Winning tokens are still outstanding. Cannot close market yet.")]
+    WinningsOutstanding,
+
+    #[msg("Metadata field exceeds its maximum length")]
+    MetadataTooLong,
+
+    #[msg("Creator is not approved to create markets")]
+    CreatorNotApproved,
+
+    #[msg("Remaining accounts do not match the trade legs")]
+    InvalidLegAccounts,
+
+    #[msg("Token account does not belong to the user or mint")]
+    InvalidTokenAccount,
+
+    #[msg("Cranker tip exceeds the allowed maximum")]
+    TipTooLarge,
+
+    #[msg("Market is not an approved delegate of this token account")]
+    DelegateNotApproved,
+
+    #[msg("Fee share must be at most 10000 bps")]
+    InvalidFeeShare,
+
+    #[msg("Traders cannot refer themselves")]
+    SelfReferral,
+
+    #[msg("Invalid pricing parameters")]
+    InvalidPricingParams,
+
+    #[msg("This market records prices; pass its price oracle account")]
+    PriceOracleRequired,
+
+    #[msg("Requested TWAP window is older than the oldest observation")]
+    TwapWindowUnavailable,
+
+    #[msg("Resolvers must be distinct, at most 10, with a threshold between 1 and their count")]
+    InvalidResolverSet,
+
+    #[msg("Bond, liveness and arbitration period must be positive and the reward at most 10000 bps")]
+    InvalidResolutionParams,
+
+    #[msg("An outcome has already been proposed")]
+    ProposalExists,
+
+    #[msg("No outcome has been proposed")]
+    NoProposal,
+
+    #[msg("The proposal has been challenged")]
+    ProposalDisputed,
+
+    #[msg("The proposal has not been challenged")]
+    ProposalNotDisputed,
+
+    #[msg("The proposal's liveness period has ended")]
+    LivenessElapsed,
+
+    #[msg("The proposal's liveness period has not ended")]
+    LivenessActive,
+
+    #[msg("The arbitration deadline has passed")]
+    ArbitrationExpired,
+
+    #[msg("The arbiter cannot propose or challenge an outcome")]
+    ArbiterIsParty,
 }
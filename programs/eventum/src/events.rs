@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::states::Side;
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct ReferralFeeAccrued {
+    pub referrer: Pubkey,
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct ReferralFeesClaimed {
+    pub referrer: Pubkey,
+    pub amount: u64,
+}
+
+/// A filled buy, from `buy_outcomes` or one leg of `batch_trade`. `tokens`
+/// is in raw units; `cost` excludes the fee and `fee` includes any referral
+/// share. Supplies and `price_yes` (scaled by `PRICE_SCALE`) are after the fill.
+#[event]
+#[derive(Clone, Debug)]
+pub struct OutcomesBought {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub side: Side,
+    pub tokens: u64,
+    pub cost: u64,
+    pub fee: u64,
+    pub yes_supply: u64,
+    pub no_supply: u64,
+    pub price_yes: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct MarketResolved {
+    pub market: Pubkey,
+    pub outcome: Side,
+}
+
+/// Winning tokens burned for lamports. `payout` is everything the vault
+/// paid, including a cranker's tip on `claim_for`.
+#[event]
+#[derive(Clone, Debug)]
+pub struct WinningsClaimed {
+    pub market: Pubkey,
+    pub holder: Pubkey,
+    pub payout: u64,
+}
+
+/// A committee member's vote; `votes` is how many now back `outcome`.
+#[event]
+#[derive(Clone, Debug)]
+pub struct ResolutionVoted {
+    pub market: Pubkey,
+    pub resolver: Pubkey,
+    pub outcome: Side,
+    pub votes: u8,
+    pub threshold: u8,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct OutcomeProposed {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    pub outcome: Side,
+    pub bond: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+pub struct ProposalChallenged {
+    pub market: Pubkey,
+    pub challenger: Pubkey,
+}
+
+/// An optimistic proposal settled, unchallenged or by the arbiter.
+/// `payout` is what `recipient` received: both bonds after a dispute, the
+/// bond plus the fee reward otherwise.
+#[event]
+#[derive(Clone, Debug)]
+pub struct ProposalSettled {
+    pub market: Pubkey,
+    pub outcome: Side,
+    pub disputed: bool,
+    pub recipient: Pubkey,
+    pub payout: u64,
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Config, CreatorApproval};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ApproveCreator<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ ErrorCode::Unauthorized,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Any wallet can be approved; only its key is recorded.
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + CreatorApproval::INIT_SPACE,
+        seeds = [b"approval", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_approval: Account<'info, CreatorApproval>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ApproveCreator>) -> Result<()> {
+    let approval = &mut ctx.accounts.creator_approval;
+    approval.creator = ctx.accounts.creator.key();
+    approval.approved_by = ctx.accounts.admin.key();
+    approval.approved_at = Clock::get()?.unix_timestamp;
+    approval.bump = ctx.bumps.creator_approval;
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{MarketResolved, ProposalSettled};
+use crate::states::{Market, OptimisticResolution, Side};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct Arbitrate<'info> {
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"Market", market.creator.as_ref(), &market.unique_market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        has_one = market @ ErrorCode::InvalidMarket,
+        has_one = arbiter @ ErrorCode::UnauthorizedResolver,
+        has_one = proposer @ ErrorCode::Unauthorized,
+        seeds = [b"optimistic-resolution", market.key().as_ref()],
+        bump = optimistic_resolution.bump,
+    )]
+    pub optimistic_resolution: Box<Account<'info, OptimisticResolution>>,
+
+    #[account(mut)]
+    pub proposer: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub challenger: SystemAccount<'info>,
+}
+
+/// Decides a challenged proposal before the arbitration deadline. The
+/// market resolves to `outcome`, and both bonds go to the proposer if it
+/// was right, to the challenger if not.
+pub fn handler(ctx: Context<Arbitrate>, outcome: Side) -> Result<()> {
+    let optimistic = &ctx.accounts.optimistic_resolution;
+    let now = Clock::get()?.unix_timestamp;
+    require!(!ctx.accounts.market.resolved, ErrorCode::MarketResolved);
+    let proposal = optimistic.proposal.ok_or(ErrorCode::NoProposal)?;
+    let challenger = optimistic.challenger.ok_or(ErrorCode::ProposalNotDisputed)?;
+    require!(now < optimistic.arbitration_deadline, ErrorCode::ArbitrationExpired);
+    require_keys_eq!(ctx.accounts.challenger.key(), challenger, ErrorCode::Unauthorized);
+
+    let payout = optimistic.bond.checked_mul(2).ok_or(ErrorCode::MathOverflow)?;
+    let recipient = if outcome == proposal {
+        ctx.accounts.proposer.to_account_info()
+    } else {
+        ctx.accounts.challenger.to_account_info()
+    };
+    ctx.accounts.optimistic_resolution.sub_lamports(payout)?;
+    recipient.add_lamports(payout)?;
+
+    let market = &mut ctx.accounts.market;
+    market.resolved = true;
+    market.winning_outcome = outcome.is_yes();
+    emit!(MarketResolved { market: market.key(), outcome });
+    emit!(ProposalSettled {
+        market: market.key(),
+        outcome,
+        disputed: true,
+        recipient: recipient.key(),
+        payout,
+    });
+    Ok(())
+}
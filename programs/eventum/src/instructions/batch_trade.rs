@@ -0,0 +1,219 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer as SystemTransfer};
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+
+use crate::events::{OutcomesBought, ReferralFeeAccrued};
+use crate::instructions::buy_outcomes::{quote_buy, referral_cut};
+use crate::pricing::{after_buy, engine_for, scaled_price};
+use crate::states::{Market, Position, PriceOracle, Referrer, Side};
+use crate::error::ErrorCode;
+
+/// Accounts each leg takes from `remaining_accounts`, in this order:
+/// market, yes_mint, no_mint, vault, the user's ATA for the side being
+/// bought (which must already exist), the market's price oracle (or this
+/// program's id when it has none), and the user's `Position` address for
+/// the market, updated whenever the position has been opened. All must be
+/// writable.
+pub const ACCOUNTS_PER_LEG: usize = 7;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TradeLeg {
+    pub side: Side,
+    pub number_of_tokens: u64,   // whole tokens, like `buy_outcomes`
+    pub max_cost: u64,           // lamports, fee included
+}
+
+#[derive(Accounts)]
+pub struct BatchTrade<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer.authority.as_ref()],
+        bump = referrer.bump,
+        constraint = referrer.authority != user.key() @ ErrorCode::SelfReferral,
+    )]
+    pub referrer: Option<Account<'info, Referrer>>,
+}
+
+/// Buys every leg or none. Legs are priced in order, so two legs on the
+/// same market see each other's supply changes. Payments are summed per
+/// vault and sent once the whole batch has been priced and minted; a
+/// referrer's share of every leg's fee is likewise paid in one transfer.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchTrade<'info>>,
+    legs: Vec<TradeLeg>,
+) -> Result<()> {
+    require!(!legs.is_empty(), ErrorCode::InvalidAmount);
+    require!(
+        ctx.remaining_accounts.len() == legs.len() * ACCOUNTS_PER_LEG,
+        ErrorCode::InvalidLegAccounts
+    );
+
+    let user = ctx.accounts.user.key();
+    let now = Clock::get()?.unix_timestamp;
+    let mut owed: Vec<(&AccountInfo<'info>, u64)> = Vec::new();
+    let mut referral_total = 0_u64;
+
+    for (leg, accounts) in legs.iter().zip(ctx.remaining_accounts.chunks(ACCOUNTS_PER_LEG)) {
+        let [market_info, yes_mint_info, no_mint_info, vault_info, ata_info, oracle_info, position_info] = accounts else {
+            return err!(ErrorCode::InvalidLegAccounts);
+        };
+        require!(leg.number_of_tokens > 0, ErrorCode::InvalidAmount);
+        let yes = leg.side.is_yes();
+
+        let mut market: Account<Market> = Account::try_from(market_info)?;
+        require_keys_eq!(yes_mint_info.key(), market.yes_mint, ErrorCode::InvalidMarket);
+        require_keys_eq!(no_mint_info.key(), market.no_mint, ErrorCode::InvalidMarket);
+
+        let vault = Pubkey::create_program_address(
+            &[b"market-vault", market_info.key.as_ref(), &[market.vault_bump]],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::InvalidVault)?;
+        require_keys_eq!(vault_info.key(), vault, ErrorCode::InvalidVault);
+
+        // the address is always checked so an opened position cannot be skipped
+        let position_address = Pubkey::find_program_address(
+            &[b"position", market_info.key.as_ref(), user.as_ref()],
+            &crate::ID,
+        )
+        .0;
+        require_keys_eq!(position_info.key(), position_address, ErrorCode::InvalidLegAccounts);
+
+        // re-read on every leg so earlier mints in this batch are priced in
+        let yes_mint: Account<Mint> = Account::try_from(yes_mint_info)?;
+        let no_mint: Account<Mint> = Account::try_from(no_mint_info)?;
+        let ata: Account<TokenAccount> = Account::try_from(ata_info)?;
+        let mint_info = if yes { yes_mint_info } else { no_mint_info };
+        require_keys_eq!(ata.owner, user, ErrorCode::InvalidTokenAccount);
+        require_keys_eq!(ata.mint, mint_info.key(), ErrorCode::InvalidTokenAccount);
+
+        let decimals = yes_mint.decimals;
+        let tokens_with_decimals = leg
+            .number_of_tokens
+            .checked_mul(10_u64.pow(decimals as u32))
+            .ok_or(ErrorCode::MathOverflow)?;
+        let (to_pay, market_cut) = quote_buy(
+            &market,
+            yes_mint.supply,
+            no_mint.supply,
+            decimals,
+            yes,
+            tokens_with_decimals,
+            now,
+        )?;
+        require!(to_pay <= leg.max_cost, ErrorCode::SlippageExceeded);
+        let referral = match ctx.accounts.referrer {
+            Some(_) => referral_cut(&market, market_cut)?,
+            None => 0,
+        };
+        if referral > 0 {
+            referral_total = referral_total.checked_add(referral).ok_or(ErrorCode::MathOverflow)?;
+            emit!(ReferralFeeAccrued {
+                referrer: ctx.accounts.referrer.as_ref().map(|r| r.key()).unwrap_or_default(),
+                market: market.key(),
+                user,
+                amount: referral,
+            });
+        }
+        let to_vault = to_pay - referral;
+
+        // engines with pool state (CPMM) write it back before the next leg reads it
+        let engine = engine_for(&market, yes_mint.supply, no_mint.supply, decimals);
+        let new_price = scaled_price(engine.price_yes_after_buy(yes, tokens_with_decimals, to_pay - market_cut)?)?;
+        if market.price_oracle != Pubkey::default() {
+            require_keys_eq!(oracle_info.key(), market.price_oracle, ErrorCode::PriceOracleRequired);
+            let mut oracle: Box<Account<PriceOracle>> = Box::new(Account::try_from(oracle_info)?);
+            oracle.record(now, new_price)?;
+            oracle.exit(&crate::ID)?;
+        }
+        engine.apply_buy(&mut market, yes, tokens_with_decimals, to_pay - market_cut)?;
+        market.fees_collected = market.fees_collected.checked_add(market_cut - referral).ok_or(ErrorCode::MathOverflow)?;
+        market.exit(&crate::ID)?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"Market",
+            market.creator.as_ref(),
+            &market.unique_market_id.to_le_bytes(),
+            &[market.bump],
+        ]];
+        let accounts = MintTo {
+            mint: mint_info.clone(),
+            to: ata_info.clone(),
+            authority: market_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            accounts,
+            signer_seeds,
+        );
+        token::mint_to(cpi_ctx, tokens_with_decimals)?;
+        if position_info.owner == &crate::ID {
+            let mut position: Box<Account<Position>> = Box::new(Account::try_from(position_info)?);
+            position.record_buy(yes, tokens_with_decimals, to_pay, market_cut)?;
+            position.exit(&crate::ID)?;
+        }
+        let (yes_supply, no_supply) = after_buy(yes_mint.supply, no_mint.supply, yes, tokens_with_decimals)?;
+        emit!(OutcomesBought {
+            market: market.key(),
+            user,
+            side: leg.side,
+            tokens: tokens_with_decimals,
+            cost: to_pay - market_cut,
+            fee: market_cut,
+            yes_supply,
+            no_supply,
+            price_yes: new_price,
+        });
+
+        match owed.iter_mut().find(|(info, _)| info.key == vault_info.key) {
+            Some((_, amount)) => {
+                *amount = amount.checked_add(to_vault).ok_or(ErrorCode::MathOverflow)?;
+            }
+            None => owed.push((vault_info, to_vault)),
+        }
+    }
+
+    let total = owed
+        .iter()
+        .try_fold(referral_total, |acc, (_, amount)| acc.checked_add(*amount))
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        ctx.accounts.user.to_account_info().lamports() >= total,
+        ErrorCode::InsufficientBalance
+    );
+
+    for (vault_info, amount) in owed {
+        let transfer_accounts = SystemTransfer {
+            from: ctx.accounts.user.to_account_info(),
+            to: vault_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            transfer_accounts,
+        );
+        transfer(cpi_ctx, amount)?;
+    }
+
+    if let Some(referrer) = ctx.accounts.referrer.as_mut() {
+        if referral_total > 0 {
+            let transfer_accounts = SystemTransfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: referrer.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                transfer_accounts,
+            );
+            transfer(cpi_ctx, referral_total)?;
+            referrer.pending = referrer.pending.checked_add(referral_total).ok_or(ErrorCode::MathOverflow)?;
+            referrer.total_earned = referrer.total_earned.checked_add(referral_total).ok_or(ErrorCode::MathOverflow)?;
+        }
+    }
+    Ok(())
+}
@@ -1,48 +1,50 @@
-use core::num;
-use std::marker;
-
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer as SystemTransfer};
-use anchor_spl::associated_token::spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
 use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
 use anchor_spl::associated_token::AssociatedToken;
-use rust_decimal::{Decimal, MathematicalOps};
+use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
-use crate::states::Market;
+use crate::states::{FeeModel, Market, Position, PriceOracle, Referrer, Side};
+use crate::events::{OutcomesBought, ReferralFeeAccrued};
+use crate::pricing::{after_buy, engine_for, scaled_price, PricingEngine};
 use crate::error::ErrorCode;
 
+/// Arguments of `buy_outcomes`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BuyOutcomesArgs {
+    pub number_of_tokens: u64,   // whole tokens
+    pub side: Side,
+    pub max_cost: u64,           // lamports the buyer will pay at most, fee included
+}
+
 #[derive(Accounts)]
-#[instruction(unique_market_id: u64)]
 pub struct BuyOutcomes<'info> {
-    /// CHECK: Used only for PDA derivation
-    pub creator: UncheckedAccount<'info>,
-    
     #[account(mut)]
     pub user: Signer<'info>,
     
     #[account(
         mut,
-        seeds = [b"Market", creator.key().as_ref(), &unique_market_id.to_le_bytes()],
-        bump,
+        seeds = [b"Market", market.creator.as_ref(), &market.unique_market_id.to_le_bytes()],
+        bump = market.bump,
     )]
     pub market: Account<'info, Market>,
     
     #[account(
         mut,
-        mint::authority = market
+        address = market.yes_mint @ ErrorCode::InvalidMarket,
     )]
     pub yes_mint: Account<'info, Mint>,
     
     #[account(
         mut,
-        mint::authority = market
+        address = market.no_mint @ ErrorCode::InvalidMarket,
     )]
     pub no_mint: Account<'info, Mint>,
     
     #[account(
         mut,
         seeds = [b"market-vault", market.key().as_ref()],
-        bump
+        bump = market.vault_bump
     )]
     pub vault: SystemAccount<'info>,
     
@@ -65,14 +67,36 @@ pub struct BuyOutcomes<'info> {
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        bump = position.bump,
+    )]
+    pub position: Option<Account<'info, Position>>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer.authority.as_ref()],
+        bump = referrer.bump,
+        constraint = referrer.authority != user.key() @ ErrorCode::SelfReferral,
+    )]
+    pub referrer: Option<Account<'info, Referrer>>,
+
+    #[account(
+        mut,
+        seeds = [b"price-oracle", market.key().as_ref()],
+        bump = price_oracle.bump,
+    )]
+    pub price_oracle: Option<Box<Account<'info, PriceOracle>>>,
 }
 
 pub fn handler(
     ctx: Context<BuyOutcomes>,
-    unique_market_id: u64,
-    number_of_tokens: u64, 
-    yes: bool
+    args: BuyOutcomesArgs
 ) -> Result<()> {
+    let BuyOutcomesArgs { number_of_tokens, side, max_cost } = args;
+    let yes = side.is_yes();
     require!(number_of_tokens > 0, ErrorCode::InvalidAmount);
     let market = &ctx.accounts.market;
     
@@ -85,72 +109,119 @@ pub fn handler(
     let curr_yes = ctx.accounts.yes_mint.supply;
     let curr_no = ctx.accounts.no_mint.supply;
     
-    let b = 1000 as u64 ;
-    let before_lmsr = calculate_lmsr(b, curr_yes, curr_no, decimals)?;
-    
-    let (after_yes, after_no) = if yes {
-        (
-            curr_yes.checked_add(tokens_with_decimals)
-                .ok_or(ErrorCode::MathOverflow)?,
-            curr_no
-        )
-    } else {
-        (
-            curr_yes,
-            curr_no.checked_add(tokens_with_decimals)
-                .ok_or(ErrorCode::MathOverflow)?
-        )
+    let now = Clock::get()?.unix_timestamp;
+    let (to_pay, market_cut) = quote_buy(market, curr_yes, curr_no, decimals, yes, tokens_with_decimals, now)?;
+    let referral = match ctx.accounts.referrer {
+        Some(_) => referral_cut(market, market_cut)?,
+        None => 0,
     };
-    
-    let after_lmsr = calculate_lmsr(b, after_yes, after_no, decimals)?;
-    let cost_diff = after_lmsr.checked_sub(before_lmsr)
-        .ok_or(ErrorCode::MathOverflow)?;
-    let mut to_pay = cost_diff.round()
-        .to_u64()
-        .ok_or(ErrorCode::MathOverflow)?;
-    let fee = market.fee as u64 ;
-    let fee_num = to_pay.checked_mul(fee).ok_or(ErrorCode::MathOverflow)?;
-    let market_cut = fee_num.checked_div(10000).ok_or(ErrorCode::MathOverflow)?;
-    to_pay = to_pay.checked_add(market_cut).ok_or(ErrorCode::MathOverflow)?;
-    transfer_amount(&ctx, to_pay)?;
-    mint_tokens(&ctx, tokens_with_decimals, yes, unique_market_id)?; 
+    require!(to_pay <= max_cost, ErrorCode::SlippageExceeded);
+    require!(ctx.accounts.user.lamports() >= to_pay, ErrorCode::InsufficientBalance);
+    let engine = engine_for(market, curr_yes, curr_no, decimals);
+    let new_price = scaled_price(engine.price_yes_after_buy(yes, tokens_with_decimals, to_pay - market_cut)?)?;
+    if market.price_oracle != Pubkey::default() {
+        let oracle = ctx.accounts.price_oracle.as_mut().ok_or(ErrorCode::PriceOracleRequired)?;
+        oracle.record(now, new_price)?;
+    }
+    engine.apply_buy(&mut ctx.accounts.market, yes, tokens_with_decimals, to_pay - market_cut)?;
+    let market = &mut ctx.accounts.market;
+    market.fees_collected = market.fees_collected.checked_add(market_cut - referral).ok_or(ErrorCode::MathOverflow)?;
+    transfer_amount(&ctx, to_pay - referral)?;
+    mint_tokens(&ctx, tokens_with_decimals, yes)?; 
+    if referral > 0 {
+        pay_referrer(&ctx, referral)?;
+        let referrer = ctx.accounts.referrer.as_mut().ok_or(ErrorCode::InvalidCalculation)?;
+        referrer.pending = referrer.pending.checked_add(referral).ok_or(ErrorCode::MathOverflow)?;
+        referrer.total_earned = referrer.total_earned.checked_add(referral).ok_or(ErrorCode::MathOverflow)?;
+        emit!(ReferralFeeAccrued {
+            referrer: referrer.key(),
+            market: ctx.accounts.market.key(),
+            user: ctx.accounts.user.key(),
+            amount: referral,
+        });
+    }
+    if let Some(position) = ctx.accounts.position.as_mut() {
+        position.record_buy(yes, tokens_with_decimals, to_pay, market_cut)?;
+    }
+    let (yes_supply, no_supply) = after_buy(curr_yes, curr_no, yes, tokens_with_decimals)?;
+    emit!(OutcomesBought {
+        market: ctx.accounts.market.key(),
+        user: ctx.accounts.user.key(),
+        side,
+        tokens: tokens_with_decimals,
+        cost: to_pay - market_cut,
+        fee: market_cut,
+        yes_supply,
+        no_supply,
+        price_yes: new_price,
+    });
     Ok(())
 }
 
+/// Prices a buy of `tokens` raw units of one side against the current
+/// supplies. Returns the total lamports owed and the fee included in it.
+pub fn quote_buy(
+    market: &Market,
+    curr_yes: u64,
+    curr_no: u64,
+    decimals: u8,
+    yes: bool,
+    tokens: u64,
+    now: i64
+) -> Result<(u64, u64)> {
+    require!(!market.resolved, ErrorCode::MarketResolved);
+    // once trading closes the outcome may already be public, e.g. proposed
+    require!(now < market.end_time, ErrorCode::MarketExpired);
 
-pub fn calculate_lmsr(
-    b: u64,
-    yes: u64,
-    no: u64,
-    decimals: u8
-) -> Result<Decimal> {
-    let decimal_factor = 10u64.pow(decimals as u32);
-    
-    let yes_whole = Decimal::from(yes) / Decimal::from(decimal_factor);
-    let no_whole = Decimal::from(no) / Decimal::from(decimal_factor);
-    let b_dec = Decimal::from(b);
+    let limits = &market.limits;
+    require!(limits.min_tokens == 0 || tokens >= limits.min_tokens, ErrorCode::AmountTooSmall);
+    require!(limits.max_tokens == 0 || tokens <= limits.max_tokens, ErrorCode::AmountTooLarge);
 
-    let exp_yes = (yes_whole / b_dec)
-        .checked_exp()
-        .ok_or(ErrorCode::MathOverflow)?;
-    
-    let exp_no = (no_whole / b_dec)
-        .checked_exp()
-        .ok_or(ErrorCode::MathOverflow)?;
+    let engine = engine_for(market, curr_yes, curr_no, decimals);
+    let mut to_pay = engine.buy_cost(yes, tokens)?;
+    if limits.max_price_move_bps > 0 {
+        let before = engine.price_yes()?;
+        let after = engine.price_yes_after_buy(yes, tokens, to_pay)?;
+        let moved_bps = (after - before).abs() * Decimal::from(10000);
+        require!(moved_bps <= Decimal::from(limits.max_price_move_bps), ErrorCode::AmountTooLarge);
+    }
+    let fee = fee_bps(market, engine.as_ref(), now)? ;
+    let fee_num = to_pay.checked_mul(fee).ok_or(ErrorCode::MathOverflow)?;
+    let market_cut = fee_num.checked_div(10000).ok_or(ErrorCode::MathOverflow)?;
+    to_pay = to_pay.checked_add(market_cut).ok_or(ErrorCode::MathOverflow)?;
+    Ok((to_pay, market_cut))
+}
 
-    let sum = exp_yes.checked_add(exp_no)
+/// Part of `market_cut` owed to a referrer under the market's share.
+pub fn referral_cut(market: &Market, market_cut: u64) -> Result<u64> {
+    let cut = (market_cut as u128)
+        .checked_mul(market.referral_share_bps as u128)
+        .and_then(|n| n.checked_div(10000))
         .ok_or(ErrorCode::MathOverflow)?;
-    
-    let log = sum.checked_ln()
-        .ok_or(ErrorCode::MathOverflow)?;
-    
-    let cost_whole = b_dec.checked_mul(log)
-        .ok_or(ErrorCode::MathOverflow)?;    // here the cost is in sols , we have to cinvert it indo lamports 
-    
-    let cost_lamports = cost_whole.checked_mul(Decimal::from(LAMPORTS_PER_SOL))
-        .ok_or(ErrorCode::MathOverflow)?;
-    
-    Ok(cost_lamports)
+    Ok(cut as u64)
+}
+
+/// Fee in bps for a trade made at `now` at the engine's current price,
+/// according to the market's `FeeModel`.
+pub fn fee_bps(
+    market: &Market,
+    engine: &dyn PricingEngine,
+    now: i64
+) -> Result<u64> {
+    let fee = Decimal::from(market.fee);
+    let bps = match market.fee_model {
+        FeeModel::Flat => fee,
+        FeeModel::PriceDependent => {
+            let p = engine.price_yes()?;
+            fee * Decimal::from(4) * p * (Decimal::ONE - p)
+        }
+        FeeModel::TimeIncreasing => {
+            let duration = market.end_time.saturating_sub(market.start_time).max(1);
+            let elapsed = now.saturating_sub(market.start_time).clamp(0, duration);
+            fee + fee * Decimal::from(elapsed) / Decimal::from(duration)
+        }
+    };
+    bps.floor().to_u64().ok_or(ErrorCode::MathOverflow.into())
 }
 
 pub fn transfer_amount(
@@ -174,20 +245,37 @@ pub fn transfer_amount(
     Ok(())
 }
 
+pub fn pay_referrer(
+    ctx: &Context<BuyOutcomes>,
+    amount: u64
+) -> Result<()> {
+    let referrer = ctx.accounts.referrer.as_ref().ok_or(ErrorCode::InvalidCalculation)?;
+    let transfer_accounts = SystemTransfer {
+        from: ctx.accounts.user.to_account_info(),
+        to: referrer.to_account_info(),
+    };
+    
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        transfer_accounts
+    );
+    
+    transfer(cpi_ctx, amount)?;
+    Ok(())
+}
+
 pub fn mint_tokens(
     ctx: &Context<BuyOutcomes>,
     amount: u64,
-    yes: bool,
-    unique_market_id: u64
+    yes: bool
 ) -> Result<()> {
     let market = &ctx.accounts.market;
-    let creator = ctx.accounts.creator.key();
     let bump = market.bump;
     
     let signer_seeds: &[&[&[u8]]] = &[&[
         b"Market",
-        creator.as_ref(),
-        &unique_market_id.to_le_bytes(),
+        market.creator.as_ref(),
+        &market.unique_market_id.to_le_bytes(),
         &[bump]
     ]];
     
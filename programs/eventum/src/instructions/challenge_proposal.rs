@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::events::ProposalChallenged;
+use crate::states::{Market, OptimisticResolution};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ChallengeProposal<'info> {
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    #[account(
+        seeds = [b"Market", market.creator.as_ref(), &market.unique_market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        has_one = market @ ErrorCode::InvalidMarket,
+        seeds = [b"optimistic-resolution", market.key().as_ref()],
+        bump = optimistic_resolution.bump,
+    )]
+    pub optimistic_resolution: Box<Account<'info, OptimisticResolution>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Disputes the proposal within its liveness period by posting an equal
+/// bond. The arbiter then has `arbitration_period` seconds to decide, and
+/// whoever it sides with takes both.
+pub fn handler(ctx: Context<ChallengeProposal>) -> Result<()> {
+    let optimistic = &ctx.accounts.optimistic_resolution;
+    let now = Clock::get()?.unix_timestamp;
+    require!(!ctx.accounts.market.resolved, ErrorCode::MarketResolved);
+    require!(optimistic.proposal.is_some(), ErrorCode::NoProposal);
+    require!(optimistic.challenger.is_none(), ErrorCode::ProposalDisputed);
+    require!(optimistic.in_liveness(now), ErrorCode::LivenessElapsed);
+    require_keys_neq!(ctx.accounts.challenger.key(), optimistic.arbiter, ErrorCode::ArbiterIsParty);
+    let deadline = now.checked_add(optimistic.arbitration_period).ok_or(ErrorCode::MathOverflow)?;
+
+    let bond = optimistic.bond;
+    require!(ctx.accounts.challenger.lamports() >= bond, ErrorCode::InsufficientBalance);
+    let accounts = Transfer {
+        from: ctx.accounts.challenger.to_account_info(),
+        to: ctx.accounts.optimistic_resolution.to_account_info(),
+    };
+    transfer(CpiContext::new(ctx.accounts.system_program.to_account_info(), accounts), bond)?;
+
+    let challenger = ctx.accounts.challenger.key();
+    let optimistic = &mut ctx.accounts.optimistic_resolution;
+    optimistic.challenger = Some(challenger);
+    optimistic.arbitration_deadline = deadline;
+    emit!(ProposalChallenged { market: ctx.accounts.market.key(), challenger });
+    Ok(())
+}
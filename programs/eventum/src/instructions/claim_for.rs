@@ -0,0 +1,131 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+
+use crate::events::WinningsClaimed;
+use crate::states::{Market, Position};
+use crate::ErrorCode;
+
+/// Largest cut of a payout a cranker may keep, in bps (0.5%).
+pub const MAX_CLAIM_TIP_BPS: u16 = 50;
+
+#[derive(Accounts)]
+pub struct ClaimFor<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// Receives the payout; does not sign.
+    #[account(mut)]
+    pub holder: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"Market", market.creator.as_ref(), &market.unique_market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        constraint = winning_mint.key() == if market.winning_outcome { market.yes_mint } else { market.no_mint } @ ErrorCode::InvalidMarket,
+    )]
+    pub winning_mint: Account<'info, Mint>,
+
+    // the holder must have approved the market PDA as delegate beforehand
+    #[account(
+        mut,
+        associated_token::mint = winning_mint,
+        associated_token::authority = holder,
+    )]
+    pub holder_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"market-vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), holder.key().as_ref()],
+        bump = position.bump,
+    )]
+    pub position: Option<Account<'info, Position>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Redeems a holder's winning tokens on their behalf. The burn is signed
+/// by the market PDA acting as the ATA's delegate, so only the delegated
+/// amount can be claimed. `tip_bps` of the payout goes to the cranker.
+pub fn handler(ctx: Context<ClaimFor>, tip_bps: u16) -> Result<()> {
+    let market = &ctx.accounts.market;
+    require!(market.resolved, ErrorCode::MarketNotResolved);
+    require!(tip_bps <= MAX_CLAIM_TIP_BPS, ErrorCode::TipTooLarge);
+
+    let ata = &ctx.accounts.holder_ata;
+    require!(ata.delegate == Some(market.key()).into(), ErrorCode::DelegateNotApproved);
+    let payout = ata.amount.min(ata.delegated_amount);
+    require!(payout > 0, ErrorCode::NoWinnings);
+
+    let unique_market_id = market.unique_market_id.to_le_bytes();
+    let market_seeds: &[&[&[u8]]] = &[&[
+        b"Market",
+        market.creator.as_ref(),
+        &unique_market_id,
+        &[market.bump],
+    ]];
+    let burn_accounts = Burn {
+        mint: ctx.accounts.winning_mint.to_account_info(),
+        from: ata.to_account_info(),
+        authority: market.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        burn_accounts,
+        market_seeds,
+    );
+    token::burn(cpi_ctx, payout)?;
+
+    let tip = (payout as u128)
+        .checked_mul(tip_bps as u128)
+        .and_then(|n| n.checked_div(10000))
+        .ok_or(ErrorCode::MathOverflow)? as u64;
+    let to_holder = payout.checked_sub(tip).ok_or(ErrorCode::MathOverflow)?;
+
+    let market_key = market.key();
+    let vault_seeds: &[&[&[u8]]] = &[&[b"market-vault", market_key.as_ref(), &[market.vault_bump]]];
+    for (to, amount) in [
+        (ctx.accounts.holder.to_account_info(), to_holder),
+        (ctx.accounts.cranker.to_account_info(), tip),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        let transfer_accounts = system_program::Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to,
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            transfer_accounts,
+            vault_seeds,
+        );
+        system_program::transfer(cpi_ctx, amount)?;
+    }
+
+    // a partial delegation leaves the rest of the position open
+    if let Some(position) = ctx.accounts.position.as_mut() {
+        if payout == ata.amount {
+            position.record_claim(to_holder)?;
+        } else {
+            position.record_partial_claim(market.winning_outcome, payout, to_holder)?;
+        }
+    }
+    emit!(WinningsClaimed {
+        market: market_key,
+        holder: ctx.accounts.holder.key(),
+        payout,
+    });
+    Ok(())
+}
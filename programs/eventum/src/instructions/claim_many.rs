@@ -0,0 +1,108 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token::{self, Burn, Token, TokenAccount};
+
+use crate::events::WinningsClaimed;
+use crate::states::{Market, Position};
+use crate::ErrorCode;
+
+/// Accounts each market takes from `remaining_accounts`, in this order:
+/// market, winning mint, the user's ATA for that mint, vault, and the
+/// user's `Position` address for the market, updated whenever the position
+/// has been opened.
+pub const ACCOUNTS_PER_CLAIM: usize = 5;
+
+#[derive(Accounts)]
+pub struct ClaimMany<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// `claim_winnings` for many resolved markets at once. Markets where the
+/// user holds no winning tokens are skipped rather than failing the batch,
+/// though an opened position is still settled against a zero payout.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimMany<'info>>) -> Result<()> {
+    let groups = ctx.remaining_accounts;
+    require!(
+        !groups.is_empty() && groups.chunks_exact(ACCOUNTS_PER_CLAIM).remainder().is_empty(),
+        ErrorCode::InvalidLegAccounts
+    );
+    let user = ctx.accounts.user.key();
+
+    for accounts in groups.chunks(ACCOUNTS_PER_CLAIM) {
+        let [market_info, mint_info, ata_info, vault_info, position_info] = accounts else {
+            return err!(ErrorCode::InvalidLegAccounts);
+        };
+
+        let market: Account<Market> = Account::try_from(market_info)?;
+        let market_key = Pubkey::create_program_address(
+            &[
+                b"Market",
+                market.creator.as_ref(),
+                &market.unique_market_id.to_le_bytes(),
+                &[market.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::InvalidMarket)?;
+        require_keys_eq!(market_info.key(), market_key, ErrorCode::InvalidMarket);
+        require!(market.resolved, ErrorCode::MarketNotResolved);
+
+        let winning_mint = if market.winning_outcome { market.yes_mint } else { market.no_mint };
+        require_keys_eq!(mint_info.key(), winning_mint, ErrorCode::InvalidMarket);
+
+        let vault_bump = market.vault_bump;
+        let vault_key = Pubkey::create_program_address(
+            &[b"market-vault", market_key.as_ref(), &[vault_bump]],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::InvalidVault)?;
+        require_keys_eq!(vault_info.key(), vault_key, ErrorCode::InvalidVault);
+
+        // the address is always checked so an opened position cannot be skipped
+        let position_address = Pubkey::find_program_address(
+            &[b"position", market_key.as_ref(), user.as_ref()],
+            &crate::ID,
+        )
+        .0;
+        require_keys_eq!(position_info.key(), position_address, ErrorCode::InvalidLegAccounts);
+
+        let ata: Account<TokenAccount> = Account::try_from(ata_info)?;
+        require_keys_eq!(ata.owner, user, ErrorCode::InvalidTokenAccount);
+        require_keys_eq!(ata.mint, winning_mint, ErrorCode::InvalidTokenAccount);
+
+        let payout = ata.amount;
+        if position_info.owner == &crate::ID {
+            let mut position: Box<Account<Position>> = Box::new(Account::try_from(position_info)?);
+            position.record_claim(payout)?;
+            position.exit(&crate::ID)?;
+        }
+        if payout == 0 {
+            continue;
+        }
+
+        let burn_accounts = Burn {
+            mint: mint_info.clone(),
+            from: ata_info.clone(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_accounts);
+        token::burn(cpi_ctx, payout)?;
+
+        let transfer_accounts = system_program::Transfer {
+            from: vault_info.clone(),
+            to: ctx.accounts.user.to_account_info(),
+        };
+        let signer_seeds: &[&[&[u8]]] = &[&[b"market-vault", market_key.as_ref(), &[vault_bump]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+        system_program::transfer(cpi_ctx, payout)?;
+        emit!(WinningsClaimed { market: market_key, holder: user, payout });
+    }
+    Ok(())
+}
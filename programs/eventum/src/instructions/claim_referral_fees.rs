@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::events::ReferralFeesClaimed;
+use crate::states::Referrer;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        seeds = [b"referrer", authority.key().as_ref()],
+        bump = referrer.bump,
+    )]
+    pub referrer: Account<'info, Referrer>,
+}
+
+pub fn handler(ctx: Context<ClaimReferralFees>) -> Result<()> {
+    let amount = ctx.accounts.referrer.pending;
+    require!(amount > 0, ErrorCode::NoBalance);
+
+    // the PDA is owned by this program, so its lamports can be moved directly
+    ctx.accounts.referrer.sub_lamports(amount)?;
+    ctx.accounts.authority.add_lamports(amount)?;
+    ctx.accounts.referrer.pending = 0;
+
+    emit!(ReferralFeesClaimed {
+        referrer: ctx.accounts.referrer.key(),
+        amount,
+    });
+    Ok(())
+}
@@ -4,19 +4,16 @@ use anchor_spl::{
     token::{self, Burn, Mint, Token, TokenAccount}
 };
 
-use crate::states::Market;
+use crate::events::WinningsClaimed;
+use crate::states::{Market, Position};
 use crate::ErrorCode;
 
 #[derive(Accounts)]
-#[instruction(unique_market_id : u64)]
 pub struct ClaimWinnings<'info>{
-    /// CHECK: only fro seeds derivation 
-    pub creator : UncheckedAccount<'info> ,
-
     #[account(
         mut ,
-        seeds = [b"Market" , creator.key().as_ref() , &unique_market_id.to_le_bytes()] ,
-        bump 
+        seeds = [b"Market" , market.creator.as_ref() , &market.unique_market_id.to_le_bytes()] ,
+        bump = market.bump
     )]
     pub market : Account<'info , Market> ,  
 
@@ -65,41 +62,31 @@ pub struct ClaimWinnings<'info>{
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(
+        mut ,
+        seeds = [b"position" , market.key().as_ref() , user.key().as_ref()] ,
+        bump = position.bump ,
+    )]
+    pub position : Option<Account<'info , Position>> ,
 }
 
 
-pub fn handler(ctx : Context<ClaimWinnings> , unique_market_id : u64)->Result<()>{
+pub fn handler(ctx : Context<ClaimWinnings>)->Result<()>{
     let market = &mut ctx.accounts.market ;
-    let creator = ctx.accounts.creator.key() ;
-    let user = ctx.accounts.user.key() ;
-    let yes_ata = &ctx.accounts.user_yes_ata ;
-    let no_ata = &ctx.accounts.user_no_ata ;
     let outcome = market.winning_outcome ;  // bool 
-    let yes_mint = &ctx.accounts.yes_mint ;
-    let no_mint = &ctx.accounts.no_mint ;
-    let vault  = ctx.accounts.vault .key() ;
-    let bump = market.bump ;
     let vault_bump= market.vault_bump ;
     let market_key = market.key() ;
 
 
-    require!(market.resolved == true , ErrorCode::MarketNotResolved) ;
+    require!(market.resolved , ErrorCode::MarketNotResolved) ;
 
     // burn tokens , like if the outcome is yes and user owns yes tokens 
 
-    let signer_seeds : &[&[&[u8]]] = &[&[
-        b"Market" ,
-        creator.as_ref() ,
-        &unique_market_id.to_le_bytes() ,
-        &[bump] 
-    ]] ;
-
-    let mut payout ;
+    let payout ;
     if outcome {    // means outcome == true ; i.e. outcome = yes 
         // burn all the yes tokens , calculate the payout , and transfer in the account
         let user_yes_tokens = ctx.accounts.user_yes_ata.amount ;
-        let vault_balance = ctx.accounts.vault .lamports();
-        let total_yes_minted = market.yes_tokens ;
         payout = user_yes_tokens ;
         let burn_accounts = Burn{
             mint : ctx.accounts.yes_mint.to_account_info() ,
@@ -111,8 +98,6 @@ pub fn handler(ctx : Context<ClaimWinnings> , unique_market_id : u64)->Result<()
         token::burn(cpi_ctx, user_yes_tokens)?;
     }else {   // else no wins 
         let user_no_tokens = ctx.accounts.user_no_ata.amount ;
-        let vault_balance = ctx.accounts.vault .lamports();
-        let total_no_minted = market.no_tokens ;
         payout = user_no_tokens ;
 
         let burn_accounts = Burn{
@@ -139,5 +124,12 @@ pub fn handler(ctx : Context<ClaimWinnings> , unique_market_id : u64)->Result<()
     let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.system_program.to_account_info(), transfer_accounts, signer_seeds2) ;
 
     system_program::transfer(cpi_ctx, payout )?; 
+
+    if let Some(position) = ctx.accounts.position.as_mut() {
+        position.record_claim(payout)?;
+    }
+    if payout > 0 {
+        emit!(WinningsClaimed { market : market_key , holder : ctx.accounts.user.key() , payout }) ;
+    }
     Ok(())
 }
\ No newline at end of file
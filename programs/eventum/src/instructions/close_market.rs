@@ -0,0 +1,93 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token::{Mint, Token};
+
+use crate::states::Market;
+use crate::ErrorCode;
+
+/// How long after `end_time` a resolved market may be closed even if some
+/// winning tokens were never redeemed (180 days).
+pub const CLOSE_GRACE_PERIOD: i64 = 180 * 24 * 60 * 60;
+
+#[derive(Accounts)]
+pub struct CloseMarket<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ ErrorCode::InvalidCreator,
+        seeds = [b"Market", market.creator.as_ref(), &market.unique_market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"yes_mint", market.key().as_ref()],
+        bump,
+    )]
+    pub yes_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"no_mint", market.key().as_ref()],
+        bump,
+    )]
+    pub no_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"market-vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Sweeps whatever is left in the vault back to the creator and closes the
+/// `Market` account. Allowed once every winning token has been burned through
+/// `claim_winnings`, or once `CLOSE_GRACE_PERIOD` has passed since `end_time`.
+///
+/// The outcome mints are owned by the legacy token program, which has no
+/// close instruction for mints, so they stay on-chain with a frozen supply
+/// (the market PDA that holds their mint authority no longer exists).
+pub fn handler(ctx: Context<CloseMarket>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    require!(market.resolved, ErrorCode::MarketNotResolved);
+
+    let winning_supply = if market.winning_outcome {
+        ctx.accounts.yes_mint.supply
+    } else {
+        ctx.accounts.no_mint.supply
+    };
+    let now = Clock::get()?.unix_timestamp;
+    let grace_over = now
+        >= market
+            .end_time
+            .checked_add(CLOSE_GRACE_PERIOD)
+            .ok_or(ErrorCode::MathOverflow)?;
+    require!(winning_supply == 0 || grace_over, ErrorCode::WinningsOutstanding);
+
+    let remaining = ctx.accounts.vault.lamports();
+    if remaining > 0 {
+        let market_key = market.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"market-vault",
+            market_key.as_ref(),
+            &[market.vault_bump],
+        ]];
+        let transfer_accounts = system_program::Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.creator.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            transfer_accounts,
+            signer_seeds,
+        );
+        system_program::transfer(cpi_ctx, remaining)?;
+    }
+
+    Ok(())
+}
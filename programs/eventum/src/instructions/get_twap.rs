@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+use crate::states::PriceOracle;
+
+#[derive(Accounts)]
+pub struct GetTwap<'info> {
+    pub price_oracle: Box<Account<'info, PriceOracle>>,
+}
+
+/// Average YES price over the last `window` seconds, scaled by
+/// `PRICE_SCALE`. Read-only; call it by simulation or through CPI.
+pub fn handler(ctx: Context<GetTwap>, window: i64) -> Result<u64> {
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.price_oracle.twap(now, window)
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Market, OptimisticResolution};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct InitOptimisticResolution<'info> {
+    #[account(mut)]
+    pub oracle_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"Market", market.creator.as_ref(), &market.unique_market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        init,
+        payer = oracle_authority,
+        space = 8 + OptimisticResolution::INIT_SPACE,
+        seeds = [b"optimistic-resolution", market.key().as_ref()],
+        bump
+    )]
+    pub optimistic_resolution: Box<Account<'info, OptimisticResolution>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Gives up the market's oracle: from here on it is resolved by bonded
+/// proposals, with `arbiter` deciding disputes within `arbitration_period`
+/// seconds. This cannot be undone.
+pub fn handler(
+    ctx: Context<InitOptimisticResolution>,
+    arbiter: Pubkey,
+    bond: u64,
+    liveness: i64,
+    reward_bps: u16,
+    arbitration_period: i64,
+) -> Result<()> {
+    let market = &ctx.accounts.market;
+    require!(!market.resolved, ErrorCode::MarketResolved);
+    require!(
+        market.oracle_authority == ctx.accounts.oracle_authority.key(),
+        ErrorCode::OracleNotMatched
+    );
+    require!(
+        bond > 0 && liveness > 0 && arbitration_period > 0 && reward_bps <= 10000,
+        ErrorCode::InvalidResolutionParams
+    );
+
+    let optimistic = &mut ctx.accounts.optimistic_resolution;
+    optimistic.market = market.key();
+    optimistic.arbiter = arbiter;
+    optimistic.bond = bond;
+    optimistic.liveness = liveness;
+    optimistic.reward_bps = reward_bps;
+    optimistic.arbitration_period = arbitration_period;
+    optimistic.proposal = None;
+    optimistic.challenger = None;
+    optimistic.arbitration_deadline = 0;
+    optimistic.bump = ctx.bumps.optimistic_resolution;
+
+    ctx.accounts.market.oracle_authority = ctx.accounts.optimistic_resolution.key();
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::pricing::{engine_for, scaled_price};
+use crate::states::{Market, Observation, PriceOracle};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct InitPriceOracle<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = creator @ ErrorCode::InvalidCreator,
+        seeds = [b"Market", market.creator.as_ref(), &market.unique_market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(address = market.yes_mint @ ErrorCode::InvalidMarket)]
+    pub yes_mint: Account<'info, Mint>,
+
+    #[account(address = market.no_mint @ ErrorCode::InvalidMarket)]
+    pub no_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PriceOracle::INIT_SPACE,
+        seeds = [b"price-oracle", market.key().as_ref()],
+        bump
+    )]
+    pub price_oracle: Box<Account<'info, PriceOracle>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Starts recording the market's YES price. From here on every trade on
+/// the market must pass the oracle account so no price move goes unseen.
+pub fn handler(ctx: Context<InitPriceOracle>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    require!(!market.resolved, ErrorCode::MarketResolved);
+
+    let engine = engine_for(
+        market,
+        ctx.accounts.yes_mint.supply,
+        ctx.accounts.no_mint.supply,
+        ctx.accounts.yes_mint.decimals,
+    );
+    let now = Clock::get()?.unix_timestamp;
+
+    let oracle = &mut ctx.accounts.price_oracle;
+    oracle.market = market.key();
+    oracle.last_price = scaled_price(engine.price_yes()?)?;
+    oracle.last_update = now;
+    oracle.cumulative = 0;
+    oracle.head = 0;
+    oracle.len = 1;
+    oracle.observations[0] = Observation { timestamp: now, cumulative: 0 };
+    oracle.bump = ctx.bumps.price_oracle;
+
+    ctx.accounts.market.price_oracle = ctx.accounts.price_oracle.key();
+    Ok(())
+}
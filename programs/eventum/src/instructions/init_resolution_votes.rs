@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Market, ResolutionVotes, MAX_RESOLVERS};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct InitResolutionVotes<'info> {
+    #[account(mut)]
+    pub oracle_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"Market", market.creator.as_ref(), &market.unique_market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        init,
+        payer = oracle_authority,
+        space = 8 + ResolutionVotes::INIT_SPACE,
+        seeds = [b"resolution-votes", market.key().as_ref()],
+        bump
+    )]
+    pub resolution_votes: Box<Account<'info, ResolutionVotes>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Hands the market's resolution from its oracle to `threshold` of
+/// `resolvers`. This cannot be undone: the market's oracle becomes the
+/// votes account, which has no key to sign `resolve_market`.
+pub fn handler(ctx: Context<InitResolutionVotes>, resolvers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    let market = &ctx.accounts.market;
+    require!(!market.resolved, ErrorCode::MarketResolved);
+    require!(
+        market.oracle_authority == ctx.accounts.oracle_authority.key(),
+        ErrorCode::OracleNotMatched
+    );
+    require!(
+        resolvers.len() <= MAX_RESOLVERS && threshold >= 1 && threshold as usize <= resolvers.len(),
+        ErrorCode::InvalidResolverSet
+    );
+    require!(
+        resolvers.iter().enumerate().all(|(i, resolver)| !resolvers[..i].contains(resolver)),
+        ErrorCode::InvalidResolverSet
+    );
+
+    let votes = &mut ctx.accounts.resolution_votes;
+    votes.market = market.key();
+    votes.threshold = threshold;
+    votes.votes = vec![None; resolvers.len()];
+    votes.resolvers = resolvers;
+    votes.bump = ctx.bumps.resolution_votes;
+
+    ctx.accounts.market.oracle_authority = ctx.accounts.resolution_votes.key();
+    Ok(())
+}
@@ -1,21 +1,60 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, system_program};
 use anchor_spl::token::{Mint, Token};
 
-use crate::states::Market;
+use crate::pricing::creation_subsidy;
+use crate::states::{Config, CreatorApproval, CreatorProfile, FeeModel, Market, PricingMode, TradeLimits};
+use crate::error::ErrorCode;
 
 
+/// Arguments of `initialize_market`. `unique_market_id` must be the
+/// creator's next id, `CreatorProfile.market_count` (0 for a new creator).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitializeMarketArgs {
+    pub unique_market_id: u64,
+    pub end_time: i64,
+    pub fee: u32,                  // bps
+    pub fee_model: FeeModel,
+    pub referral_share_bps: u16,
+    pub pricing_mode: PricingMode,
+    pub limits: TradeLimits,
+    pub question: String,
+}
+
 #[derive(Accounts)]
-#[instruction(unique_market_id: u64)]
+#[instruction(args: InitializeMarketArgs)]
 pub struct InitializeMarket<'info>{
     #[account(mut)]
     pub creator : Signer<'info> ,
 
-    /// CHECK: Oracle authority is stored but not validated at initialization.
+    /// CHECK: The config PDA, read only if it exists. Until the upgrade
+    /// authority runs `initialize_config`, market creation is permissionless.
+    #[account(
+        seeds = [b"config"] ,
+        bump ,
+    )]
+    pub config : UncheckedAccount<'info> ,
+    #[account(
+        seeds = [b"approval" , creator.key().as_ref()] ,
+        bump = creator_approval.bump ,
+    )]
+    pub creator_approval : Option<Account<'info , CreatorApproval>> ,
+
+    /// CHECK: Stored as the market's oracle authority, the key that may
+    /// resolve it; any address works, including the creator's own.
     pub oracle_authority : UncheckedAccount<'info> ,
+    #[account(
+        init_if_needed ,
+        payer = creator ,
+        seeds = [b"creator" , creator.key().as_ref()] ,
+        space = 8 + CreatorProfile::INIT_SPACE ,
+        bump ,
+        constraint = creator_profile.market_count == args.unique_market_id @ ErrorCode::DuplicateMarketId
+    )]
+    pub creator_profile : Account<'info , CreatorProfile> ,
     #[account(
         init , 
         payer = creator ,
-        seeds =[ b"Market" , creator.key().as_ref() , &unique_market_id.to_le_bytes()] ,
+        seeds =[ b"Market" , creator.key().as_ref() , &args.unique_market_id.to_le_bytes()] ,
         space = 8 + Market::INIT_SPACE ,
         bump 
     )]
@@ -47,23 +86,73 @@ pub struct InitializeMarket<'info>{
     pub system_program : Program<'info , System> , 
     pub token_program : Program<'info , Token>
 }
-pub fn handler(ctx : Context<InitializeMarket> , unique_market_id : u64  , end_time : i64 , fee : u32 , question : String )->Result<()>{
+pub fn handler(ctx : Context<InitializeMarket> , args : InitializeMarketArgs )->Result<()>{
+    let InitializeMarketArgs { unique_market_id , end_time , fee , fee_model , referral_share_bps , pricing_mode , limits , question } = args ;
+    require!(referral_share_bps <= 10000 , ErrorCode::InvalidFeeShare) ;
+    match pricing_mode {
+        PricingMode::Lmsr { b } => {
+            require!(b > 0 , ErrorCode::InvalidPricingParams) ;
+        }
+        PricingMode::LsLmsr { alpha_bps , min_b } => {
+            require!(alpha_bps > 0 && alpha_bps <= 10000 && min_b > 0 , ErrorCode::InvalidPricingParams) ;
+        }
+        PricingMode::Cpmm { seed_liquidity } => {
+            require!(seed_liquidity > 0 , ErrorCode::NoLiquidity) ;
+        }
+    }
+    require!(limits.max_price_move_bps <= 10000 , ErrorCode::InvalidPricingParams) ;
+    require!(limits.max_tokens == 0 || limits.min_tokens <= limits.max_tokens , ErrorCode::InvalidPricingParams) ;
+    let approved = ctx.accounts.creator_approval.is_some() ;
+    let config = &ctx.accounts.config ;
+    let permissioned = if *config.owner == crate::ID {
+        Config::try_deserialize(&mut &config.data.borrow()[..])?.permissioned
+    } else {
+        false
+    } ;
+    require!(approved || !permissioned , ErrorCode::CreatorNotApproved) ;
     let market = &mut ctx.accounts.market ;
     market.question = question ;
     market.creator = ctx.accounts.creator.key() ;
-    market.oracle_authority = ctx.accounts.creator.key() ;
+    market.oracle_authority = ctx.accounts.oracle_authority.key() ;
     market.unique_market_id = unique_market_id ;
     market.start_time = Clock::get()?.unix_timestamp ;
     market.end_time = end_time ;
     market.resolved = false ;
     market.winning_outcome = false ;
     market.fee = fee ;    // bps 
+    market.fee_model = fee_model ;
+    market.referral_share_bps = referral_share_bps ;
+    market.pricing_mode = pricing_mode ;
+    market.limits = limits ;
     market.bump = ctx.bumps.market ;
     market.vault_bump = ctx.bumps.vault;
-    market.total_liquidity = 0 ;
     market.yes_mint = ctx.accounts.yes_mint.key() ;
     market.no_mint = ctx.accounts.no_mint.key() ;
     market.yes_tokens = 0 ;
     market.no_tokens = 0 ;
+    market.approved_creator = approved ;
+
+    // the creator funds the maker's worst-case loss (for CPMM, the pool's
+    // complete sets, one lamport per raw token) so the vault covers every
+    // payout, plus the vault's rent-exempt minimum so the last payout never
+    // leaves it holding dust; `close_market` returns both
+    if let PricingMode::Cpmm { seed_liquidity } = pricing_mode {
+        market.yes_reserve = seed_liquidity ;
+        market.no_reserve = seed_liquidity ;
+    }
+    let subsidy = creation_subsidy(pricing_mode , ctx.accounts.yes_mint.decimals)? ;
+    market.total_liquidity = subsidy ;
+    let vault_rent = Rent::get()?.minimum_balance(0).saturating_sub(ctx.accounts.vault.lamports()) ;
+    let transfer_accounts = system_program::Transfer{
+        from : ctx.accounts.creator.to_account_info() ,
+        to : ctx.accounts.vault.to_account_info() ,
+    } ;
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_accounts) ;
+    system_program::transfer(cpi_ctx, subsidy.checked_add(vault_rent).ok_or(ErrorCode::MathOverflow)?)?;
+
+    let profile = &mut ctx.accounts.creator_profile ;
+    profile.creator = ctx.accounts.creator.key() ;
+    profile.bump = ctx.bumps.creator_profile ;
+    profile.market_count = profile.market_count.checked_add(1).ok_or(ErrorCode::MathOverflow)? ;
     Ok(())
 }
\ No newline at end of file
@@ -1,8 +1,9 @@
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize_market;
 pub use initialize_market::*;
 
 pub mod add_liquidity;
-pub use add_liquidity::*;
 
 pub mod buy_outcomes;
 pub use buy_outcomes::*;
@@ -11,4 +12,67 @@ pub mod resolve_market;
 pub use resolve_market::* ;
 
 pub mod claim_winnings; 
-pub use claim_winnings::* ;
\ No newline at end of file
+pub use claim_winnings::* ;
+
+pub mod close_market; 
+pub use close_market::* ;
+
+pub mod open_position; 
+pub use open_position::* ;
+
+pub mod set_market_metadata; 
+pub use set_market_metadata::* ;
+
+pub mod initialize_config; 
+pub use initialize_config::* ;
+
+pub mod set_permissioned; 
+pub use set_permissioned::* ;
+
+pub mod approve_creator; 
+pub use approve_creator::* ;
+
+pub mod revoke_creator; 
+pub use revoke_creator::* ;
+
+pub mod batch_trade; 
+pub use batch_trade::* ;
+
+pub mod claim_many; 
+pub use claim_many::* ;
+
+pub mod claim_for; 
+pub use claim_for::* ;
+
+pub mod register_referrer; 
+pub use register_referrer::* ;
+
+pub mod claim_referral_fees; 
+pub use claim_referral_fees::* ;
+
+pub mod init_price_oracle; 
+pub use init_price_oracle::* ;
+
+pub mod get_twap; 
+pub use get_twap::* ;
+
+pub mod init_resolution_votes; 
+pub use init_resolution_votes::* ;
+
+pub mod vote_resolution; 
+pub use vote_resolution::* ;
+
+pub mod init_optimistic_resolution; 
+pub use init_optimistic_resolution::* ;
+
+pub mod propose_outcome; 
+pub use propose_outcome::* ;
+
+pub mod challenge_proposal; 
+pub use challenge_proposal::* ;
+
+pub mod settle_proposal; 
+pub use settle_proposal::* ;
+
+pub mod arbitrate; 
+pub use arbitrate::* ;
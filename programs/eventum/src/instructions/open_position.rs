@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Market, Position};
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"Market", market.creator.as_ref(), &market.unique_market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<OpenPosition>) -> Result<()> {
+    let position = &mut ctx.accounts.position;
+    position.market = ctx.accounts.market.key();
+    position.owner = ctx.accounts.user.key();
+    position.bump = ctx.bumps.position;
+    Ok(())
+}
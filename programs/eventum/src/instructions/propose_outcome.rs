@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::events::OutcomeProposed;
+use crate::states::{Market, OptimisticResolution, Side};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ProposeOutcome<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        seeds = [b"Market", market.creator.as_ref(), &market.unique_market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        has_one = market @ ErrorCode::InvalidMarket,
+        seeds = [b"optimistic-resolution", market.key().as_ref()],
+        bump = optimistic_resolution.bump,
+    )]
+    pub optimistic_resolution: Box<Account<'info, OptimisticResolution>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Proposes `outcome` once the market has ended, bonding `bond` lamports
+/// into the resolution account. Only one proposal is ever accepted, and
+/// never from the arbiter.
+pub fn handler(ctx: Context<ProposeOutcome>, outcome: Side) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let now = Clock::get()?.unix_timestamp;
+    require!(!market.resolved, ErrorCode::MarketResolved);
+    require!(now >= market.end_time, ErrorCode::MarketNotExpired);
+    require!(ctx.accounts.optimistic_resolution.proposal.is_none(), ErrorCode::ProposalExists);
+    require_keys_neq!(
+        ctx.accounts.proposer.key(),
+        ctx.accounts.optimistic_resolution.arbiter,
+        ErrorCode::ArbiterIsParty
+    );
+
+    let bond = ctx.accounts.optimistic_resolution.bond;
+    require!(ctx.accounts.proposer.lamports() >= bond, ErrorCode::InsufficientBalance);
+    let accounts = Transfer {
+        from: ctx.accounts.proposer.to_account_info(),
+        to: ctx.accounts.optimistic_resolution.to_account_info(),
+    };
+    transfer(CpiContext::new(ctx.accounts.system_program.to_account_info(), accounts), bond)?;
+
+    let optimistic = &mut ctx.accounts.optimistic_resolution;
+    optimistic.proposal = Some(outcome);
+    optimistic.proposer = ctx.accounts.proposer.key();
+    optimistic.proposed_at = now;
+
+    emit!(OutcomeProposed {
+        market: market.key(),
+        proposer: optimistic.proposer,
+        outcome,
+        bond,
+    });
+    Ok(())
+}
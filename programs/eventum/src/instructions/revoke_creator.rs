@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Config, CreatorApproval};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct RevokeCreator<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ ErrorCode::Unauthorized,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Used only for PDA derivation
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"approval", creator.key().as_ref()],
+        bump = creator_approval.bump,
+    )]
+    pub creator_approval: Account<'info, CreatorApproval>,
+}
+
+/// Markets the creator already made keep their `approved_creator` flag;
+/// revocation only stops new ones while the program is permissioned.
+pub fn handler(_ctx: Context<RevokeCreator>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::states::*;
+use crate::error::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetMarketMetadataArgs {
+    pub question: String,
+    pub resolution_rules: String,
+    pub category: MarketCategory,
+    pub tags: Vec<String>,
+    pub uri: String,
+    pub content_hash: [u8; 32],
+}
+
+#[derive(Accounts)]
+pub struct SetMarketMetadata<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        has_one = creator @ ErrorCode::InvalidCreator,
+        seeds = [b"Market", market.creator.as_ref(), &market.unique_market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + MarketMetadata::INIT_SPACE,
+        seeds = [b"metadata", market.key().as_ref()],
+        bump
+    )]
+    pub metadata: Account<'info, MarketMetadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetMarketMetadata>, args: SetMarketMetadataArgs) -> Result<()> {
+    let SetMarketMetadataArgs {
+        question,
+        resolution_rules,
+        category,
+        tags,
+        uri,
+        content_hash,
+    } = args;
+    require!(question.len() <= MAX_METADATA_QUESTION_LEN, ErrorCode::MetadataTooLong);
+    require!(resolution_rules.len() <= MAX_RESOLUTION_RULES_LEN, ErrorCode::MetadataTooLong);
+    require!(tags.len() <= MAX_TAGS, ErrorCode::MetadataTooLong);
+    require!(tags.iter().all(|tag| tag.len() <= MAX_TAG_LEN), ErrorCode::MetadataTooLong);
+    require!(uri.len() <= MAX_URI_LEN, ErrorCode::MetadataTooLong);
+
+    let metadata = &mut ctx.accounts.metadata;
+    metadata.market = ctx.accounts.market.key();
+    metadata.question = question;
+    metadata.resolution_rules = resolution_rules;
+    metadata.category = category;
+    metadata.tags = tags;
+    metadata.uri = uri;
+    metadata.content_hash = content_hash;
+    metadata.bump = ctx.bumps.metadata;
+    Ok(())
+}
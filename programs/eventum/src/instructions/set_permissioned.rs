@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::states::Config;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetPermissioned<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::Unauthorized,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<SetPermissioned>, permissioned: bool) -> Result<()> {
+    ctx.accounts.config.permissioned = permissioned;
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::events::{MarketResolved, ProposalSettled};
+use crate::states::{Market, OptimisticResolution};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SettleProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"Market", market.creator.as_ref(), &market.unique_market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        has_one = market @ ErrorCode::InvalidMarket,
+        has_one = proposer @ ErrorCode::Unauthorized,
+        seeds = [b"optimistic-resolution", market.key().as_ref()],
+        bump = optimistic_resolution.bump,
+    )]
+    pub optimistic_resolution: Box<Account<'info, OptimisticResolution>>,
+
+    #[account(mut)]
+    pub proposer: SystemAccount<'info>,
+
+    /// Only needed to refund a challenger whose dispute the arbiter let lapse.
+    #[account(mut)]
+    pub challenger: Option<SystemAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"market-vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Finalizes an unchallenged proposal once its liveness period is over.
+/// Anyone may crank it; the proposer gets the bond back plus `reward_bps`
+/// of the fees the vault has collected, which are surplus to what winning
+/// tokens are owed. A challenged proposal the arbiter has not decided by
+/// the arbitration deadline also stands, without the reward, and the
+/// challenger's bond is refunded too.
+pub fn handler(ctx: Context<SettleProposal>) -> Result<()> {
+    let optimistic = &ctx.accounts.optimistic_resolution;
+    let now = Clock::get()?.unix_timestamp;
+    require!(!ctx.accounts.market.resolved, ErrorCode::MarketResolved);
+    let outcome = optimistic.proposal.ok_or(ErrorCode::NoProposal)?;
+    let disputed = match optimistic.challenger {
+        None => {
+            require!(!optimistic.in_liveness(now), ErrorCode::LivenessActive);
+            false
+        }
+        Some(challenger) => {
+            require!(now >= optimistic.arbitration_deadline, ErrorCode::ProposalDisputed);
+            let account = ctx.accounts.challenger.as_ref().ok_or(ErrorCode::Unauthorized)?;
+            require_keys_eq!(account.key(), challenger, ErrorCode::Unauthorized);
+            true
+        }
+    };
+
+    let bond = optimistic.bond;
+    let reward = if disputed {
+        0
+    } else {
+        let reward = (ctx.accounts.market.fees_collected as u128)
+            .checked_mul(optimistic.reward_bps as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            / 10000;
+        u64::try_from(reward).map_err(|_| ErrorCode::MathOverflow)?
+    };
+    let payout = bond.checked_add(reward).ok_or(ErrorCode::MathOverflow)?;
+
+    if reward > 0 {
+        let market_key = ctx.accounts.market.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"market-vault", market_key.as_ref(), &[ctx.accounts.market.vault_bump]]];
+        let accounts = Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.proposer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.system_program.to_account_info(), accounts, signer_seeds);
+        transfer(cpi_ctx, reward)?;
+    }
+    // the resolution PDA is owned by this program, so the bond moves directly
+    ctx.accounts.optimistic_resolution.sub_lamports(bond)?;
+    ctx.accounts.proposer.add_lamports(bond)?;
+    if disputed {
+        // checked above to be the challenger's account
+        let challenger = ctx.accounts.challenger.as_ref().ok_or(ErrorCode::Unauthorized)?;
+        ctx.accounts.optimistic_resolution.sub_lamports(bond)?;
+        challenger.add_lamports(bond)?;
+    }
+
+    let market = &mut ctx.accounts.market;
+    market.fees_collected = market.fees_collected.checked_sub(reward).ok_or(ErrorCode::MathOverflow)?;
+    market.resolved = true;
+    market.winning_outcome = outcome.is_yes();
+    emit!(MarketResolved { market: market.key(), outcome });
+    emit!(ProposalSettled {
+        market: market.key(),
+        outcome,
+        disputed,
+        recipient: ctx.accounts.proposer.key(),
+        payout,
+    });
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{MarketResolved, ResolutionVoted};
+use crate::states::{Market, ResolutionVotes, Side};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct VoteResolution<'info> {
+    pub resolver: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"Market", market.creator.as_ref(), &market.unique_market_id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        has_one = market @ ErrorCode::InvalidMarket,
+        seeds = [b"resolution-votes", market.key().as_ref()],
+        bump = resolution_votes.bump,
+    )]
+    pub resolution_votes: Box<Account<'info, ResolutionVotes>>,
+}
+
+/// Records `resolver`'s vote and resolves the market once `threshold`
+/// votes agree. A vote cannot be changed: repeating it is a no-op and
+/// contradicting it fails with `InvalidOracleData`.
+pub fn handler(ctx: Context<VoteResolution>, outcome: Side) -> Result<()> {
+    require!(!ctx.accounts.market.resolved, ErrorCode::MarketResolved);
+
+    let resolver = ctx.accounts.resolver.key();
+    let votes = &mut ctx.accounts.resolution_votes;
+    let index = votes
+        .resolvers
+        .iter()
+        .position(|r| *r == resolver)
+        .ok_or(ErrorCode::UnauthorizedResolver)?;
+    match votes.votes[index] {
+        Some(previous) => require!(previous == outcome, ErrorCode::InvalidOracleData),
+        None => votes.votes[index] = Some(outcome),
+    }
+
+    let tally = votes.tally(outcome);
+    let threshold = votes.threshold;
+    let market = &mut ctx.accounts.market;
+    emit!(ResolutionVoted { market: market.key(), resolver, outcome, votes: tally, threshold });
+
+    if tally >= threshold {
+        market.resolved = true;
+        market.winning_outcome = outcome.is_yes();
+        emit!(MarketResolved { market: market.key(), outcome });
+    }
+    Ok(())
+}
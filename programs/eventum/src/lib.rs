@@ -3,8 +3,15 @@ declare_id!("GppvioZ7AAwV14n7oM4B9fSndufUL4ZicevuYNfKUrbx");
 
 pub mod instructions;
 pub mod states;
+pub mod events;
+pub mod pricing;
+pub mod pda;
 
 use instructions::*;
+
+// argument types of the public instruction API, for CPI callers
+pub use instructions::{BuyOutcomesArgs, InitializeMarketArgs, SetMarketMetadataArgs, TradeLeg};
+pub use states::{FeeModel, MarketCategory, PricingMode, Side, TradeLimits};
 pub mod error; 
 use error::ErrorCode;
 
@@ -14,8 +21,28 @@ pub mod eventum {
 
     use super::*;
 
-    pub fn initialize_market(ctx: Context<InitializeMarket> , unique_market_id : u64 , end_time :i64 , fee : u32 , question : String ) -> Result<()> {
-        instructions::initialize_market::handler(ctx,  unique_market_id ,end_time , fee , question)?;
+    pub fn initialize_config(ctx: Context<InitializeConfig> , permissioned : bool ) -> Result<()> {
+        instructions::initialize_config::handler(ctx , permissioned )?;
+        Ok(())
+    }
+
+    pub fn set_permissioned(ctx: Context<SetPermissioned> , permissioned : bool ) -> Result<()> {
+        instructions::set_permissioned::handler(ctx , permissioned )?;
+        Ok(())
+    }
+
+    pub fn approve_creator(ctx: Context<ApproveCreator> ) -> Result<()> {
+        instructions::approve_creator::handler(ctx )?;
+        Ok(())
+    }
+
+    pub fn revoke_creator(ctx: Context<RevokeCreator> ) -> Result<()> {
+        instructions::revoke_creator::handler(ctx )?;
+        Ok(())
+    }
+
+    pub fn initialize_market(ctx: Context<InitializeMarket> , args : InitializeMarketArgs ) -> Result<()> {
+        instructions::initialize_market::handler(ctx, args)?;
         Ok(())
     }
 
@@ -24,18 +51,102 @@ pub mod eventum {
     //     Ok(())
     // }
 
-    pub fn buy_outcomes(ctx: Context<BuyOutcomes> , unique_market_id: u64 , number_of_tokens : u64 , yes : bool ) -> Result<()> {
-        instructions::buy_outcomes::handler(ctx,unique_market_id ,  number_of_tokens , yes )?;
+    pub fn set_market_metadata(ctx: Context<SetMarketMetadata> , args : SetMarketMetadataArgs ) -> Result<()> {
+        instructions::set_market_metadata::handler(ctx, args)?;
+        Ok(())
+    }
+
+    pub fn open_position(ctx: Context<OpenPosition> ) -> Result<()> {
+        instructions::open_position::handler(ctx )?;
+        Ok(())
+    }
+
+    pub fn buy_outcomes(ctx: Context<BuyOutcomes> , args : BuyOutcomesArgs ) -> Result<()> {
+        instructions::buy_outcomes::handler(ctx, args)?;
+        Ok(())
+    }
+
+    pub fn batch_trade<'info>(ctx: Context<'_, '_, 'info, 'info, BatchTrade<'info>> , legs : Vec<TradeLeg> ) -> Result<()> {
+        instructions::batch_trade::handler(ctx , legs )?;
+        Ok(())
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer> ) -> Result<()> {
+        instructions::register_referrer::handler(ctx )?;
+        Ok(())
+    }
+
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees> ) -> Result<()> {
+        instructions::claim_referral_fees::handler(ctx )?;
+        Ok(())
+    }
+
+    pub fn init_price_oracle(ctx: Context<InitPriceOracle> ) -> Result<()> {
+        instructions::init_price_oracle::handler(ctx )?;
+        Ok(())
+    }
+
+    pub fn get_twap(ctx: Context<GetTwap> , window : i64 ) -> Result<u64> {
+        instructions::get_twap::handler(ctx , window )
+    }
+
+    pub fn resolve_market(ctx: Context<ResolveMarket> , outcome : Side ) -> Result<()> {
+        instructions::resolve_market::handler(ctx , outcome )?;
+        Ok(())
+    }
+
+    pub fn init_resolution_votes(ctx: Context<InitResolutionVotes> , resolvers : Vec<Pubkey> , threshold : u8 ) -> Result<()> {
+        instructions::init_resolution_votes::handler(ctx , resolvers , threshold )?;
+        Ok(())
+    }
+
+    pub fn vote_resolution(ctx: Context<VoteResolution> , outcome : Side ) -> Result<()> {
+        instructions::vote_resolution::handler(ctx , outcome )?;
+        Ok(())
+    }
+
+    pub fn init_optimistic_resolution(ctx: Context<InitOptimisticResolution> , arbiter : Pubkey , bond : u64 , liveness : i64 , reward_bps : u16 , arbitration_period : i64 ) -> Result<()> {
+        instructions::init_optimistic_resolution::handler(ctx , arbiter , bond , liveness , reward_bps , arbitration_period )?;
+        Ok(())
+    }
+
+    pub fn propose_outcome(ctx: Context<ProposeOutcome> , outcome : Side ) -> Result<()> {
+        instructions::propose_outcome::handler(ctx , outcome )?;
+        Ok(())
+    }
+
+    pub fn challenge_proposal(ctx: Context<ChallengeProposal> ) -> Result<()> {
+        instructions::challenge_proposal::handler(ctx )?;
+        Ok(())
+    }
+
+    pub fn settle_proposal(ctx: Context<SettleProposal> ) -> Result<()> {
+        instructions::settle_proposal::handler(ctx )?;
+        Ok(())
+    }
+
+    pub fn arbitrate(ctx: Context<Arbitrate> , outcome : Side ) -> Result<()> {
+        instructions::arbitrate::handler(ctx , outcome )?;
+        Ok(())
+    }
+
+    pub fn claim_winnings(ctx: Context<ClaimWinnings> ) -> Result<()> {
+        instructions::claim_winnings::handler(ctx )?;
+        Ok(())
+    }
+
+    pub fn claim_for(ctx: Context<ClaimFor> , tip_bps : u16 ) -> Result<()> {
+        instructions::claim_for::handler(ctx , tip_bps )?;
         Ok(())
     }
 
-    pub fn resolve_market(ctx: Context<ResolveMarket> , unique_market_id: u64 , outcome : bool  ) -> Result<()> {
-        instructions::resolve_market::handler(ctx , unique_market_id , outcome )?;
+    pub fn claim_many<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimMany<'info>> ) -> Result<()> {
+        instructions::claim_many::handler(ctx )?;
         Ok(())
     }
 
-    pub fn claim_winnings(ctx: Context<ClaimWinnings> , unique_market_id: u64  ) -> Result<()> {
-        instructions::claim_winnings::handler(ctx , unique_market_id )?;
+    pub fn close_market(ctx: Context<CloseMarket> ) -> Result<()> {
+        instructions::close_market::handler(ctx )?;
         Ok(())
     }
 }
@@ -0,0 +1,74 @@
+//! Addresses of every account the program derives, for clients and for
+//! programs composing with Eventum over CPI. Each helper returns the
+//! address and its bump, exactly as the `seeds` constraints expect.
+
+use anchor_lang::prelude::*;
+
+pub const MARKET_SEED: &[u8] = b"Market";
+pub const YES_MINT_SEED: &[u8] = b"yes_mint";
+pub const NO_MINT_SEED: &[u8] = b"no_mint";
+pub const VAULT_SEED: &[u8] = b"market-vault";
+pub const POSITION_SEED: &[u8] = b"position";
+pub const METADATA_SEED: &[u8] = b"metadata";
+pub const CREATOR_PROFILE_SEED: &[u8] = b"creator";
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const APPROVAL_SEED: &[u8] = b"approval";
+pub const REFERRER_SEED: &[u8] = b"referrer";
+pub const PRICE_ORACLE_SEED: &[u8] = b"price-oracle";
+pub const RESOLUTION_VOTES_SEED: &[u8] = b"resolution-votes";
+pub const OPTIMISTIC_RESOLUTION_SEED: &[u8] = b"optimistic-resolution";
+
+pub fn market_address(creator: &Pubkey, unique_market_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MARKET_SEED, creator.as_ref(), &unique_market_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+pub fn yes_mint_address(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[YES_MINT_SEED, market.as_ref()], &crate::ID)
+}
+
+pub fn no_mint_address(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NO_MINT_SEED, market.as_ref()], &crate::ID)
+}
+
+pub fn vault_address(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, market.as_ref()], &crate::ID)
+}
+
+pub fn position_address(market: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POSITION_SEED, market.as_ref(), owner.as_ref()], &crate::ID)
+}
+
+pub fn metadata_address(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[METADATA_SEED, market.as_ref()], &crate::ID)
+}
+
+pub fn creator_profile_address(creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CREATOR_PROFILE_SEED, creator.as_ref()], &crate::ID)
+}
+
+pub fn config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], &crate::ID)
+}
+
+pub fn approval_address(creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[APPROVAL_SEED, creator.as_ref()], &crate::ID)
+}
+
+pub fn referrer_address(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REFERRER_SEED, authority.as_ref()], &crate::ID)
+}
+
+pub fn price_oracle_address(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PRICE_ORACLE_SEED, market.as_ref()], &crate::ID)
+}
+
+pub fn resolution_votes_address(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RESOLUTION_VOTES_SEED, market.as_ref()], &crate::ID)
+}
+
+pub fn optimistic_resolution_address(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[OPTIMISTIC_RESOLUTION_SEED, market.as_ref()], &crate::ID)
+}
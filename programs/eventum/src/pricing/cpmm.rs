@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use rust_decimal::Decimal;
+
+use super::PricingEngine;
+use crate::states::Market;
+use crate::error::ErrorCode;
+
+/// Fixed-product market maker in the style of Gnosis' FPMM. The pool holds
+/// virtual YES/NO reserves (in raw token units, one lamport of collateral
+/// per complete set). A buy mints complete sets with the lamports paid,
+/// adds them to both reserves, and sends the bought side out of the pool
+/// so that `yes_reserve * no_reserve` stays constant.
+pub struct Cpmm {
+    pub yes_reserve: u64,
+    pub no_reserve: u64,
+}
+
+impl PricingEngine for Cpmm {
+    /// Solves `(a + x - n)(c + x) = a·c` for the collateral `x`, where `a`
+    /// is the bought side's reserve and `c` the other one:
+    /// `x = (-(a + c - n) + sqrt((a + c - n)^2 + 4cn)) / 2`, rounded up.
+    fn buy_cost(&self, yes: bool, tokens: u64) -> Result<u64> {
+        let (a, c) = if yes {
+            (self.yes_reserve as i128, self.no_reserve as i128)
+        } else {
+            (self.no_reserve as i128, self.yes_reserve as i128)
+        };
+        require!(a > 0 && c > 0, ErrorCode::NoLiquidity);
+        let n = tokens as i128;
+
+        let s = a + c - n;
+        let disc = s
+            .checked_mul(s)
+            .and_then(|sq| c.checked_mul(n)?.checked_mul(4)?.checked_add(sq))
+            .ok_or(ErrorCode::MathOverflow)?;
+        let root = isqrt_ceil(disc as u128) as i128;
+        let x = (root - s + 1) / 2;
+        u64::try_from(x.max(0)).map_err(|_| ErrorCode::MathOverflow.into())
+    }
+
+    fn price_yes(&self) -> Result<Decimal> {
+        let total = (self.yes_reserve as u128)
+            .checked_add(self.no_reserve as u128)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(total > 0, ErrorCode::NoLiquidity);
+        Ok(Decimal::from(self.no_reserve) / Decimal::from(total))
+    }
+
+    fn price_yes_after_buy(&self, yes: bool, tokens: u64, cost: u64) -> Result<Decimal> {
+        self.after_buy(yes, tokens, cost)?.price_yes()
+    }
+
+    fn apply_buy(&self, market: &mut Market, yes: bool, tokens: u64, cost: u64) -> Result<()> {
+        let after = self.after_buy(yes, tokens, cost)?;
+        market.yes_reserve = after.yes_reserve;
+        market.no_reserve = after.no_reserve;
+        Ok(())
+    }
+}
+
+impl Cpmm {
+    /// The pool once `cost` lamports of complete sets went in and `tokens`
+    /// of the bought side came out.
+    fn after_buy(&self, yes: bool, tokens: u64, cost: u64) -> Result<Cpmm> {
+        let add = |reserve: u64| reserve.checked_add(cost).ok_or(ErrorCode::MathOverflow);
+        let (bought, other) = if yes {
+            (self.yes_reserve, self.no_reserve)
+        } else {
+            (self.no_reserve, self.yes_reserve)
+        };
+        let bought = add(bought)?
+            .checked_sub(tokens)
+            .ok_or(ErrorCode::InsufficientLiquidity)?;
+        let other = add(other)?;
+        Ok(if yes {
+            Cpmm { yes_reserve: bought, no_reserve: other }
+        } else {
+            Cpmm { yes_reserve: other, no_reserve: bought }
+        })
+    }
+}
+
+/// Smallest `r` with `r * r >= n`.
+fn isqrt_ceil(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method from above converges to floor(sqrt(n))
+    let mut x = n;
+    let mut y = n / 2 + 1;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    if x * x < n { x + 1 } else { x }
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
+use rust_decimal::{Decimal, MathematicalOps};
+
+use super::{after_buy, cost_diff, exp_neg, PricingEngine};
+use crate::error::ErrorCode;
+
+/// Hanson's LMSR with a fixed liquidity parameter `b`.
+pub struct Lmsr {
+    pub b: u64,
+    pub yes: u64,
+    pub no: u64,
+    pub decimals: u8,
+}
+
+impl PricingEngine for Lmsr {
+    fn buy_cost(&self, yes: bool, tokens: u64) -> Result<u64> {
+        let before = calculate_lmsr(self.b, self.yes, self.no, self.decimals)?;
+        let (after_yes, after_no) = after_buy(self.yes, self.no, yes, tokens)?;
+        let after = calculate_lmsr(self.b, after_yes, after_no, self.decimals)?;
+        cost_diff(before, after)
+    }
+
+    fn price_yes(&self) -> Result<Decimal> {
+        lmsr_price_yes(self.b, self.yes, self.no, self.decimals)
+    }
+
+    fn price_yes_after_buy(&self, yes: bool, tokens: u64, _cost: u64) -> Result<Decimal> {
+        let (after_yes, after_no) = after_buy(self.yes, self.no, yes, tokens)?;
+        lmsr_price_yes(self.b, after_yes, after_no, self.decimals)
+    }
+}
+
+pub fn calculate_lmsr(
+    b: u64,
+    yes: u64,
+    no: u64,
+    decimals: u8
+) -> Result<Decimal> {
+    let decimal_factor = 10u64.pow(decimals as u32);
+    
+    let yes_whole = Decimal::from(yes) / Decimal::from(decimal_factor);
+    let no_whole = Decimal::from(no) / Decimal::from(decimal_factor);
+    let b_dec = Decimal::from(b);
+
+    // ln(e^x + e^y) = max + ln(1 + e^(min - max)) keeps exp in range for
+    // any b the creator picks
+    let (high, low) = if yes_whole >= no_whole { (yes_whole, no_whole) } else { (no_whole, yes_whole) };
+    let tail = exp_neg((high - low) / b_dec)?;
+    let log = (Decimal::ONE + tail)
+        .checked_ln()
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_add(high / b_dec)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let cost_whole = b_dec.checked_mul(log)
+        .ok_or(ErrorCode::MathOverflow)?;    // here the cost is in sols , we have to cinvert it indo lamports 
+    
+    let cost_lamports = cost_whole.checked_mul(Decimal::from(LAMPORTS_PER_SOL))
+        .ok_or(ErrorCode::MathOverflow)?;
+    
+    Ok(cost_lamports)
+}
+
+/// Marginal YES price (implied probability) under the LMSR.
+pub fn lmsr_price_yes(
+    b: u64,
+    yes: u64,
+    no: u64,
+    decimals: u8
+) -> Result<Decimal> {
+    let decimal_factor = Decimal::from(10u64.pow(decimals as u32));
+    let b_dec = Decimal::from(b);
+    // p_high = 1 / (1 + e^(-|q_yes - q_no| / b)), which stays in range for
+    // large q and small b
+    let diff = (Decimal::from(yes) - Decimal::from(no)) / decimal_factor / b_dec;
+    let p_high = Decimal::ONE / (Decimal::ONE + exp_neg(diff.abs())?);
+    Ok(if diff >= Decimal::ZERO { p_high } else { Decimal::ONE - p_high })
+}
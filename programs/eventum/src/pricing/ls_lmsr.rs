@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
+use rust_decimal::{Decimal, MathematicalOps};
+
+use super::{after_buy, cost_diff, PricingEngine};
+use crate::error::ErrorCode;
+
+/// Liquidity-sensitive LMSR (Othman, Pennock, Reeves & Sandholm, 2013).
+/// `b(q) = α · (q_yes + q_no)`, so the book deepens as volume grows.
+/// `alpha_bps` is α in bps; the resulting prices sum to slightly more
+/// than 1, which is the maker's built-in vig. `min_b` floors `b(q)`, in
+/// whole tokens, so a market with no volume yet still has a finite price.
+#[derive(Clone, Copy)]
+pub struct LsLmsr {
+    pub alpha_bps: u16,
+    pub min_b: u64,
+    pub yes: u64,
+    pub no: u64,
+    pub decimals: u8,
+}
+
+impl PricingEngine for LsLmsr {
+    fn buy_cost(&self, yes: bool, tokens: u64) -> Result<u64> {
+        let before = calculate_ls_lmsr(self.alpha_bps, self.min_b, self.yes, self.no, self.decimals)?;
+        let (after_yes, after_no) = after_buy(self.yes, self.no, yes, tokens)?;
+        let after = calculate_ls_lmsr(self.alpha_bps, self.min_b, after_yes, after_no, self.decimals)?;
+        cost_diff(before, after)
+    }
+
+    fn price_yes(&self) -> Result<Decimal> {
+        let (q_yes, q_no) = whole(self.yes, self.no, self.decimals);
+        let b = liquidity(self.alpha_bps, self.min_b, q_yes, q_no);
+        // softmax at the current depth, normalised so YES + NO = 1
+        let tail = exp_neg((q_yes - q_no).abs() / b)?;
+        let p_high = Decimal::ONE / (Decimal::ONE + tail);
+        Ok(if q_yes >= q_no { p_high } else { Decimal::ONE - p_high })
+    }
+
+    fn price_yes_after_buy(&self, yes: bool, tokens: u64, _cost: u64) -> Result<Decimal> {
+        let (after_yes, after_no) = after_buy(self.yes, self.no, yes, tokens)?;
+        LsLmsr { yes: after_yes, no: after_no, ..*self }.price_yes()
+    }
+}
+
+/// `C(q) = b(q) · ln(e^(q_yes / b(q)) + e^(q_no / b(q)))` in lamports.
+pub fn calculate_ls_lmsr(alpha_bps: u16, min_b: u64, yes: u64, no: u64, decimals: u8) -> Result<Decimal> {
+    let (q_yes, q_no) = whole(yes, no, decimals);
+    let b = liquidity(alpha_bps, min_b, q_yes, q_no);
+
+    // ln(e^x + e^y) = max + ln(1 + e^(min - max)) keeps exp in range
+    let (high, low) = if q_yes >= q_no { (q_yes, q_no) } else { (q_no, q_yes) };
+    let tail = exp_neg((high - low) / b)?;
+    let log = (Decimal::ONE + tail)
+        .checked_ln()
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_add(high / b)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    b.checked_mul(log)
+        .and_then(|cost| cost.checked_mul(Decimal::from(LAMPORTS_PER_SOL)))
+        .ok_or(ErrorCode::MathOverflow.into())
+}
+
+fn whole(yes: u64, no: u64, decimals: u8) -> (Decimal, Decimal) {
+    let factor = Decimal::from(10u64.pow(decimals as u32));
+    (Decimal::from(yes) / factor, Decimal::from(no) / factor)
+}
+
+fn liquidity(alpha_bps: u16, min_b: u64, q_yes: Decimal, q_no: Decimal) -> Decimal {
+    let b = Decimal::from(alpha_bps) * (q_yes + q_no) / Decimal::from(10000);
+    b.max(Decimal::from(min_b))
+}
+
+/// `e^(-x)` for `x >= 0`, flushed to zero once it is below Decimal's precision.
+pub(crate) fn exp_neg(x: Decimal) -> Result<Decimal> {
+    if x > Decimal::from(60) {
+        return Ok(Decimal::ZERO);
+    }
+    let exp = x.checked_exp().ok_or(ErrorCode::MathOverflow)?;
+    Ok(Decimal::ONE / exp)
+}
@@ -0,0 +1,89 @@
+//! Market makers the trade path can price against. Each market picks one
+//! at creation through `Market.pricing_mode`; `buy_outcomes` and
+//! `batch_trade` only ever talk to the `PricingEngine` trait.
+
+use anchor_lang::prelude::*;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::states::{Market, PricingMode, PRICE_SCALE};
+use crate::error::ErrorCode;
+
+pub mod lmsr;
+pub use lmsr::*;
+
+pub mod ls_lmsr;
+pub use ls_lmsr::*;
+
+pub mod cpmm;
+pub use cpmm::*;
+
+pub trait PricingEngine {
+    /// Lamports, before fees, to buy `tokens` raw units of one side.
+    fn buy_cost(&self, yes: bool, tokens: u64) -> Result<u64>;
+
+    /// Implied probability of YES, in `[0, 1]`.
+    fn price_yes(&self) -> Result<Decimal>;
+
+    /// Implied probability of YES once a buy of `tokens` for `cost`
+    /// lamports (fees excluded) has been filled.
+    fn price_yes_after_buy(&self, yes: bool, tokens: u64, cost: u64) -> Result<Decimal>;
+
+    /// Records a filled buy of `tokens` for `cost` lamports (fees excluded)
+    /// on engines that keep their own state in `Market`. Cost-function
+    /// makers read everything from the mint supplies and need nothing here.
+    fn apply_buy(&self, _market: &mut Market, _yes: bool, _tokens: u64, _cost: u64) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Builds the engine for `market` at the given raw outcome supplies.
+pub fn engine_for(market: &Market, yes: u64, no: u64, decimals: u8) -> Box<dyn PricingEngine> {
+    match market.pricing_mode {
+        PricingMode::Lmsr { b } => Box::new(Lmsr { b, yes, no, decimals }),
+        PricingMode::LsLmsr { alpha_bps, min_b } => Box::new(LsLmsr { alpha_bps, min_b, yes, no, decimals }),
+        PricingMode::Cpmm { .. } => Box::new(Cpmm {
+            yes_reserve: market.yes_reserve,
+            no_reserve: market.no_reserve,
+        }),
+    }
+}
+
+/// Lamports the creator deposits in the vault at creation so that it can
+/// pay every winner whatever is bought later: the maker's worst-case loss.
+/// The cost-function makers start at `C(0, 0) = b · ln 2` rather than 0
+/// (`min_b · ln 2` for LS-LMSR), so the creator picks the subsidy by
+/// picking `b`; CPMM pools are backed by the seeded complete sets.
+pub fn creation_subsidy(pricing_mode: PricingMode, decimals: u8) -> Result<u64> {
+    let cost = match pricing_mode {
+        PricingMode::Lmsr { b } => calculate_lmsr(b, 0, 0, decimals)?,
+        PricingMode::LsLmsr { alpha_bps, min_b } => calculate_ls_lmsr(alpha_bps, min_b, 0, 0, decimals)?,
+        PricingMode::Cpmm { seed_liquidity } => return Ok(seed_liquidity),
+    };
+    cost.ceil().to_u64().ok_or(ErrorCode::MathOverflow.into())
+}
+
+/// Supplies after buying `tokens` more of one side.
+pub(crate) fn after_buy(yes: u64, no: u64, side_yes: bool, tokens: u64) -> Result<(u64, u64)> {
+    let after = if side_yes {
+        (yes.checked_add(tokens).ok_or(ErrorCode::MathOverflow)?, no)
+    } else {
+        (yes, no.checked_add(tokens).ok_or(ErrorCode::MathOverflow)?)
+    };
+    Ok(after)
+}
+
+/// `after - before` of a cost function, rounded up to whole lamports so
+/// rounding never leaves the vault short.
+pub(crate) fn cost_diff(before: Decimal, after: Decimal) -> Result<u64> {
+    let diff = after.checked_sub(before).ok_or(ErrorCode::MathOverflow)?;
+    Ok(diff.ceil().to_u64().ok_or(ErrorCode::MathOverflow)?)
+}
+
+/// A probability as a `PRICE_SCALE` fixed-point integer, for the oracle.
+pub fn scaled_price(p: Decimal) -> Result<u64> {
+    (p * Decimal::from(PRICE_SCALE))
+        .floor()
+        .to_u64()
+        .ok_or(ErrorCode::MathOverflow.into())
+}
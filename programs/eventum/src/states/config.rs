@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+/// Program-wide settings, a single PDA owned by the admin.
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub permissioned: bool,   // when set, only approved creators can create markets
+    pub bump: u8,
+}
+
+/// Issued by the admin to a creator; closing it revokes the approval.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorApproval {
+    pub creator: Pubkey,
+    pub approved_by: Pubkey,
+    pub approved_at: i64,
+    pub bump: u8,
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+/// One per creator. `market_count` is the id the creator's next market
+/// must use, so every market they made is `0..market_count`.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorProfile {
+    pub creator: Pubkey,
+    pub market_count: u64,
+    pub bump: u8,
+}
@@ -2,6 +2,53 @@ use crate::InitSpace;
 
 use anchor_lang::prelude::*;
 
+/// An outcome of a binary market.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum Side {
+    Yes,
+    No,
+}
+
+impl Side {
+    pub fn is_yes(self) -> bool {
+        self == Side::Yes
+    }
+}
+
+/// How `Market.fee` (bps) is turned into the fee charged on a trade.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum FeeModel {
+    /// `fee` on every trade.
+    Flat,
+    /// `fee * 4p(1-p)` for YES price `p`: the full fee at 50/50, falling
+    /// towards zero as the market approaches certainty.
+    PriceDependent,
+    /// Ramps linearly from `fee` at `start_time` to `2 * fee` at
+    /// `end_time`, so late informed traders pay more.
+    TimeIncreasing,
+}
+
+/// Which market maker prices trades; see `crate::pricing`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PricingMode {
+    /// LMSR with a fixed liquidity parameter of `b` whole tokens.
+    Lmsr { b: u64 },
+    /// Liquidity-sensitive LMSR with `b = α · (q_yes + q_no)`, never below
+    /// `min_b` whole tokens.
+    LsLmsr { alpha_bps: u16, min_b: u64 },
+    /// Constant-product pool over `yes_reserve`/`no_reserve`, seeded by
+    /// the creator with `seed_liquidity` lamports of complete sets.
+    Cpmm { seed_liquidity: u64 },
+}
+
+/// Per-trade guards checked before a buy is filled. Zero disables a limit.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug, InitSpace)]
+pub struct TradeLimits {
+    pub min_tokens: u64,           // raw token units
+    pub max_tokens: u64,           // raw token units
+    pub max_price_move_bps: u16,   // largest move in the YES price one trade may cause
+}
+
 #[account]
 #[derive(InitSpace)]
 
@@ -24,4 +71,13 @@ pub struct Market{
     pub yes_tokens : u64 ,
     pub no_tokens : u64 ,
     pub bump : u8 ,
+    pub approved_creator : bool ,
+    pub fee_model : FeeModel ,
+    pub referral_share_bps : u16 ,   // share of the fee paid to a referrer, if one is passed
+    pub pricing_mode : PricingMode ,
+    pub yes_reserve : u64 ,   // CPMM pool reserves, raw token units
+    pub no_reserve : u64 ,
+    pub limits : TradeLimits ,
+    pub price_oracle : Pubkey ,   // default until `init_price_oracle`, then required on every trade
+    pub fees_collected : u64 ,   // trading fees kept by the vault, net of referral shares
 }
\ No newline at end of file
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_METADATA_QUESTION_LEN: usize = 512;
+pub const MAX_RESOLUTION_RULES_LEN: usize = 1024;
+pub const MAX_TAGS: usize = 5;
+pub const MAX_TAG_LEN: usize = 32;
+pub const MAX_URI_LEN: usize = 200;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum MarketCategory {
+    Sports,
+    Politics,
+    Crypto,
+    Finance,
+    Entertainment,
+    Science,
+    Other,
+}
+
+/// Long-form description of a market, kept out of `Market` so the hot
+/// trading account stays small. Written once by the creator and never
+/// updated, so the resolution rules traders saw cannot change under them.
+#[account]
+#[derive(InitSpace)]
+pub struct MarketMetadata {
+    pub market: Pubkey,
+    #[max_len(512)]
+    pub question: String,
+    #[max_len(1024)]
+    pub resolution_rules: String,
+    pub category: MarketCategory,
+    #[max_len(5, 32)]
+    pub tags: Vec<String>,
+    #[max_len(200)]
+    pub uri: String,
+    pub content_hash: [u8; 32],   // hash of the document behind `uri`
+    pub bump: u8,
+}
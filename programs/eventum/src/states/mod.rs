@@ -1,2 +1,18 @@
 pub mod market;
 pub use market::*;
+pub mod position;
+pub use position::*;
+pub mod metadata;
+pub use metadata::*;
+pub mod creator_profile;
+pub use creator_profile::*;
+pub mod config;
+pub use config::*;
+pub mod referrer;
+pub use referrer::*;
+pub mod price_oracle;
+pub use price_oracle::*;
+pub mod resolution_votes;
+pub use resolution_votes::*;
+pub mod optimistic_resolution;
+pub use optimistic_resolution::*;
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::states::Side;
+
+/// Resolution without a designated oracle. After `end_time` anyone may
+/// propose an outcome by bonding `bond` lamports; if nobody challenges it
+/// (with an equal bond) within `liveness` seconds it becomes final and the
+/// proposer gets the bond back plus `reward_bps` of the market's fees. A
+/// challenge hands the decision to `arbiter`, who has `arbitration_period`
+/// seconds to make it; after that the proposal stands and both bonds are
+/// refunded. The arbiter may not propose or challenge. The market's
+/// `oracle_authority` is this account's address, so `resolve_market`
+/// cannot be used. Bonds are held in this account until paid out.
+#[account]
+#[derive(InitSpace)]
+pub struct OptimisticResolution {
+    pub market: Pubkey,
+    pub arbiter: Pubkey,
+    pub bond: u64,           // lamports, posted by the proposer and again by a challenger
+    pub liveness: i64,       // seconds a proposal stays open to challenge
+    pub arbitration_period: i64, // seconds the arbiter has to decide a challenge
+    pub reward_bps: u16,     // share of `Market.fees_collected` paid to an unchallenged proposer
+    pub proposal: Option<Side>,
+    pub proposer: Pubkey,
+    pub proposed_at: i64,
+    pub challenger: Option<Pubkey>,
+    pub arbitration_deadline: i64, // set when the proposal is challenged
+    pub bump: u8,
+}
+
+impl OptimisticResolution {
+    /// Whether a proposal made at `proposed_at` can still be challenged at `now`.
+    pub fn in_liveness(&self, now: i64) -> bool {
+        now < self.proposed_at.saturating_add(self.liveness)
+    }
+}
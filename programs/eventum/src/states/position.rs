@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+/// Per-user bookkeeping for one market, seeded by `(market, owner)`.
+/// Opened with `open_position` and then updated by every trade and claim
+/// that passes it in, so the UI can show cost basis and PnL without
+/// replaying transaction history.
+#[account]
+#[derive(InitSpace)]
+pub struct Position {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub yes_quantity: u64,
+    pub no_quantity: u64,
+    pub yes_cost_basis: u64,   // lamports paid for the YES still held, fees included
+    pub no_cost_basis: u64,
+    pub lamports_spent: u64,   // lifetime total, fees included
+    pub fees_paid: u64,
+    pub realized_pnl: i64,
+    pub bump: u8,
+}
+
+impl Position {
+    pub fn record_buy(&mut self, yes: bool, quantity: u64, paid: u64, fee: u64) -> Result<()> {
+        let (held, cost) = if yes {
+            (&mut self.yes_quantity, &mut self.yes_cost_basis)
+        } else {
+            (&mut self.no_quantity, &mut self.no_cost_basis)
+        };
+        *held = held.checked_add(quantity).ok_or(ErrorCode::MathOverflow)?;
+        *cost = cost.checked_add(paid).ok_or(ErrorCode::MathOverflow)?;
+        self.lamports_spent = self.lamports_spent.checked_add(paid).ok_or(ErrorCode::MathOverflow)?;
+        self.fees_paid = self.fees_paid.checked_add(fee).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Settles the whole position against `payout`: the winning side is
+    /// redeemed, the losing side expires worthless, and both cost bases
+    /// are realized.
+    pub fn record_claim(&mut self, payout: u64) -> Result<()> {
+        let cost = self.yes_cost_basis
+            .checked_add(self.no_cost_basis)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let pnl = (payout as i128)
+            .checked_sub(cost as i128)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let pnl = i64::try_from(pnl).map_err(|_| ErrorCode::MathOverflow)?;
+        self.realized_pnl = self.realized_pnl.checked_add(pnl).ok_or(ErrorCode::MathOverflow)?;
+        self.yes_quantity = 0;
+        self.no_quantity = 0;
+        self.yes_cost_basis = 0;
+        self.no_cost_basis = 0;
+        Ok(())
+    }
+
+    /// Settles `redeemed` of the winning side against `payout`, realizing
+    /// its cost pro rata and keeping the rest open for a later claim. The
+    /// losing side expires worthless, so its cost basis is realized in full.
+    pub fn record_partial_claim(&mut self, yes: bool, redeemed: u64, payout: u64) -> Result<()> {
+        let (held, cost, lost_held, lost_cost) = if yes {
+            (&mut self.yes_quantity, &mut self.yes_cost_basis, &mut self.no_quantity, &mut self.no_cost_basis)
+        } else {
+            (&mut self.no_quantity, &mut self.no_cost_basis, &mut self.yes_quantity, &mut self.yes_cost_basis)
+        };
+        let settled = redeemed.min(*held);
+        let realized = if settled == *held {
+            *cost
+        } else {
+            ((*cost as u128) * (settled as u128) / (*held as u128)) as u64
+        };
+        let pnl = (payout as i128) - (realized as i128) - (*lost_cost as i128);
+        let pnl = i64::try_from(pnl).map_err(|_| ErrorCode::MathOverflow)?;
+        self.realized_pnl = self.realized_pnl.checked_add(pnl).ok_or(ErrorCode::MathOverflow)?;
+        *held -= settled;
+        *cost -= realized;
+        *lost_held = 0;
+        *lost_cost = 0;
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+/// Fixed-point scale of oracle prices: `PRICE_SCALE` means a YES price of 1.
+pub const PRICE_SCALE: u64 = 1_000_000_000;
+pub const MAX_OBSERVATIONS: usize = 32;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct Observation {
+    pub timestamp: i64,
+    pub cumulative: u128,   // price × seconds accumulated up to `timestamp`
+}
+
+/// Uniswap-v2 style accumulator for a market's YES price. Every trade
+/// adds `last_price × elapsed` before moving the price, and snapshots the
+/// running sum into a ring buffer so averages over past windows can be
+/// read back without trusting any single trade.
+#[account]
+#[derive(InitSpace)]
+pub struct PriceOracle {
+    pub market: Pubkey,
+    pub last_price: u64,   // scaled by PRICE_SCALE
+    pub last_update: i64,
+    pub cumulative: u128,
+    pub head: u16,         // index of the newest observation
+    pub len: u16,
+    pub observations: [Observation; MAX_OBSERVATIONS],
+    pub bump: u8,
+}
+
+impl PriceOracle {
+    /// Accumulates the old price up to `now`, then switches to `new_price`.
+    pub fn record(&mut self, now: i64, new_price: u64) -> Result<()> {
+        if now > self.last_update {
+            let elapsed = (now - self.last_update) as u128;
+            self.cumulative = self
+                .cumulative
+                .checked_add((self.last_price as u128).checked_mul(elapsed).ok_or(ErrorCode::MathOverflow)?)
+                .ok_or(ErrorCode::MathOverflow)?;
+            self.last_update = now;
+
+            self.head = ((self.head as usize + 1) % MAX_OBSERVATIONS) as u16;
+            self.len = (self.len + 1).min(MAX_OBSERVATIONS as u16);
+            self.observations[self.head as usize] = Observation {
+                timestamp: now,
+                cumulative: self.cumulative,
+            };
+        }
+        self.last_price = new_price;
+        Ok(())
+    }
+
+    /// Average YES price (scaled by `PRICE_SCALE`) over `[now - window, now]`.
+    pub fn twap(&self, now: i64, window: i64) -> Result<u64> {
+        require!(window > 0 && now >= self.last_update, ErrorCode::InvalidAmount);
+        let start = now.checked_sub(window).ok_or(ErrorCode::MathOverflow)?;
+        let diff = self
+            .cumulative_at(now)?
+            .checked_sub(self.cumulative_at(start)?)
+            .ok_or(ErrorCode::InvalidCalculation)?;
+        Ok((diff / window as u128) as u64)
+    }
+
+    /// Accumulator value at `t`, interpolated between observations (the
+    /// price is constant between two of them).
+    fn cumulative_at(&self, t: i64) -> Result<u128> {
+        let extend = |from: &Observation, price: u128| -> Result<u128> {
+            let elapsed = (t - from.timestamp) as u128;
+            price
+                .checked_mul(elapsed)
+                .and_then(|p| p.checked_add(from.cumulative))
+                .ok_or(ErrorCode::MathOverflow.into())
+        };
+
+        let newest = self.observations[self.head as usize];
+        if t >= newest.timestamp {
+            return extend(&newest, self.last_price as u128);
+        }
+        // walk back from the newest until we straddle `t`
+        let mut later = newest;
+        for i in 1..self.len as usize {
+            let idx = (self.head as usize + MAX_OBSERVATIONS - i) % MAX_OBSERVATIONS;
+            let earlier = self.observations[idx];
+            if earlier.timestamp <= t {
+                let span = (later.timestamp - earlier.timestamp) as u128;
+                let price = (later.cumulative - earlier.cumulative) / span;
+                return extend(&earlier, price);
+            }
+            later = earlier;
+        }
+        err!(ErrorCode::TwapWindowUnavailable)
+    }
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// A partner front-end's fee account. Its share of trading fees is paid
+/// straight into this PDA and withdrawn with `claim_referral_fees`.
+#[account]
+#[derive(InitSpace)]
+pub struct Referrer {
+    pub authority: Pubkey,
+    pub pending: u64,        // lamports accrued but not yet withdrawn
+    pub total_earned: u64,
+    pub bump: u8,
+}
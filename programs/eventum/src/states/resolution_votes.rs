@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::states::Side;
+
+pub const MAX_RESOLVERS: usize = 10;
+
+/// An M-of-N committee standing in for a market's single oracle. The
+/// market's `oracle_authority` is set to this account's address, so no key
+/// can call `resolve_market`; each resolver calls `vote_resolution` instead
+/// and the market resolves once `threshold` of them agree.
+#[account]
+#[derive(InitSpace)]
+pub struct ResolutionVotes {
+    pub market: Pubkey,
+    pub threshold: u8,
+    #[max_len(MAX_RESOLVERS)]
+    pub resolvers: Vec<Pubkey>,
+    #[max_len(MAX_RESOLVERS)]
+    pub votes: Vec<Option<Side>>,   // parallel to `resolvers`; votes are final
+    pub bump: u8,
+}
+
+impl ResolutionVotes {
+    pub fn tally(&self, outcome: Side) -> u8 {
+        self.votes.iter().filter(|vote| **vote == Some(outcome)).count() as u8
+    }
+}
@@ -0,0 +1,198 @@
+//! Fixtures shared by the integration tests: a runtime with the program
+//! deployed, funded wallets, and shortcuts for the usual market set-up.
+//! `Env` keeps every wallet's keypair and signs each transaction with the
+//! ones listed, so tests name signers by pubkey.
+
+#![allow(dead_code, unused_imports)]
+
+pub mod svm;
+
+use std::collections::HashMap;
+
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{Mint, TokenAccount};
+use eventum::error::ErrorCode;
+use eventum::states::CreatorProfile;
+use eventum_client::instructions::{self, BuyOptions};
+use solana_sdk::hash::Hash;
+use solana_sdk::message::Message;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+use eventum_client::{
+    pda, BuyOutcomesArgs, FeeModel, InitializeMarketArgs, Market, MarketState, PricingMode, Quote,
+    Side, TradeLimits,
+};
+
+pub use svm::{Svm, TxError, GENESIS_TIME, LAMPORTS_PER_SOL};
+
+pub const ONE_TOKEN: u64 = 1_000_000_000;
+pub const DAY: i64 = 24 * 60 * 60;
+/// Rent-exempt minimum of a data-less account, which the creator deposits
+/// in the vault on top of the subsidy.
+pub const VAULT_RENT: u64 = 890_880;
+
+pub struct Env {
+    pub svm: Svm,
+    /// Upgrade authority of the program, so the only key that may
+    /// `initialize_config`. Pays for transactions nobody signs.
+    pub admin: Pubkey,
+    keypairs: HashMap<Pubkey, Keypair>,
+}
+
+impl Env {
+    /// The program deployed with a fresh admin, and an open (not
+    /// permissioned) config.
+    pub fn new() -> Self {
+        let mut env = Self::without_config();
+        let admin = env.admin;
+        env.send(instructions::initialize_config(&admin, false), &[&admin])
+            .expect("initialize_config");
+        env
+    }
+
+    /// The program deployed, before `initialize_config`.
+    pub fn without_config() -> Self {
+        let admin = Keypair::new();
+        let mut svm = Svm::new(eventum::ID, eventum::entry, &admin.pubkey());
+        svm.airdrop(&admin.pubkey(), 100 * LAMPORTS_PER_SOL);
+        Self { svm, admin: admin.pubkey(), keypairs: HashMap::from([(admin.pubkey(), admin)]) }
+    }
+
+    /// A new wallet holding `sol` SOL.
+    pub fn wallet(&mut self, sol: u64) -> Pubkey {
+        let keypair = Keypair::new();
+        let key = keypair.pubkey();
+        self.svm.airdrop(&key, sol * LAMPORTS_PER_SOL);
+        self.keypairs.insert(key, keypair);
+        key
+    }
+
+    /// A wallet that can subsidise a few default markets (an LMSR market
+    /// costs the creator `b · ln 2`, about 693 SOL at `b` = 1000).
+    pub fn creator(&mut self) -> Pubkey {
+        self.wallet(5_000)
+    }
+
+    /// `ix` as a transaction paid for by the first of `signers` (the admin
+    /// if there are none) and signed by each of them.
+    pub fn transaction(&self, ix: Instruction, signers: &[&Pubkey]) -> Transaction {
+        let payer = signers.first().copied().unwrap_or(&self.admin);
+        let keypair = |key: &Pubkey| self.keypairs.get(key).unwrap_or_else(|| panic!("no keypair for {key}"));
+        let mut keypairs: Vec<&Keypair> = signers.iter().map(|key| keypair(key)).collect();
+        if signers.is_empty() {
+            keypairs.push(keypair(payer));
+        }
+        let mut transaction = Transaction::new_unsigned(Message::new(&[ix], Some(payer)));
+        transaction.try_partial_sign(&keypairs, Hash::default()).expect("signers");
+        transaction
+    }
+
+    pub fn send(&mut self, ix: Instruction, signers: &[&Pubkey]) -> Result<(), TxError> {
+        let transaction = self.transaction(ix, signers);
+        self.svm.process(&transaction)
+    }
+
+    pub fn now(&self) -> i64 {
+        self.svm.now()
+    }
+
+    pub fn market(&self, address: &Pubkey) -> Market {
+        self.svm.account(address)
+    }
+
+    /// The market with its current mint supplies, for client-side quotes.
+    pub fn state(&self, address: &Pubkey) -> MarketState {
+        let market = self.market(address);
+        let yes: Mint = self.svm.account(&market.yes_mint);
+        let no: Mint = self.svm.account(&market.no_mint);
+        MarketState {
+            address: *address,
+            market,
+            yes_supply: yes.supply,
+            no_supply: no.supply,
+            decimals: yes.decimals,
+        }
+    }
+
+    pub fn quote(&self, address: &Pubkey, side: Side, tokens: u64) -> Quote {
+        self.state(address)
+            .quote_buy(side, tokens, self.now())
+            .expect("quote")
+    }
+
+    /// Raw balance of `owner`'s ATA for `mint`, 0 if it does not exist.
+    pub fn token_balance(&self, owner: &Pubkey, mint: &Pubkey) -> u64 {
+        let ata = get_associated_token_address(owner, mint);
+        match self.svm.get_account(&ata) {
+            Some(_) => self.svm.account::<TokenAccount>(&ata).amount,
+            None => 0,
+        }
+    }
+
+    pub fn vault_lamports(&self, market: &Pubkey) -> u64 {
+        self.svm.lamports(&pda::vault_address(market).0)
+    }
+
+    /// `creator`'s next market: LMSR with `b` = 1000, flat 1% fee, closing
+    /// in a day.
+    pub fn market_args(&self, creator: &Pubkey) -> InitializeMarketArgs {
+        let profile = pda::creator_profile_address(creator).0;
+        let unique_market_id = match self.svm.get_account(&profile) {
+            Some(_) => self.svm.account::<CreatorProfile>(&profile).market_count,
+            None => 0,
+        };
+        InitializeMarketArgs {
+            unique_market_id,
+            end_time: self.now() + DAY,
+            fee: 100,
+            fee_model: FeeModel::Flat,
+            referral_share_bps: 0,
+            pricing_mode: PricingMode::Lmsr { b: 1000 },
+            limits: TradeLimits::default(),
+            question: "Will it rain tomorrow?".into(),
+        }
+    }
+
+    /// Creates a market from `args` and returns its address.
+    pub fn create_market_with(&mut self, creator: &Pubkey, args: InitializeMarketArgs) -> Pubkey {
+        let address = pda::market_address(creator, args.unique_market_id).0;
+        self.send(instructions::initialize_market(creator, creator, false, args), &[creator])
+            .expect("initialize_market");
+        address
+    }
+
+    /// Creates a market with the default `market_args`.
+    pub fn create_market(&mut self, creator: &Pubkey) -> Pubkey {
+        let args = self.market_args(creator);
+        self.create_market_with(creator, args)
+    }
+
+    pub fn buy_ix(&self, user: &Pubkey, market: &Pubkey, side: Side, tokens: u64, max_cost: u64) -> Instruction {
+        let args = BuyOutcomesArgs { number_of_tokens: tokens, side, max_cost };
+        instructions::buy_outcomes(user, market, &self.market(market), args, BuyOptions::default())
+    }
+
+    /// Buys at exactly the quoted price and returns the quote.
+    pub fn buy(&mut self, user: &Pubkey, market: &Pubkey, side: Side, tokens: u64) -> Quote {
+        let quote = self.quote(market, side, tokens);
+        let ix = self.buy_ix(user, market, side, tokens, quote.total);
+        self.send(ix, &[user]).expect("buy_outcomes");
+        quote
+    }
+
+    pub fn resolve(&mut self, market: &Pubkey, outcome: Side) {
+        let oracle = self.market(market).oracle_authority;
+        self.send(instructions::resolve_market(&oracle, market, outcome), &[&oracle])
+            .expect("resolve_market");
+    }
+}
+
+/// Asserts that a transaction failed with the program's `code`.
+#[track_caller]
+pub fn assert_error(result: Result<(), TxError>, code: ErrorCode) {
+    let expected = TxError::Program(ProgramError::Custom(u32::from(code)));
+    assert_eq!(result, Err(expected), "expected {code:?}");
+}
@@ -0,0 +1,605 @@
+//! A minimal in-process Solana runtime for the integration tests.
+//!
+//! Instructions run natively: a program's `entry` is called directly with
+//! `AccountInfo`s laid out the way the loader lays them out. CPIs are served
+//! through `program_stubs`, into the system, token and associated-token
+//! programs (the token program by spl-token's own processor) or into any
+//! program added with `Svm::add_program`. The clock is a per-thread value
+//! the tests can warp.
+//!
+//! `process` takes a signed `Transaction`: every required signature is
+//! verified against the message, account privileges come from the message
+//! header, and the instructions run in order with no account changes kept
+//! if any of them fails. No fees are charged. After each instruction the
+//! usual runtime rules are checked (read-only accounts unchanged, lamports
+//! conserved, only owned or system-debited accounts lose lamports,
+//! rent-exempt or empty accounts).
+//!
+//! Return data and Anchor events are not available: off-chain, solana-cpi's
+//! `set_return_data` and anchor-lang's `sol_log_data` are no-ops that never
+//! reach the stubs.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Once;
+
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_lang::solana_program::entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE, SUCCESS};
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::rent::Rent;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::token::spl_token;
+use solana_program::bpf_loader;
+use solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::Transaction;
+
+/// A program's Anchor-generated `entry`.
+pub type Entry = fn(&Pubkey, &'static [AccountInfo<'static>], &[u8]) -> ProgramResult;
+
+const NATIVE_LOADER_ID: Pubkey = Pubkey::from_str_const("NativeLoader1111111111111111111111111111111");
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// Unix time the clock starts at in every test.
+pub const GENESIS_TIME: i64 = 1_700_000_000;
+
+#[derive(Clone, Debug)]
+pub struct StoredAccount {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+/// Why a transaction failed.
+#[derive(Debug, PartialEq)]
+pub enum TxError {
+    /// The instruction (or a CPI it made) returned an error.
+    Program(ProgramError),
+    /// A signer the transaction needs did not sign.
+    MissingSigner(Pubkey),
+    /// A signature does not verify against the message and its signer.
+    InvalidSignature(Pubkey),
+    /// An account was left with a balance below its rent-exempt minimum.
+    InsufficientFundsForRent(Pubkey),
+}
+
+thread_local! {
+    static CLOCK: RefCell<Clock> = RefCell::new(Clock {
+        unix_timestamp: GENESIS_TIME,
+        ..Clock::default()
+    });
+    static PROGRAM_STACK: RefCell<Vec<Pubkey>> = const { RefCell::new(Vec::new()) };
+    // entrypoints of the programs deployed on this thread's runtime
+    static PROGRAMS: RefCell<HashMap<Pubkey, Entry>> = RefCell::new(HashMap::new());
+    // lamports the native programs took from accounts during this transaction
+    static DEBITS: RefCell<HashMap<Pubkey, u64>> = RefCell::new(HashMap::new());
+}
+
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = CLOCK.with(|c| c.borrow().clone());
+        unsafe { *(var_addr as *mut Clock) = clock };
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        PROGRAM_STACK.with(|s| s.borrow().len() as u64)
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        invoke(instruction, account_infos, signers_seeds)
+    }
+}
+
+fn current_program() -> Pubkey {
+    PROGRAM_STACK.with(|s| s.borrow().last().copied().unwrap_or_default())
+}
+
+/// Runs a CPI with the privileges the runtime would grant: callee accounts
+/// share the caller's memory, signers must have signed the transaction or
+/// be PDAs of the caller signed for with `signers_seeds`.
+fn invoke(
+    instruction: &Instruction,
+    caller_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let caller = current_program();
+    let signed: Vec<Pubkey> = signers_seeds
+        .iter()
+        .map(|seeds| Pubkey::create_program_address(seeds, &caller))
+        .collect::<Result<_, _>>()
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+
+    let mut infos = Vec::with_capacity(instruction.accounts.len());
+    for meta in &instruction.accounts {
+        let info = caller_infos
+            .iter()
+            .find(|info| *info.key == meta.pubkey)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if meta.is_writable && !info.is_writable {
+            return Err(ProgramError::InvalidArgument);
+        }
+        if meta.is_signer && !info.is_signer && !signed.contains(&meta.pubkey) {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        let mut info = info.clone();
+        info.is_signer = meta.is_signer;
+        info.is_writable = meta.is_writable;
+        infos.push(info);
+    }
+
+    PROGRAM_STACK.with(|s| s.borrow_mut().push(instruction.program_id));
+    let result = execute(&instruction.program_id, &infos, &instruction.data);
+    PROGRAM_STACK.with(|s| s.borrow_mut().pop());
+    result
+}
+
+fn execute(program_id: &Pubkey, infos: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if *program_id == system_program::ID {
+        system_processor(infos, data)
+    } else if *program_id == spl_token::ID {
+        spl_token::processor::Processor::process(program_id, infos, data)
+    } else if *program_id == associated_token::ID {
+        associated_token_processor(infos, data)
+    } else if let Some(entry) = PROGRAMS.with(|p| p.borrow().get(program_id).copied()) {
+        // every info points into the transaction's arena, which outlives the call
+        let infos = unsafe { std::mem::transmute::<&[AccountInfo], &'static [AccountInfo<'static>]>(infos) };
+        entry(program_id, infos, data)
+    } else {
+        Err(ProgramError::IncorrectProgramId)
+    }
+}
+
+fn debit(info: &AccountInfo, lamports: u64) -> ProgramResult {
+    let balance = info.lamports();
+    if balance < lamports {
+        return Err(ProgramError::InsufficientFunds);
+    }
+    **info.try_borrow_mut_lamports()? = balance - lamports;
+    DEBITS.with(|d| *d.borrow_mut().entry(*info.key).or_default() += lamports);
+    Ok(())
+}
+
+fn credit(info: &AccountInfo, lamports: u64) -> ProgramResult {
+    let balance = info.lamports();
+    **info.try_borrow_mut_lamports()? = balance
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+fn read_u64(data: &[u8], at: usize) -> Result<u64, ProgramError> {
+    data.get(at..at + 8)
+        .and_then(|b| b.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+fn read_pubkey(data: &[u8], at: usize) -> Result<Pubkey, ProgramError> {
+    data.get(at..at + 32)
+        .and_then(|b| <[u8; 32]>::try_from(b).ok())
+        .map(Pubkey::new_from_array)
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+fn allocate(info: &AccountInfo, space: u64, owner: &Pubkey) -> ProgramResult {
+    if !info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !info.data_is_empty() || *info.owner != system_program::ID {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    info.resize(space as usize)?;
+    info.assign(owner);
+    Ok(())
+}
+
+/// The subset of the system program Anchor and the token programs use.
+fn system_processor(infos: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let tag = data
+        .get(..4)
+        .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let account = |i: usize| infos.get(i).ok_or(ProgramError::NotEnoughAccountKeys);
+    match tag {
+        // CreateAccount { lamports, space, owner }
+        0 => {
+            let (from, to) = (account(0)?, account(1)?);
+            if !from.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if to.lamports() > 0 {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            allocate(to, read_u64(data, 12)?, &read_pubkey(data, 20)?)?;
+            let lamports = read_u64(data, 4)?;
+            debit(from, lamports)?;
+            credit(to, lamports)
+        }
+        // Assign { owner }
+        1 => {
+            let info = account(0)?;
+            if !info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            info.assign(&read_pubkey(data, 4)?);
+            Ok(())
+        }
+        // Transfer { lamports }
+        2 => {
+            let (from, to) = (account(0)?, account(1)?);
+            if !from.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if !from.data_is_empty() || *from.owner != system_program::ID {
+                return Err(ProgramError::InvalidArgument);
+            }
+            let lamports = read_u64(data, 4)?;
+            debit(from, lamports)?;
+            credit(to, lamports)
+        }
+        // Allocate { space }
+        8 => allocate(account(0)?, read_u64(data, 4)?, &system_program::ID),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+/// `Create` and `CreateIdempotent` of the associated token account program.
+fn associated_token_processor(infos: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [payer, ata, wallet, mint, ..] = infos else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if *ata.key != get_associated_token_address(wallet.key, mint.key) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let idempotent = data.first() == Some(&1);
+    if idempotent && *ata.owner == spl_token::ID {
+        return Ok(());
+    }
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if ata.lamports() > 0 || *ata.owner != system_program::ID {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    let space = spl_token::state::Account::LEN;
+    let lamports = Rent::default().minimum_balance(space);
+    debit(payer, lamports)?;
+    credit(ata, lamports)?;
+    ata.resize(space)?;
+    ata.assign(&spl_token::ID);
+    let init = spl_token::instruction::initialize_account3(&spl_token::ID, ata.key, mint.key, wallet.key)?;
+    spl_token::processor::Processor::process(&spl_token::ID, &[ata.clone(), mint.clone()], &init.data)
+}
+
+/// Backing memory for one transaction's `AccountInfo`s. The entrypoint
+/// wants `'static` infos, so they are handed out as such; the arena outlives
+/// every use of them and frees the buffers when the transaction is done.
+#[derive(Default)]
+#[allow(clippy::vec_box)] // boxed so handed-out addresses survive the vectors growing
+struct Arena {
+    key_blocks: Vec<Box<[u8]>>,
+    buffers: Vec<Box<[u64]>>,
+    lamports: Vec<Box<u64>>,
+    owners: Vec<Box<Pubkey>>,
+}
+
+impl Arena {
+    /// `AccountInfo` laid out as the loader serializes it: the original
+    /// data length sits 4 bytes before the key and the current length 8
+    /// bytes before the data, which is followed by realloc headroom.
+    fn account_info(
+        &mut self,
+        key: &Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+        account: &StoredAccount,
+    ) -> AccountInfo<'static> {
+        let len = account.data.len();
+        let mut key_block = vec![0u8; 36].into_boxed_slice();
+        key_block[..4].copy_from_slice(&(len as u32).to_le_bytes());
+        key_block[4..].copy_from_slice(key.as_ref());
+        let key: &'static Pubkey = unsafe { &*(key_block.as_ptr().add(4) as *const Pubkey) };
+        self.key_blocks.push(key_block);
+
+        let words = (8 + len + MAX_PERMITTED_DATA_INCREASE).div_ceil(8);
+        let mut buffer = vec![0u64; words].into_boxed_slice();
+        let bytes: &'static mut [u8] =
+            unsafe { std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, words * 8) };
+        self.buffers.push(buffer);
+        bytes[..8].copy_from_slice(&(len as u64).to_le_bytes());
+        bytes[8..8 + len].copy_from_slice(&account.data);
+        let data = &mut bytes[8..8 + len];
+
+        let mut lamports = Box::new(account.lamports);
+        let lamports_ref: &'static mut u64 = unsafe { &mut *(lamports.as_mut() as *mut u64) };
+        self.lamports.push(lamports);
+        let owner = Box::new(account.owner);
+        let owner_ref: &'static Pubkey = unsafe { &*(owner.as_ref() as *const Pubkey) };
+        self.owners.push(owner);
+
+        AccountInfo::new(key, is_signer, is_writable, lamports_ref, data, owner_ref, account.executable, 0)
+    }
+}
+
+/// Serialized `UpgradeableLoaderState::Program { programdata_address }`.
+fn upgradeable_program_data(programdata: &Pubkey) -> Vec<u8> {
+    let mut data = 2u32.to_le_bytes().to_vec();
+    data.extend_from_slice(programdata.as_ref());
+    data
+}
+
+/// Serialized `UpgradeableLoaderState::ProgramData { slot, upgrade_authority_address }`.
+fn upgradeable_programdata_data(authority: &Pubkey) -> Vec<u8> {
+    let mut data = 3u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.push(1);
+    data.extend_from_slice(authority.as_ref());
+    data
+}
+
+pub struct Svm {
+    accounts: HashMap<Pubkey, StoredAccount>,
+}
+
+impl Svm {
+    /// A runtime with `program_id` (whose entrypoint is `entry`) deployed
+    /// under the upgradeable loader with `upgrade_authority`, plus the
+    /// system, token and associated token programs. Resets this thread's
+    /// clock to `GENESIS_TIME` and forgets programs added by earlier runtimes.
+    pub fn new(program_id: Pubkey, entry: Entry, upgrade_authority: &Pubkey) -> Self {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            set_syscall_stubs(Box::new(Stubs));
+        });
+        CLOCK.with(|c| {
+            *c.borrow_mut() = Clock { unix_timestamp: GENESIS_TIME, ..Clock::default() }
+        });
+
+        PROGRAMS.with(|p| *p.borrow_mut() = HashMap::from([(program_id, entry)]));
+
+        let mut svm = Self { accounts: HashMap::new() };
+        let executable = |owner: Pubkey, data: Vec<u8>| StoredAccount {
+            lamports: 1,
+            data,
+            owner,
+            executable: true,
+        };
+        let programdata =
+            Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::ID).0;
+        svm.set_account(
+            program_id,
+            executable(bpf_loader_upgradeable::ID, upgradeable_program_data(&programdata)),
+        );
+        svm.set_account(
+            programdata,
+            StoredAccount {
+                executable: false,
+                ..executable(bpf_loader_upgradeable::ID, upgradeable_programdata_data(upgrade_authority))
+            },
+        );
+        svm.set_account(system_program::ID, executable(NATIVE_LOADER_ID, Vec::new()));
+        svm.set_account(spl_token::ID, executable(bpf_loader::ID, Vec::new()));
+        svm.set_account(associated_token::ID, executable(bpf_loader::ID, Vec::new()));
+        svm
+    }
+
+    /// Deploys another program, immutable, under the BPF loader, so
+    /// transactions and CPIs can call it.
+    pub fn add_program(&mut self, program_id: Pubkey, entry: Entry) {
+        PROGRAMS.with(|p| p.borrow_mut().insert(program_id, entry));
+        self.set_account(
+            program_id,
+            StoredAccount { lamports: 1, data: Vec::new(), owner: bpf_loader::ID, executable: true },
+        );
+    }
+
+    pub fn set_account(&mut self, key: Pubkey, account: StoredAccount) {
+        self.accounts.insert(key, account);
+    }
+
+    pub fn get_account(&self, key: &Pubkey) -> Option<&StoredAccount> {
+        self.accounts.get(key)
+    }
+
+    pub fn airdrop(&mut self, to: &Pubkey, lamports: u64) {
+        let account = self.accounts.entry(*to).or_insert(StoredAccount {
+            lamports: 0,
+            data: Vec::new(),
+            owner: system_program::ID,
+            executable: false,
+        });
+        account.lamports += lamports;
+    }
+
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.accounts.get(key).map_or(0, |a| a.lamports)
+    }
+
+    /// Deserializes an Anchor (or token) account, panicking if it is
+    /// missing or malformed.
+    pub fn account<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        let account = self.accounts.get(key).unwrap_or_else(|| panic!("no account {key}"));
+        T::try_deserialize(&mut account.data.as_slice())
+            .unwrap_or_else(|e| panic!("account {key}: {e}"))
+    }
+
+    pub fn now(&self) -> i64 {
+        CLOCK.with(|c| c.borrow().unix_timestamp)
+    }
+
+    /// Moves the clock to `unix_timestamp` (and the slot forward with it).
+    pub fn warp_to(&mut self, unix_timestamp: i64) {
+        CLOCK.with(|c| {
+            let mut clock = c.borrow_mut();
+            let elapsed = unix_timestamp.saturating_sub(clock.unix_timestamp).max(0) as u64;
+            clock.slot += elapsed * 5 / 2; // ~400ms slots
+            clock.unix_timestamp = unix_timestamp;
+        });
+    }
+
+    pub fn warp_by(&mut self, seconds: i64) {
+        self.warp_to(self.now() + seconds);
+    }
+
+    /// Verifies `transaction`'s signatures and executes its instructions,
+    /// keeping their account changes only if all of them succeed.
+    pub fn process(&mut self, transaction: &Transaction) -> Result<(), TxError> {
+        let message = &transaction.message;
+        let required = message.header.num_required_signatures as usize;
+        let data = message.serialize();
+        for (i, key) in message.account_keys.iter().enumerate().take(required) {
+            match transaction.signatures.get(i) {
+                Some(signature) if signature.verify(key.as_ref(), &data) => {}
+                Some(signature) if *signature != Signature::default() => {
+                    return Err(TxError::InvalidSignature(*key))
+                }
+                _ => return Err(TxError::MissingSigner(*key)),
+            }
+        }
+
+        let snapshot = self.accounts.clone();
+        for compiled in &message.instructions {
+            let key = |i: &u8| message.account_keys[*i as usize];
+            let instruction = Instruction {
+                program_id: key(&compiled.program_id_index),
+                accounts: compiled
+                    .accounts
+                    .iter()
+                    .map(|&i| AccountMeta {
+                        pubkey: key(&i),
+                        is_signer: message.is_signer(i as usize),
+                        is_writable: message.is_maybe_writable(i as usize, None),
+                    })
+                    .collect(),
+                data: compiled.data.clone(),
+            };
+            if let Err(err) = self.process_instruction(&instruction) {
+                self.accounts = snapshot;
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    fn process_instruction(&mut self, instruction: &Instruction) -> Result<(), TxError> {
+        // one AccountInfo per key; a transaction grants each key the same
+        // privileges in every instruction
+        let keys: Vec<(Pubkey, bool, bool)> = instruction
+            .accounts
+            .iter()
+            .fold(Vec::new(), |mut keys, meta| {
+                if !keys.iter().any(|(key, ..)| *key == meta.pubkey) {
+                    keys.push((meta.pubkey, meta.is_signer, meta.is_writable));
+                }
+                keys
+            });
+        let empty = StoredAccount {
+            lamports: 0,
+            data: Vec::new(),
+            owner: system_program::ID,
+            executable: false,
+        };
+        let pre: Vec<StoredAccount> = keys
+            .iter()
+            .map(|(key, ..)| self.accounts.get(key).cloned().unwrap_or_else(|| empty.clone()))
+            .collect();
+        // `arena` is declared first so it is dropped after every info
+        let mut arena = Arena::default();
+        let unique: Vec<AccountInfo<'static>> = keys
+            .iter()
+            .zip(&pre)
+            .map(|((key, signer, writable), account)| arena.account_info(key, *signer, *writable, account))
+            .collect();
+        let ordered_infos: Vec<AccountInfo<'static>> = instruction
+            .accounts
+            .iter()
+            .map(|meta| unique[keys.iter().position(|(k, ..)| *k == meta.pubkey).unwrap()].clone())
+            .collect();
+        let ordered: &'static [AccountInfo<'static>] =
+            unsafe { std::slice::from_raw_parts(ordered_infos.as_ptr(), ordered_infos.len()) };
+
+        DEBITS.with(|d| d.borrow_mut().clear());
+        PROGRAM_STACK.with(|s| *s.borrow_mut() = vec![instruction.program_id]);
+        let result = execute(&instruction.program_id, ordered, &instruction.data);
+        PROGRAM_STACK.with(|s| s.borrow_mut().clear());
+        result.map_err(TxError::Program)?;
+
+        let post: Vec<StoredAccount> = unique
+            .iter()
+            .map(|info| StoredAccount {
+                lamports: info.lamports(),
+                data: info.data.borrow().to_vec(),
+                owner: *info.owner,
+                executable: info.executable,
+            })
+            .collect();
+        self.check_runtime_rules(&keys, &pre, &post)?;
+        for ((key, ..), account) in keys.iter().zip(post) {
+            if account.lamports == 0 {
+                self.accounts.remove(key);
+            } else {
+                self.accounts.insert(*key, account);
+            }
+        }
+        Ok(())
+    }
+
+    fn check_runtime_rules(
+        &self,
+        keys: &[(Pubkey, bool, bool)],
+        pre: &[StoredAccount],
+        post: &[StoredAccount],
+    ) -> Result<(), TxError> {
+        let total = |accounts: &[StoredAccount]| accounts.iter().map(|a| a.lamports as u128).sum::<u128>();
+        assert_eq!(total(pre), total(post), "lamports were created or destroyed");
+
+        let rent = Rent::default();
+        for (((key, _, writable), before), after) in keys.iter().zip(pre).zip(post) {
+            let changed = before.lamports != after.lamports
+                || before.data != after.data
+                || before.owner != after.owner;
+            assert!(*writable || !changed, "read-only account {key} was modified");
+
+            // any deployed program may have run as the owner, directly or through a CPI
+            let owner_ran = PROGRAMS.with(|p| p.borrow().contains_key(&before.owner));
+            if after.lamports < before.lamports && !owner_ran {
+                let debited = DEBITS.with(|d| d.borrow().get(key).copied().unwrap_or(0));
+                assert!(
+                    before.lamports - after.lamports <= debited,
+                    "{key} lost lamports but is not owned by a program"
+                );
+            }
+
+            let exempt = |a: &StoredAccount| a.lamports == 0 || rent.is_exempt(a.lamports, a.data.len());
+            let still_rent_paying = !exempt(before)
+                && before.data.len() == after.data.len()
+                && after.lamports <= before.lamports;
+            if changed && !exempt(after) && !still_rent_paying {
+                return Err(TxError::InsufficientFundsForRent(*key));
+            }
+        }
+        Ok(())
+    }
+}
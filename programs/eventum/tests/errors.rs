@@ -0,0 +1,623 @@
+//! One test per `ErrorCode` the program can raise, each driving the real
+//! handler into the failing `require!` or account constraint.
+//!
+//! Declared but never raised, so not covered: `InvalidCost`,
+//! `InvalidDuration`, `InvalidEndTime`, `ExcessiveLiquidityRemoval`,
+//! `NoLPTokens`, `MarketNotActive`, `InvalidOutcome`,
+//! `InsufficientTokens`, `ZeroTokenMint`, `ZeroTokenBurn`, `DivisionByZero`,
+//! `AlreadyResolved`, `AlreadyClaimed`, `NotWinner`, `InvalidSigner`,
+//! `AccountNotInitialized` and `InvalidSupply`.
+//!
+//! Raised only by guards that cannot trip: `InsufficientLiquidity` (CPMM
+//! costs round up, so the bought reserve never goes negative) and
+//! `InvalidCalculation` (oracle accumulators only grow).
+
+mod common;
+
+use anchor_lang::solana_program::instruction::AccountMeta;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account;
+use anchor_spl::token::spl_token;
+use common::*;
+use eventum::error::ErrorCode;
+use eventum::TradeLeg;
+use eventum_client::instructions::{self, BuyOptions};
+use eventum_client::{
+    pda, BuyOutcomesArgs, MarketCategory, PricingMode, SetMarketMetadataArgs, Side, TradeLimits,
+};
+
+/// A market with one YES and one NO holder, resolved to `outcome`.
+fn resolved_market(env: &mut Env, outcome: Side) -> (Pubkey, Pubkey, Pubkey) {
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let (yes, no) = (env.wallet(20), env.wallet(20));
+    env.buy(&yes, &market, Side::Yes, 5);
+    env.buy(&no, &market, Side::No, 5);
+    env.resolve(&market, outcome);
+    (creator, market, if outcome == Side::Yes { yes } else { no })
+}
+
+fn metadata_args(tags: usize) -> SetMarketMetadataArgs {
+    SetMarketMetadataArgs {
+        question: "Will it rain?".into(),
+        resolution_rules: "YES on any rainfall.".into(),
+        category: MarketCategory::Other,
+        tags: vec!["tag".into(); tags],
+        uri: String::new(),
+        content_hash: [0; 32],
+    }
+}
+
+#[test]
+fn duplicate_market_id() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let mut args = env.market_args(&creator);
+    args.unique_market_id = 1;
+    let ix = instructions::initialize_market(&creator, &creator, false, args);
+    assert_error(env.send(ix, &[&creator]), ErrorCode::DuplicateMarketId);
+}
+
+#[test]
+fn invalid_fee_share() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let mut args = env.market_args(&creator);
+    args.referral_share_bps = 10_001;
+    let ix = instructions::initialize_market(&creator, &creator, false, args);
+    assert_error(env.send(ix, &[&creator]), ErrorCode::InvalidFeeShare);
+}
+
+#[test]
+fn invalid_pricing_params() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let mut zero_alpha = env.market_args(&creator);
+    zero_alpha.pricing_mode = PricingMode::LsLmsr { alpha_bps: 0, min_b: 100 };
+    let mut zero_b = env.market_args(&creator);
+    zero_b.pricing_mode = PricingMode::Lmsr { b: 0 };
+    let mut zero_min_b = env.market_args(&creator);
+    zero_min_b.pricing_mode = PricingMode::LsLmsr { alpha_bps: 500, min_b: 0 };
+    let mut wide_move = env.market_args(&creator);
+    wide_move.limits.max_price_move_bps = 10_001;
+    let mut inverted = env.market_args(&creator);
+    inverted.limits = TradeLimits { min_tokens: 5, max_tokens: 4, max_price_move_bps: 0 };
+    for args in [zero_alpha, zero_b, zero_min_b, wide_move, inverted] {
+        let ix = instructions::initialize_market(&creator, &creator, false, args);
+        assert_error(env.send(ix, &[&creator]), ErrorCode::InvalidPricingParams);
+    }
+}
+
+#[test]
+fn no_liquidity() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let mut args = env.market_args(&creator);
+    args.pricing_mode = PricingMode::Cpmm { seed_liquidity: 0 };
+    let ix = instructions::initialize_market(&creator, &creator, false, args);
+    assert_error(env.send(ix, &[&creator]), ErrorCode::NoLiquidity);
+}
+
+#[test]
+fn creator_not_approved() {
+    let mut env = Env::new();
+    let admin = env.admin;
+    env.send(instructions::set_permissioned(&admin, true), &[&admin]).unwrap();
+    let creator = env.creator();
+    let args = env.market_args(&creator);
+    let ix = instructions::initialize_market(&creator, &creator, false, args);
+    assert_error(env.send(ix, &[&creator]), ErrorCode::CreatorNotApproved);
+}
+
+#[test]
+fn unauthorized() {
+    let mut env = Env::without_config();
+    let impostor = env.wallet(10);
+    let ix = instructions::initialize_config(&impostor, false);
+    assert_error(env.send(ix, &[&impostor]), ErrorCode::Unauthorized);
+
+    let admin = env.admin;
+    env.send(instructions::initialize_config(&admin, false), &[&admin]).unwrap();
+    let ix = instructions::set_permissioned(&impostor, true);
+    assert_error(env.send(ix, &[&impostor]), ErrorCode::Unauthorized);
+    let ix = instructions::approve_creator(&impostor, &impostor);
+    assert_error(env.send(ix, &[&impostor]), ErrorCode::Unauthorized);
+}
+
+#[test]
+fn invalid_creator() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let impostor = env.wallet(10);
+    let ix = instructions::set_market_metadata(&impostor, &market, metadata_args(0));
+    assert_error(env.send(ix, &[&impostor]), ErrorCode::InvalidCreator);
+    let ix = instructions::init_price_oracle(&impostor, &market);
+    assert_error(env.send(ix, &[&impostor]), ErrorCode::InvalidCreator);
+}
+
+#[test]
+fn metadata_too_long() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let ix = instructions::set_market_metadata(&creator, &market, metadata_args(6));
+    assert_error(env.send(ix, &[&creator]), ErrorCode::MetadataTooLong);
+}
+
+#[test]
+fn invalid_amount() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let alice = env.wallet(10);
+    let ix = env.buy_ix(&alice, &market, Side::Yes, 0, u64::MAX);
+    assert_error(env.send(ix, &[&alice]), ErrorCode::InvalidAmount);
+}
+
+#[test]
+fn math_overflow() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let alice = env.wallet(10);
+    // whole tokens times 10^9 no longer fit in a u64
+    let ix = env.buy_ix(&alice, &market, Side::Yes, u64::MAX / 1_000, u64::MAX);
+    assert_error(env.send(ix, &[&alice]), ErrorCode::MathOverflow);
+}
+
+#[test]
+fn slippage_exceeded() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let alice = env.wallet(10);
+    let quote = env.quote(&market, Side::Yes, 3);
+    let ix = env.buy_ix(&alice, &market, Side::Yes, 3, quote.total - 1);
+    assert_error(env.send(ix, &[&alice]), ErrorCode::SlippageExceeded);
+}
+
+#[test]
+fn insufficient_balance() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    // enough for the two ATAs' rent, not for the tokens
+    let alice = env.wallet(0);
+    env.svm.airdrop(&alice, LAMPORTS_PER_SOL / 100);
+    let ix = env.buy_ix(&alice, &market, Side::Yes, 3, u64::MAX);
+    assert_error(env.send(ix, &[&alice]), ErrorCode::InsufficientBalance);
+}
+
+#[test]
+fn amount_too_small_and_too_large() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let mut args = env.market_args(&creator);
+    args.limits = TradeLimits { min_tokens: 2 * ONE_TOKEN, max_tokens: 5 * ONE_TOKEN, max_price_move_bps: 0 };
+    let market = env.create_market_with(&creator, args);
+    let mut args = env.market_args(&creator);
+    args.limits.max_price_move_bps = 10;
+    let tight = env.create_market_with(&creator, args);
+
+    let alice = env.wallet(20);
+    let ix = env.buy_ix(&alice, &market, Side::Yes, 1, u64::MAX);
+    assert_error(env.send(ix, &[&alice]), ErrorCode::AmountTooSmall);
+    let ix = env.buy_ix(&alice, &market, Side::Yes, 6, u64::MAX);
+    assert_error(env.send(ix, &[&alice]), ErrorCode::AmountTooLarge);
+    // 10 tokens move a b = 1000 LMSR by 25 bps
+    let ix = env.buy_ix(&alice, &tight, Side::Yes, 10, u64::MAX);
+    assert_error(env.send(ix, &[&alice]), ErrorCode::AmountTooLarge);
+}
+
+#[test]
+fn invalid_market() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let other = env.create_market(&creator);
+    let alice = env.wallet(10);
+    let mut ix = env.buy_ix(&alice, &market, Side::Yes, 1, u64::MAX);
+    let foreign = env.market(&other).yes_mint;
+    ix.accounts[2].pubkey = foreign;
+    ix.accounts[5].pubkey = get_associated_token_address(&alice, &foreign);
+    assert_error(env.send(ix, &[&alice]), ErrorCode::InvalidMarket);
+}
+
+#[test]
+fn self_referral() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let alice = env.wallet(10);
+    env.send(instructions::register_referrer(&alice), &[&alice]).unwrap();
+    let args = BuyOutcomesArgs { number_of_tokens: 1, side: Side::Yes, max_cost: u64::MAX };
+    let options = BuyOptions { position: false, referrer: Some(alice) };
+    let ix = instructions::buy_outcomes(&alice, &market, &env.market(&market), args, options);
+    assert_error(env.send(ix, &[&alice]), ErrorCode::SelfReferral);
+}
+
+#[test]
+fn price_oracle_required() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    env.send(instructions::init_price_oracle(&creator, &market), &[&creator]).unwrap();
+    let alice = env.wallet(10);
+    // leave the optional oracle account out
+    let mut ix = env.buy_ix(&alice, &market, Side::Yes, 1, u64::MAX);
+    *ix.accounts.last_mut().unwrap() = AccountMeta::new_readonly(eventum::ID, false);
+    assert_error(env.send(ix, &[&alice]), ErrorCode::PriceOracleRequired);
+}
+
+#[test]
+fn twap_window_unavailable() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    env.send(instructions::init_price_oracle(&creator, &market), &[&creator]).unwrap();
+    env.svm.warp_by(100);
+    env.send(instructions::get_twap(&market, 100), &[]).unwrap();
+    // reaches back before the oracle existed
+    let ix = instructions::get_twap(&market, 101);
+    assert_error(env.send(ix, &[]), ErrorCode::TwapWindowUnavailable);
+}
+
+#[test]
+fn invalid_leg_accounts() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let alice = env.wallet(10);
+    let m = env.market(&market);
+    let leg = TradeLeg { side: Side::Yes, number_of_tokens: 1, max_cost: u64::MAX };
+    let mut ix = instructions::batch_trade(&alice, &[(market, &m, leg.clone())], None);
+    ix.accounts.pop();
+    assert_error(env.send(ix, &[&alice]), ErrorCode::InvalidLegAccounts);
+    // a leg must name the user's position address, opened or not
+    let mut ix = instructions::batch_trade(&alice, &[(market, &m, leg)], None);
+    ix.accounts.last_mut().unwrap().pubkey = Pubkey::new_unique();
+    assert_error(env.send(ix, &[&alice]), ErrorCode::InvalidLegAccounts);
+
+    let ix = instructions::claim_many(&alice, &[]);
+    assert_error(env.send(ix, &[&alice]), ErrorCode::InvalidLegAccounts);
+    env.resolve(&market, Side::Yes);
+    let mut ix = instructions::claim_many(&alice, &[(market, &env.market(&market))]);
+    ix.accounts.last_mut().unwrap().pubkey = Pubkey::new_unique();
+    assert_error(env.send(ix, &[&alice]), ErrorCode::InvalidLegAccounts);
+}
+
+#[test]
+fn invalid_vault() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let other = env.create_market(&creator);
+    let alice = env.wallet(10);
+    let m = env.market(&market);
+    let ata = create_associated_token_account(&alice, &alice, &m.yes_mint, &spl_token::ID);
+    env.send(ata, &[&alice]).unwrap();
+    let leg = TradeLeg { side: Side::Yes, number_of_tokens: 1, max_cost: u64::MAX };
+    let mut ix = instructions::batch_trade(&alice, &[(market, &m, leg)], None);
+    // the leg's accounts follow the four fixed ones; the vault is the fourth
+    ix.accounts[4 + 3].pubkey = pda::vault_address(&other).0;
+    assert_error(env.send(ix, &[&alice]), ErrorCode::InvalidVault);
+}
+
+#[test]
+fn invalid_token_account() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let (alice, bob) = (env.wallet(10), env.wallet(10));
+    let m = env.market(&market);
+    let ata = create_associated_token_account(&bob, &bob, &m.yes_mint, &spl_token::ID);
+    env.send(ata, &[&bob]).unwrap();
+    let leg = TradeLeg { side: Side::Yes, number_of_tokens: 1, max_cost: u64::MAX };
+    let mut ix = instructions::batch_trade(&alice, &[(market, &m, leg)], None);
+    // mint into bob's account on alice's dime
+    ix.accounts[4 + 4].pubkey = get_associated_token_address(&bob, &m.yes_mint);
+    assert_error(env.send(ix, &[&alice]), ErrorCode::InvalidTokenAccount);
+}
+
+#[test]
+fn oracle_not_matched() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let stranger = env.wallet(1);
+    let ix = instructions::resolve_market(&stranger, &market, Side::Yes);
+    assert_error(env.send(ix, &[&stranger]), ErrorCode::OracleNotMatched);
+    let ix = instructions::init_resolution_votes(&stranger, &market, vec![stranger], 1);
+    assert_error(env.send(ix, &[&stranger]), ErrorCode::OracleNotMatched);
+
+    // once a committee decides the market, its old oracle no longer can
+    let ix = instructions::init_resolution_votes(&creator, &market, vec![stranger], 1);
+    env.send(ix, &[&creator]).unwrap();
+    let ix = instructions::resolve_market(&creator, &market, Side::Yes);
+    assert_error(env.send(ix, &[&creator]), ErrorCode::OracleNotMatched);
+}
+
+#[test]
+fn market_resolved() {
+    let mut env = Env::new();
+    let (creator, market, winner) = resolved_market(&mut env, Side::Yes);
+    let ix = instructions::resolve_market(&creator, &market, Side::No);
+    assert_error(env.send(ix, &[&creator]), ErrorCode::MarketResolved);
+    // the shared `quote_buy` check stops single buys and batch legs alike
+    let ix = env.buy_ix(&winner, &market, Side::Yes, 1, u64::MAX);
+    assert_error(env.send(ix, &[&winner]), ErrorCode::MarketResolved);
+    let m = env.market(&market);
+    let leg = TradeLeg { side: Side::Yes, number_of_tokens: 1, max_cost: u64::MAX };
+    let ix = instructions::batch_trade(&winner, &[(market, &m, leg)], None);
+    assert_error(env.send(ix, &[&winner]), ErrorCode::MarketResolved);
+    let ix = instructions::init_price_oracle(&creator, &market);
+    assert_error(env.send(ix, &[&creator]), ErrorCode::MarketResolved);
+    let ix = instructions::init_resolution_votes(&creator, &market, vec![creator], 1);
+    assert_error(env.send(ix, &[&creator]), ErrorCode::MarketResolved);
+}
+
+#[test]
+fn market_expired() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let alice = env.wallet(10);
+    // a batch leg needs the ATA to exist already
+    env.buy(&alice, &market, Side::No, 1);
+    env.svm.warp_to(env.market(&market).end_time);
+    let ix = env.buy_ix(&alice, &market, Side::Yes, 1, u64::MAX);
+    assert_error(env.send(ix, &[&alice]), ErrorCode::MarketExpired);
+    let m = env.market(&market);
+    let leg = TradeLeg { side: Side::No, number_of_tokens: 1, max_cost: u64::MAX };
+    let ix = instructions::batch_trade(&alice, &[(market, &m, leg)], None);
+    assert_error(env.send(ix, &[&alice]), ErrorCode::MarketExpired);
+}
+
+/// A market handed to a two-of-three committee of fresh resolvers.
+fn committee_market(env: &mut Env) -> (Pubkey, [Pubkey; 3]) {
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let resolvers = [env.wallet(1), env.wallet(1), env.wallet(1)];
+    let ix = instructions::init_resolution_votes(&creator, &market, resolvers.to_vec(), 2);
+    env.send(ix, &[&creator]).unwrap();
+    (market, resolvers)
+}
+
+#[test]
+fn invalid_resolver_set() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let a = Pubkey::new_unique();
+    let b = Pubkey::new_unique();
+    let too_many: Vec<Pubkey> = (0..11).map(|_| Pubkey::new_unique()).collect();
+    for (resolvers, threshold) in [(vec![a, b], 0), (vec![a, b], 3), (vec![a, a], 1), (vec![], 0), (too_many, 1)] {
+        let ix = instructions::init_resolution_votes(&creator, &market, resolvers, threshold);
+        assert_error(env.send(ix, &[&creator]), ErrorCode::InvalidResolverSet);
+    }
+}
+
+#[test]
+fn unauthorized_resolver() {
+    let mut env = Env::new();
+    let (market, _) = committee_market(&mut env);
+    let stranger = env.wallet(1);
+    let ix = instructions::vote_resolution(&stranger, &market, Side::Yes);
+    assert_error(env.send(ix, &[&stranger]), ErrorCode::UnauthorizedResolver);
+
+    // only the arbiter decides a disputed optimistic proposal
+    let (market, _, proposer) = proposed_market(&mut env);
+    let challenger = env.wallet(2);
+    env.send(instructions::challenge_proposal(&challenger, &market), &[&challenger]).unwrap();
+    let ix = instructions::arbitrate(&stranger, &market, &proposer, &challenger, Side::No);
+    assert_error(env.send(ix, &[&stranger]), ErrorCode::UnauthorizedResolver);
+}
+
+#[test]
+fn invalid_oracle_data() {
+    let mut env = Env::new();
+    let (market, [a, _, _]) = committee_market(&mut env);
+    env.send(instructions::vote_resolution(&a, &market, Side::Yes), &[&a]).unwrap();
+    let ix = instructions::vote_resolution(&a, &market, Side::No);
+    assert_error(env.send(ix, &[&a]), ErrorCode::InvalidOracleData);
+}
+
+/// A market handed to optimistic resolution (one-hour liveness and
+/// arbitration period) and its arbiter.
+fn optimistic_market(env: &mut Env) -> (Pubkey, Pubkey) {
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let arbiter = env.wallet(1);
+    let ix = instructions::init_optimistic_resolution(&creator, &market, &arbiter, LAMPORTS_PER_SOL, 3600, 0, 3600);
+    env.send(ix, &[&creator]).unwrap();
+    (market, arbiter)
+}
+
+/// `optimistic_market` past its `end_time` with an outcome proposed.
+fn proposed_market(env: &mut Env) -> (Pubkey, Pubkey, Pubkey) {
+    let (market, arbiter) = optimistic_market(env);
+    env.svm.warp_to(env.market(&market).end_time);
+    let proposer = env.wallet(2);
+    env.send(instructions::propose_outcome(&proposer, &market, Side::Yes), &[&proposer]).unwrap();
+    (market, arbiter, proposer)
+}
+
+#[test]
+fn invalid_resolution_params() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let arbiter = Pubkey::new_unique();
+    let params = [(0, 3600, 0, 3600), (1, 0, 0, 3600), (1, 3600, 10001, 3600), (1, 3600, 0, 0)];
+    for (bond, liveness, reward_bps, arbitration_period) in params {
+        let ix = instructions::init_optimistic_resolution(
+            &creator,
+            &market,
+            &arbiter,
+            bond,
+            liveness,
+            reward_bps,
+            arbitration_period,
+        );
+        assert_error(env.send(ix, &[&creator]), ErrorCode::InvalidResolutionParams);
+    }
+}
+
+#[test]
+fn market_not_expired() {
+    let mut env = Env::new();
+    let (market, _) = optimistic_market(&mut env);
+    let proposer = env.wallet(2);
+    let ix = instructions::propose_outcome(&proposer, &market, Side::Yes);
+    assert_error(env.send(ix, &[&proposer]), ErrorCode::MarketNotExpired);
+}
+
+#[test]
+fn proposal_exists() {
+    let mut env = Env::new();
+    let (market, _, _) = proposed_market(&mut env);
+    let rival = env.wallet(2);
+    let ix = instructions::propose_outcome(&rival, &market, Side::No);
+    assert_error(env.send(ix, &[&rival]), ErrorCode::ProposalExists);
+}
+
+#[test]
+fn no_proposal() {
+    let mut env = Env::new();
+    let (market, _) = optimistic_market(&mut env);
+    let challenger = env.wallet(2);
+    let ix = instructions::challenge_proposal(&challenger, &market);
+    assert_error(env.send(ix, &[&challenger]), ErrorCode::NoProposal);
+}
+
+#[test]
+fn proposal_disputed() {
+    let mut env = Env::new();
+    let (market, _, proposer) = proposed_market(&mut env);
+    let challenger = env.wallet(2);
+    env.send(instructions::challenge_proposal(&challenger, &market), &[&challenger]).unwrap();
+    let ix = instructions::challenge_proposal(&proposer, &market);
+    assert_error(env.send(ix, &[&proposer]), ErrorCode::ProposalDisputed);
+    // the arbiter still has a second left
+    env.svm.warp_by(3599);
+    let ix = instructions::settle_proposal(&market, &proposer, Some(&challenger));
+    assert_error(env.send(ix, &[]), ErrorCode::ProposalDisputed);
+}
+
+#[test]
+fn proposal_not_disputed() {
+    let mut env = Env::new();
+    let (market, arbiter, proposer) = proposed_market(&mut env);
+    let ix = instructions::arbitrate(&arbiter, &market, &proposer, &proposer, Side::No);
+    assert_error(env.send(ix, &[&arbiter]), ErrorCode::ProposalNotDisputed);
+}
+
+#[test]
+fn liveness_elapsed() {
+    let mut env = Env::new();
+    let (market, _, _) = proposed_market(&mut env);
+    env.svm.warp_by(3600);
+    let challenger = env.wallet(2);
+    let ix = instructions::challenge_proposal(&challenger, &market);
+    assert_error(env.send(ix, &[&challenger]), ErrorCode::LivenessElapsed);
+}
+
+#[test]
+fn liveness_active() {
+    let mut env = Env::new();
+    let (market, _, proposer) = proposed_market(&mut env);
+    env.svm.warp_by(3599);
+    let ix = instructions::settle_proposal(&market, &proposer, None);
+    assert_error(env.send(ix, &[]), ErrorCode::LivenessActive);
+}
+
+#[test]
+fn arbitration_expired() {
+    let mut env = Env::new();
+    let (market, arbiter, proposer) = proposed_market(&mut env);
+    let challenger = env.wallet(2);
+    env.send(instructions::challenge_proposal(&challenger, &market), &[&challenger]).unwrap();
+    env.svm.warp_by(3600);
+    let ix = instructions::arbitrate(&arbiter, &market, &proposer, &challenger, Side::No);
+    assert_error(env.send(ix, &[&arbiter]), ErrorCode::ArbitrationExpired);
+}
+
+#[test]
+fn arbiter_is_party() {
+    let mut env = Env::new();
+    let (market, arbiter) = optimistic_market(&mut env);
+    env.svm.warp_to(env.market(&market).end_time);
+    let ix = instructions::propose_outcome(&arbiter, &market, Side::Yes);
+    assert_error(env.send(ix, &[&arbiter]), ErrorCode::ArbiterIsParty);
+
+    let proposer = env.wallet(2);
+    env.send(instructions::propose_outcome(&proposer, &market, Side::Yes), &[&proposer]).unwrap();
+    let ix = instructions::challenge_proposal(&arbiter, &market);
+    assert_error(env.send(ix, &[&arbiter]), ErrorCode::ArbiterIsParty);
+}
+
+#[test]
+fn market_not_resolved() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let alice = env.wallet(10);
+    env.buy(&alice, &market, Side::Yes, 1);
+    let ix = instructions::claim_winnings(&alice, &market, false);
+    assert_error(env.send(ix, &[&alice]), ErrorCode::MarketNotResolved);
+    let ix = instructions::claim_many(&alice, &[(market, &env.market(&market))]);
+    assert_error(env.send(ix, &[&alice]), ErrorCode::MarketNotResolved);
+    let ix = instructions::close_market(&creator, &market);
+    assert_error(env.send(ix, &[&creator]), ErrorCode::MarketNotResolved);
+}
+
+#[test]
+fn winnings_outstanding() {
+    let mut env = Env::new();
+    let (creator, market, _) = resolved_market(&mut env, Side::No);
+    let ix = instructions::close_market(&creator, &market);
+    assert_error(env.send(ix, &[&creator]), ErrorCode::WinningsOutstanding);
+}
+
+#[test]
+fn tip_too_large() {
+    let mut env = Env::new();
+    let (_, market, winner) = resolved_market(&mut env, Side::Yes);
+    let cranker = env.wallet(1);
+    let ix = instructions::claim_for(&cranker, &winner, &market, &env.market(&market), 51, false);
+    assert_error(env.send(ix, &[&cranker]), ErrorCode::TipTooLarge);
+}
+
+#[test]
+fn delegate_not_approved() {
+    let mut env = Env::new();
+    let (_, market, winner) = resolved_market(&mut env, Side::Yes);
+    let cranker = env.wallet(1);
+    let ix = instructions::claim_for(&cranker, &winner, &market, &env.market(&market), 0, false);
+    assert_error(env.send(ix, &[&cranker]), ErrorCode::DelegateNotApproved);
+}
+
+#[test]
+fn no_winnings() {
+    let mut env = Env::new();
+    let (_, market, winner) = resolved_market(&mut env, Side::Yes);
+    let m = env.market(&market);
+    // a delegate approved for nothing can claim nothing
+    let ata = get_associated_token_address(&winner, &m.yes_mint);
+    let approve = spl_token::instruction::approve(&spl_token::ID, &ata, &market, &winner, &[], 0).unwrap();
+    env.send(approve, &[&winner]).unwrap();
+    let cranker = env.wallet(1);
+    let ix = instructions::claim_for(&cranker, &winner, &market, &m, 0, false);
+    assert_error(env.send(ix, &[&cranker]), ErrorCode::NoWinnings);
+}
+
+#[test]
+fn no_balance() {
+    let mut env = Env::new();
+    let partner = env.wallet(1);
+    env.send(instructions::register_referrer(&partner), &[&partner]).unwrap();
+    let ix = instructions::claim_referral_fees(&partner);
+    assert_error(env.send(ix, &[&partner]), ErrorCode::NoBalance);
+}
@@ -0,0 +1,731 @@
+//! Happy paths through the program, run in-process against the real
+//! instruction handlers: create, trade, resolve, claim and close, plus the
+//! optional accounts (positions, referrers, price oracles) and the parts of
+//! a market's life that depend on the clock.
+
+mod common;
+
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account;
+use anchor_spl::token::spl_token;
+use common::*;
+use eventum::instructions::close_market::CLOSE_GRACE_PERIOD;
+use eventum::states::{
+    Config, CreatorApproval, MarketMetadata, OptimisticResolution, Position, PriceOracle, Referrer,
+    ResolutionVotes, PRICE_SCALE,
+};
+use eventum::TradeLeg;
+use eventum_client::instructions::{self, BuyOptions};
+use eventum_client::{
+    pda, BuyOutcomesArgs, FeeModel, MarketCategory, PricingMode, SetMarketMetadataArgs, Side,
+};
+use solana_sdk::signature::{Keypair, Signature, Signer};
+
+#[test]
+fn initialize_config_and_gate_creators() {
+    let mut env = Env::without_config();
+    let admin = env.admin;
+    env.send(instructions::initialize_config(&admin, true), &[&admin]).unwrap();
+    let config: Config = env.svm.account(&pda::config_address().0);
+    assert_eq!(config.admin, admin);
+    assert!(config.permissioned);
+
+    let creator = env.creator();
+    env.send(instructions::approve_creator(&admin, &creator), &[&admin]).unwrap();
+    let approval: CreatorApproval = env.svm.account(&pda::approval_address(&creator).0);
+    assert_eq!(approval.creator, creator);
+    assert_eq!(approval.approved_by, admin);
+    assert_eq!(approval.approved_at, GENESIS_TIME);
+
+    let args = env.market_args(&creator);
+    let market = pda::market_address(&creator, 0).0;
+    env.send(instructions::initialize_market(&creator, &creator, true, args), &[&creator])
+        .unwrap();
+    assert!(env.market(&market).approved_creator);
+
+    env.send(instructions::revoke_creator(&admin, &creator), &[&admin]).unwrap();
+    assert!(env.svm.get_account(&pda::approval_address(&creator).0).is_none());
+    // existing markets keep their flag
+    assert!(env.market(&market).approved_creator);
+
+    env.send(instructions::set_permissioned(&admin, false), &[&admin]).unwrap();
+    env.create_market(&creator);
+}
+
+#[test]
+fn markets_can_be_created_before_initialize_config() {
+    let mut env = Env::without_config();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    assert!(!env.market(&market).approved_creator);
+    assert!(env.svm.get_account(&pda::config_address().0).is_none());
+}
+
+#[test]
+fn trades_need_the_buyers_signature() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let alice = env.wallet(10);
+    let ix = env.buy_ix(&alice, &market, Side::Yes, 1, u64::MAX);
+
+    let mut unsigned = env.transaction(ix.clone(), &[&alice]);
+    unsigned.signatures[0] = Signature::default();
+    assert_eq!(env.svm.process(&unsigned), Err(TxError::MissingSigner(alice)));
+    let mut forged = env.transaction(ix.clone(), &[&alice]);
+    forged.signatures[0] = Keypair::new().sign_message(&forged.message_data());
+    assert_eq!(env.svm.process(&forged), Err(TxError::InvalidSignature(alice)));
+    assert_eq!(env.svm.lamports(&alice), 10 * LAMPORTS_PER_SOL);
+
+    env.send(ix, &[&alice]).unwrap();
+    assert_eq!(env.token_balance(&alice, &env.market(&market).yes_mint), ONE_TOKEN);
+}
+
+#[test]
+fn lmsr_market_from_creation_to_close() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let args = env.market_args(&creator);
+    let end_time = args.end_time;
+    let market = env.create_market_with(&creator, args);
+
+    let m = env.market(&market);
+    assert_eq!(m.creator, creator);
+    assert_eq!(m.oracle_authority, creator);
+    assert_eq!(m.unique_market_id, 0);
+    assert_eq!((m.start_time, m.end_time), (GENESIS_TIME, end_time));
+    assert_eq!(m.yes_mint, pda::yes_mint_address(&market).0);
+    assert_eq!(m.no_mint, pda::no_mint_address(&market).0);
+    assert!(!m.resolved);
+    let profile: eventum::states::CreatorProfile =
+        env.svm.account(&pda::creator_profile_address(&creator).0);
+    assert_eq!(profile.market_count, 1);
+
+    // the creator subsidises the maker's worst-case loss, b · ln 2
+    assert_eq!(m.total_liquidity, 693_147_180_560);
+    let subsidy = m.total_liquidity + VAULT_RENT;
+    assert_eq!(env.vault_lamports(&market), subsidy);
+
+    // a complete set of 10 YES and 10 NO costs 10 SOL under LMSR, up to the
+    // error of rust_decimal's exp/ln
+    let alice = env.wallet(20);
+    let bob = env.wallet(20);
+    let alice_before = env.svm.lamports(&alice);
+    let yes = env.buy(&alice, &market, Side::Yes, 10);
+    assert_eq!(env.vault_lamports(&market), subsidy + yes.total);
+    assert!(yes.price_yes_after > PRICE_SCALE / 2);
+    let no = env.buy(&bob, &market, Side::No, 10);
+    assert_eq!(env.vault_lamports(&market), subsidy + yes.total + no.total);
+    assert!((yes.cost + no.cost).abs_diff(10 * ONE_TOKEN) < 1_000, "{yes:?} {no:?}");
+    assert_eq!(env.token_balance(&alice, &m.yes_mint), 10 * ONE_TOKEN);
+    assert_eq!(env.token_balance(&bob, &m.no_mint), 10 * ONE_TOKEN);
+    assert_eq!(env.token_balance(&alice, &m.no_mint), 0);
+
+    env.resolve(&market, Side::Yes);
+    let m = env.market(&market);
+    assert!(m.resolved && m.winning_outcome);
+
+    let alice_mid = env.svm.lamports(&alice);
+    assert!(alice_mid < alice_before - yes.total);   // the ATAs' rent came out too
+    env.send(instructions::claim_winnings(&alice, &market, false), &[&alice]).unwrap();
+    assert_eq!(env.svm.lamports(&alice), alice_mid + 10 * ONE_TOKEN);
+    assert_eq!(env.token_balance(&alice, &m.yes_mint), 0);
+
+    // the loser's claim burns nothing and pays nothing
+    let bob_before = env.svm.lamports(&bob);
+    env.send(instructions::claim_winnings(&bob, &market, false), &[&bob]).unwrap();
+    assert_eq!(env.svm.lamports(&bob), bob_before);
+
+    // every winning token is burned, so the creator may sweep the subsidy
+    // and the fees at once
+    let fees = env.vault_lamports(&market);
+    assert_eq!(fees, subsidy + yes.total + no.total - 10 * ONE_TOKEN);
+    let market_rent = env.svm.lamports(&market);
+    let creator_before = env.svm.lamports(&creator);
+    env.send(instructions::close_market(&creator, &market), &[&creator]).unwrap();
+    assert_eq!(env.svm.lamports(&creator), creator_before + fees + market_rent);
+    assert!(env.svm.get_account(&market).is_none());
+    assert_eq!(env.vault_lamports(&market), 0);
+}
+
+#[test]
+fn ls_lmsr_market_trades() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let mut args = env.market_args(&creator);
+    args.pricing_mode = PricingMode::LsLmsr { alpha_bps: 500, min_b: 100 };
+    let market = env.create_market_with(&creator, args);
+    let subsidy = env.vault_lamports(&market);
+    assert_eq!(subsidy, 69_314_718_056 + VAULT_RENT); // min_b · ln 2
+
+    let alice = env.wallet(20);
+    let first = env.buy(&alice, &market, Side::No, 5);
+    assert_eq!(first.price_yes_before, PRICE_SCALE / 2);
+    assert!(first.price_yes_after < PRICE_SCALE / 2);
+    let second = env.buy(&alice, &market, Side::No, 5);
+    assert_eq!(second.price_yes_before, first.price_yes_after);
+    assert_eq!(env.vault_lamports(&market), subsidy + first.total + second.total);
+    assert_eq!(env.token_balance(&alice, &env.market(&market).no_mint), 10 * ONE_TOKEN);
+}
+
+#[test]
+fn lmsr_subsidy_follows_the_creators_b() {
+    let mut env = Env::new();
+    let creator = env.wallet(10);
+    let mut args = env.market_args(&creator);
+    args.pricing_mode = PricingMode::Lmsr { b: 10 };
+    let market = env.create_market_with(&creator, args);
+    let subsidy = env.vault_lamports(&market) - VAULT_RENT;
+    assert_eq!(subsidy, 6_931_471_806); // b · ln 2, rounded up
+    assert!(env.svm.lamports(&creator) > 3 * LAMPORTS_PER_SOL);
+
+    // far past the point where e^(q / b) leaves Decimal's range
+    let whale = env.wallet(1_100);
+    let quote = env.buy(&whale, &market, Side::Yes, 1_000);
+    assert!(quote.price_yes_after > PRICE_SCALE - PRICE_SCALE / 1_000);
+    assert!(env.vault_lamports(&market) >= 1_000 * ONE_TOKEN + VAULT_RENT);
+}
+
+#[test]
+fn cpmm_market_is_seeded_by_creator() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let seed = 5 * LAMPORTS_PER_SOL;
+    let mut args = env.market_args(&creator);
+    args.pricing_mode = PricingMode::Cpmm { seed_liquidity: seed };
+    let market = env.create_market_with(&creator, args);
+    assert_eq!(env.vault_lamports(&market), seed + VAULT_RENT);
+    let m = env.market(&market);
+    assert_eq!((m.yes_reserve, m.no_reserve, m.total_liquidity), (seed, seed, seed));
+
+    let alice = env.wallet(10);
+    let quote = env.buy(&alice, &market, Side::Yes, 2);
+    let m = env.market(&market);
+    assert_eq!(m.no_reserve, seed + quote.cost);
+    assert_eq!(m.yes_reserve, seed + quote.cost - 2 * ONE_TOKEN);
+    // the constant product never shrinks
+    assert!(m.yes_reserve as u128 * m.no_reserve as u128 >= seed as u128 * seed as u128);
+    assert_eq!(env.vault_lamports(&market), seed + VAULT_RENT + quote.total);
+    assert!(quote.price_yes_after > PRICE_SCALE / 2);
+}
+
+#[test]
+fn the_named_oracle_resolves_the_market() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let oracle = env.wallet(1);
+    let args = env.market_args(&creator);
+    let market = pda::market_address(&creator, args.unique_market_id).0;
+    env.send(instructions::initialize_market(&creator, &oracle, false, args), &[&creator]).unwrap();
+    assert_eq!(env.market(&market).oracle_authority, oracle);
+
+    // the creator has no say once someone else is the oracle
+    let ix = instructions::resolve_market(&creator, &market, Side::Yes);
+    assert_error(env.send(ix, &[&creator]), eventum::error::ErrorCode::OracleNotMatched);
+    env.send(instructions::resolve_market(&oracle, &market, Side::Yes), &[&oracle]).unwrap();
+    assert!(env.market(&market).resolved);
+}
+
+#[test]
+fn metadata_and_position_follow_the_market() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let args = SetMarketMetadataArgs {
+        question: "Will it rain in Lisbon on 1 May?".into(),
+        resolution_rules: "YES if IPMA reports any rainfall at Lisbon airport.".into(),
+        category: MarketCategory::Science,
+        tags: vec!["weather".into()],
+        uri: "https://example.com/rain".into(),
+        content_hash: [7; 32],
+    };
+    env.send(instructions::set_market_metadata(&creator, &market, args), &[&creator]).unwrap();
+    let metadata: MarketMetadata = env.svm.account(&pda::metadata_address(&market).0);
+    assert_eq!(metadata.market, market);
+    assert_eq!(metadata.tags, vec!["weather".to_string()]);
+    assert_eq!(metadata.content_hash, [7; 32]);
+
+    let alice = env.wallet(20);
+    let bob = env.wallet(20);
+    env.send(instructions::open_position(&alice, &market), &[&alice]).unwrap();
+    let options = BuyOptions { position: true, referrer: None };
+    let mut paid = 0;
+    for side in [Side::Yes, Side::No, Side::Yes] {
+        let quote = env.quote(&market, side, 3);
+        let args = BuyOutcomesArgs { number_of_tokens: 3, side, max_cost: quote.total };
+        let ix = instructions::buy_outcomes(&alice, &market, &env.market(&market), args, options);
+        env.send(ix, &[&alice]).unwrap();
+        paid += quote.total;
+    }
+    let position_address = pda::position_address(&market, &alice).0;
+    let position: Position = env.svm.account(&position_address);
+    assert_eq!(position.owner, alice);
+    assert_eq!((position.yes_quantity, position.no_quantity), (6 * ONE_TOKEN, 3 * ONE_TOKEN));
+    assert_eq!(position.yes_cost_basis + position.no_cost_basis, paid);
+    assert_eq!(position.lamports_spent, paid);
+
+    // keep the vault solvent for the YES payout
+    env.buy(&bob, &market, Side::No, 3);
+    env.resolve(&market, Side::Yes);
+    env.send(instructions::claim_winnings(&alice, &market, true), &[&alice]).unwrap();
+    let position: Position = env.svm.account(&position_address);
+    assert_eq!(position.realized_pnl, (6 * ONE_TOKEN) as i64 - paid as i64);
+    assert_eq!((position.yes_quantity, position.yes_cost_basis), (0, 0));
+}
+
+#[test]
+fn referrer_earns_its_share_and_withdraws() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let mut args = env.market_args(&creator);
+    args.referral_share_bps = 2_500;
+    let market = env.create_market_with(&creator, args);
+    let subsidy = env.vault_lamports(&market);
+
+    let partner = env.wallet(1);
+    env.send(instructions::register_referrer(&partner), &[&partner]).unwrap();
+    let referrer = pda::referrer_address(&partner).0;
+    let rent = env.svm.lamports(&referrer);
+
+    let alice = env.wallet(20);
+    let quote = env.quote(&market, Side::Yes, 10);
+    let args = BuyOutcomesArgs { number_of_tokens: 10, side: Side::Yes, max_cost: quote.total };
+    let options = BuyOptions { position: false, referrer: Some(partner) };
+    let ix = instructions::buy_outcomes(&alice, &market, &env.market(&market), args, options);
+    env.send(ix, &[&alice]).unwrap();
+
+    let share = quote.fee / 4;
+    let state: Referrer = env.svm.account(&referrer);
+    assert_eq!((state.pending, state.total_earned), (share, share));
+    assert_eq!(env.svm.lamports(&referrer), rent + share);
+    assert_eq!(env.vault_lamports(&market), subsidy + quote.total - share);
+
+    let before = env.svm.lamports(&partner);
+    env.send(instructions::claim_referral_fees(&partner), &[&partner]).unwrap();
+    assert_eq!(env.svm.lamports(&partner), before + share);
+    assert_eq!(env.svm.lamports(&referrer), rent);
+    let state: Referrer = env.svm.account(&referrer);
+    assert_eq!((state.pending, state.total_earned), (0, share));
+}
+
+#[test]
+fn batch_trade_fills_every_leg() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let first = env.create_market(&creator);
+    let second = env.create_market(&creator);
+    let subsidy = env.vault_lamports(&first);
+
+    let alice = env.wallet(40);
+    let (m1, m2) = (env.market(&first), env.market(&second));
+    for mint in [m1.yes_mint, m2.no_mint] {
+        let ix = create_associated_token_account(&alice, &alice, &mint, &spl_token::ID);
+        env.send(ix, &[&alice]).unwrap();
+    }
+
+    // two legs on the first market see each other's supply change
+    let q1 = env.quote(&first, Side::Yes, 4);
+    let q3 = env.quote(&second, Side::No, 2);
+    let legs = [
+        (first, &m1, TradeLeg { side: Side::Yes, number_of_tokens: 4, max_cost: q1.total }),
+        (first, &m1, TradeLeg { side: Side::Yes, number_of_tokens: 4, max_cost: q1.total }),
+        (second, &m2, TradeLeg { side: Side::No, number_of_tokens: 2, max_cost: q3.total }),
+    ];
+    // the second leg costs more than the first
+    let ix = instructions::batch_trade(&alice, &legs, None);
+    assert_error(env.send(ix, &[&alice]), eventum::error::ErrorCode::SlippageExceeded);
+
+    let mut legs = legs;
+    legs[1].2.max_cost = u64::MAX;
+    let before = env.svm.lamports(&alice);
+    env.send(instructions::batch_trade(&alice, &legs, None), &[&alice]).unwrap();
+    assert_eq!(env.token_balance(&alice, &m1.yes_mint), 8 * ONE_TOKEN);
+    assert_eq!(env.token_balance(&alice, &m2.no_mint), 2 * ONE_TOKEN);
+    let paid = before - env.svm.lamports(&alice);
+    assert_eq!(paid + 2 * subsidy, env.vault_lamports(&first) + env.vault_lamports(&second));
+    assert_eq!(env.vault_lamports(&second), subsidy + q3.total);
+    assert!(env.vault_lamports(&first) > subsidy + 2 * q1.total);
+}
+
+#[test]
+fn batch_trade_updates_open_positions() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let first = env.create_market(&creator);
+    let second = env.create_market(&creator);
+    let alice = env.wallet(40);
+    let (m1, m2) = (env.market(&first), env.market(&second));
+    for mint in [m1.yes_mint, m2.no_mint] {
+        let ix = create_associated_token_account(&alice, &alice, &mint, &spl_token::ID);
+        env.send(ix, &[&alice]).unwrap();
+    }
+    env.send(instructions::open_position(&alice, &first), &[&alice]).unwrap();
+
+    let q1 = env.quote(&first, Side::Yes, 4);
+    let legs = [
+        (first, &m1, TradeLeg { side: Side::Yes, number_of_tokens: 4, max_cost: q1.total }),
+        (second, &m2, TradeLeg { side: Side::No, number_of_tokens: 2, max_cost: u64::MAX }),
+    ];
+    env.send(instructions::batch_trade(&alice, &legs, None), &[&alice]).unwrap();
+
+    // only the opened position is tracked; the other leg still fills
+    let position: Position = env.svm.account(&pda::position_address(&first, &alice).0);
+    assert_eq!((position.yes_quantity, position.yes_cost_basis), (4 * ONE_TOKEN, q1.total));
+    assert_eq!(position.fees_paid, q1.fee);
+    assert!(env.svm.get_account(&pda::position_address(&second, &alice).0).is_none());
+    assert_eq!(env.token_balance(&alice, &m2.no_mint), 2 * ONE_TOKEN);
+}
+
+#[test]
+fn price_oracle_reports_time_weighted_price() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    env.send(instructions::init_price_oracle(&creator, &market), &[&creator]).unwrap();
+    let oracle_address = pda::price_oracle_address(&market).0;
+    assert_eq!(env.market(&market).price_oracle, oracle_address);
+
+    let alice = env.wallet(100);
+    env.svm.warp_by(100);
+    env.buy(&alice, &market, Side::Yes, 50);
+    let oracle: PriceOracle = env.svm.account(&oracle_address);
+    assert_eq!((oracle.len, oracle.last_update), (2, GENESIS_TIME + 100));
+    let after = oracle.last_price;
+    assert!(after > PRICE_SCALE / 2);
+
+    env.svm.warp_by(100);
+    // `get_twap` returns the price as return data, which the native runtime
+    // does not capture; check it runs and read the same average off the account
+    env.send(instructions::get_twap(&market, 200), &[]).unwrap();
+    let oracle: PriceOracle = env.svm.account(&oracle_address);
+    // 100s at 0.5, then 100s at the post-trade price
+    assert_eq!(oracle.twap(env.now(), 200).unwrap(), (PRICE_SCALE / 2 + after) / 2);
+    assert_eq!(oracle.twap(env.now(), 50).unwrap(), after);
+}
+
+#[test]
+fn resolver_committee_resolves_once_threshold_agrees() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let alice = env.wallet(20);
+    env.buy(&alice, &market, Side::No, 5);
+
+    let [a, b, c] = [env.wallet(1), env.wallet(1), env.wallet(1)];
+    let ix = instructions::init_resolution_votes(&creator, &market, vec![a, b, c], 2);
+    env.send(ix, &[&creator]).unwrap();
+    let votes_address = pda::resolution_votes_address(&market).0;
+    assert_eq!(env.market(&market).oracle_authority, votes_address);
+
+    // a split vote decides nothing; repeating a vote changes nothing
+    env.send(instructions::vote_resolution(&a, &market, Side::Yes), &[&a]).unwrap();
+    env.send(instructions::vote_resolution(&b, &market, Side::No), &[&b]).unwrap();
+    env.send(instructions::vote_resolution(&a, &market, Side::Yes), &[&a]).unwrap();
+    assert!(!env.market(&market).resolved);
+    let votes: ResolutionVotes = env.svm.account(&votes_address);
+    assert_eq!((votes.tally(Side::Yes), votes.tally(Side::No)), (1, 1));
+
+    env.send(instructions::vote_resolution(&c, &market, Side::No), &[&c]).unwrap();
+    let m = env.market(&market);
+    assert!(m.resolved && !m.winning_outcome);
+    let ix = instructions::vote_resolution(&a, &market, Side::No);
+    assert_error(env.send(ix, &[&a]), eventum::error::ErrorCode::MarketResolved);
+
+    env.send(instructions::claim_winnings(&alice, &market, false), &[&alice]).unwrap();
+    assert_eq!(env.token_balance(&alice, &m.no_mint), 0);
+}
+
+const BOND: u64 = LAMPORTS_PER_SOL;
+const LIVENESS: i64 = 2 * 60 * 60;
+const ARBITRATION: i64 = 24 * 60 * 60;
+
+/// A market with one NO buyer, handed to optimistic resolution with a 50%
+/// fee reward, and the clock at its `end_time`.
+fn optimistic_market(env: &mut Env, arbiter: &Pubkey) -> (Pubkey, Pubkey) {
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let alice = env.wallet(20);
+    let quote = env.buy(&alice, &market, Side::No, 5);
+    assert_eq!(env.market(&market).fees_collected, quote.fee);
+
+    let ix = instructions::init_optimistic_resolution(&creator, &market, arbiter, BOND, LIVENESS, 5000, ARBITRATION);
+    env.send(ix, &[&creator]).unwrap();
+    assert_eq!(env.market(&market).oracle_authority, pda::optimistic_resolution_address(&market).0);
+    env.svm.warp_to(env.market(&market).end_time);
+    (market, alice)
+}
+
+#[test]
+fn unchallenged_proposal_settles_after_liveness() {
+    let mut env = Env::new();
+    let (market, alice) = optimistic_market(&mut env, &Pubkey::new_unique());
+    let fees = env.market(&market).fees_collected;
+    let vault_before = env.vault_lamports(&market);
+
+    let proposer = env.wallet(2);
+    env.send(instructions::propose_outcome(&proposer, &market, Side::No), &[&proposer]).unwrap();
+    assert_eq!(env.svm.lamports(&proposer), 2 * LAMPORTS_PER_SOL - BOND);
+    let optimistic: OptimisticResolution = env.svm.account(&pda::optimistic_resolution_address(&market).0);
+    assert_eq!((optimistic.proposal, optimistic.proposer), (Some(Side::No), proposer));
+
+    env.svm.warp_by(LIVENESS);
+    // anyone may settle; the proposer gets the bond back and half the fees
+    env.send(instructions::settle_proposal(&market, &proposer, None), &[]).unwrap();
+    assert_eq!(env.svm.lamports(&proposer), 2 * LAMPORTS_PER_SOL + fees / 2);
+    assert_eq!(env.vault_lamports(&market), vault_before - fees / 2);
+    let m = env.market(&market);
+    assert!(m.resolved && !m.winning_outcome);
+    assert_eq!(m.fees_collected, fees - fees / 2);
+
+    env.send(instructions::claim_winnings(&alice, &market, false), &[&alice]).unwrap();
+    assert_eq!(env.token_balance(&alice, &m.no_mint), 0);
+}
+
+#[test]
+fn challenged_proposal_is_decided_by_the_arbiter() {
+    let mut env = Env::new();
+    let arbiter = env.wallet(1);
+    let (market, _) = optimistic_market(&mut env, &arbiter);
+    let fees = env.market(&market).fees_collected;
+
+    let (proposer, challenger) = (env.wallet(2), env.wallet(2));
+    env.send(instructions::propose_outcome(&proposer, &market, Side::Yes), &[&proposer]).unwrap();
+    env.svm.warp_by(LIVENESS - 1);
+    env.send(instructions::challenge_proposal(&challenger, &market), &[&challenger]).unwrap();
+
+    // the arbiter sides with the challenger, who takes both bonds
+    let ix = instructions::arbitrate(&arbiter, &market, &proposer, &challenger, Side::No);
+    env.send(ix, &[&arbiter]).unwrap();
+    assert_eq!(env.svm.lamports(&proposer), 2 * LAMPORTS_PER_SOL - BOND);
+    assert_eq!(env.svm.lamports(&challenger), 2 * LAMPORTS_PER_SOL + BOND);
+    let m = env.market(&market);
+    assert!(m.resolved && !m.winning_outcome);
+    assert_eq!(m.fees_collected, fees);
+}
+
+#[test]
+fn lapsed_arbitration_keeps_the_proposal_and_refunds_both_bonds() {
+    let mut env = Env::new();
+    let arbiter = env.wallet(1);
+    let (market, _) = optimistic_market(&mut env, &arbiter);
+    let fees = env.market(&market).fees_collected;
+
+    let (proposer, challenger) = (env.wallet(2), env.wallet(2));
+    env.send(instructions::propose_outcome(&proposer, &market, Side::Yes), &[&proposer]).unwrap();
+    env.send(instructions::challenge_proposal(&challenger, &market), &[&challenger]).unwrap();
+    let optimistic: OptimisticResolution = env.svm.account(&pda::optimistic_resolution_address(&market).0);
+    assert_eq!(optimistic.arbitration_deadline, env.now() + ARBITRATION);
+
+    // the arbiter never decides; once the deadline passes anyone may settle
+    env.svm.warp_by(ARBITRATION);
+    let ix = instructions::settle_proposal(&market, &proposer, Some(&challenger));
+    env.send(ix, &[]).unwrap();
+    assert_eq!(env.svm.lamports(&proposer), 2 * LAMPORTS_PER_SOL);
+    assert_eq!(env.svm.lamports(&challenger), 2 * LAMPORTS_PER_SOL);
+    let m = env.market(&market);
+    assert!(m.resolved && m.winning_outcome);
+    assert_eq!(m.fees_collected, fees);
+
+    let ix = instructions::arbitrate(&arbiter, &market, &proposer, &challenger, Side::No);
+    assert_error(env.send(ix, &[&arbiter]), eventum::error::ErrorCode::MarketResolved);
+}
+
+#[test]
+fn claim_for_pays_holder_and_cranker() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let holder = env.wallet(20);
+    let other = env.wallet(20);
+    env.buy(&holder, &market, Side::No, 10);
+    env.buy(&other, &market, Side::Yes, 10);
+    env.resolve(&market, Side::No);
+
+    let m = env.market(&market);
+    let ata = get_associated_token_address(&holder, &m.no_mint);
+    let approve = spl_token::instruction::approve(&spl_token::ID, &ata, &market, &holder, &[], 4 * ONE_TOKEN)
+        .unwrap();
+    env.send(approve, &[&holder]).unwrap();
+
+    let cranker = env.wallet(1);
+    let (holder_before, cranker_before) = (env.svm.lamports(&holder), env.svm.lamports(&cranker));
+    env.send(instructions::claim_for(&cranker, &holder, &market, &m, 50, false), &[&cranker])
+        .unwrap();
+    // only the delegated amount is redeemed
+    let tip = 4 * ONE_TOKEN / 200;
+    assert_eq!(env.svm.lamports(&cranker), cranker_before + tip);
+    assert_eq!(env.svm.lamports(&holder), holder_before + 4 * ONE_TOKEN - tip);
+    assert_eq!(env.token_balance(&holder, &m.no_mint), 6 * ONE_TOKEN);
+}
+
+#[test]
+fn partial_claim_for_keeps_the_rest_of_the_position() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let holder = env.wallet(20);
+    let other = env.wallet(20);
+    env.send(instructions::open_position(&holder, &market), &[&holder]).unwrap();
+    let quote = env.quote(&market, Side::No, 10);
+    let args = BuyOutcomesArgs { number_of_tokens: 10, side: Side::No, max_cost: quote.total };
+    let options = BuyOptions { position: true, referrer: None };
+    let ix = instructions::buy_outcomes(&holder, &market, &env.market(&market), args, options);
+    env.send(ix, &[&holder]).unwrap();
+    env.buy(&other, &market, Side::Yes, 10);
+    env.resolve(&market, Side::No);
+
+    let m = env.market(&market);
+    let ata = get_associated_token_address(&holder, &m.no_mint);
+    let approve = spl_token::instruction::approve(&spl_token::ID, &ata, &market, &holder, &[], 4 * ONE_TOKEN)
+        .unwrap();
+    env.send(approve, &[&holder]).unwrap();
+    let cranker = env.wallet(1);
+    env.send(instructions::claim_for(&cranker, &holder, &market, &m, 50, true), &[&cranker])
+        .unwrap();
+
+    // four tenths of the cost is realized, the rest stays open
+    let tip = 4 * ONE_TOKEN / 200;
+    let realized = quote.total * 4 / 10;
+    let position_address = pda::position_address(&market, &holder).0;
+    let position: Position = env.svm.account(&position_address);
+    assert_eq!(position.no_quantity, 6 * ONE_TOKEN);
+    assert_eq!(position.no_cost_basis, quote.total - realized);
+    assert_eq!(position.realized_pnl, (4 * ONE_TOKEN - tip) as i64 - realized as i64);
+
+    env.send(instructions::claim_winnings(&holder, &market, true), &[&holder]).unwrap();
+    let position: Position = env.svm.account(&position_address);
+    assert_eq!((position.no_quantity, position.no_cost_basis), (0, 0));
+    assert_eq!(position.realized_pnl, (10 * ONE_TOKEN - tip) as i64 - quote.total as i64);
+}
+
+#[test]
+fn claim_many_settles_several_markets() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let first = env.create_market(&creator);
+    let second = env.create_market(&creator);
+    let alice = env.wallet(40);
+    let bob = env.wallet(40);
+    for market in [first, second] {
+        env.buy(&alice, &market, Side::Yes, 5);
+        env.buy(&bob, &market, Side::No, 5);
+    }
+    env.resolve(&first, Side::Yes);
+    env.resolve(&second, Side::No);
+
+    let (m1, m2) = (env.market(&first), env.market(&second));
+    let before = env.svm.lamports(&alice);
+    let ix = instructions::claim_many(&alice, &[(first, &m1), (second, &m2)]);
+    env.send(ix, &[&alice]).unwrap();
+    // the losing market is skipped, not failed
+    assert_eq!(env.svm.lamports(&alice), before + 5 * ONE_TOKEN);
+    assert_eq!(env.token_balance(&alice, &m1.yes_mint), 0);
+    assert_eq!(env.token_balance(&alice, &m2.yes_mint), 5 * ONE_TOKEN);
+}
+
+#[test]
+fn claim_many_settles_open_positions() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let first = env.create_market(&creator);
+    let second = env.create_market(&creator);
+    let alice = env.wallet(40);
+    let bob = env.wallet(40);
+    let options = BuyOptions { position: true, referrer: None };
+    let mut paid = Vec::new();
+    for market in [first, second] {
+        env.send(instructions::open_position(&alice, &market), &[&alice]).unwrap();
+        let quote = env.quote(&market, Side::Yes, 5);
+        let args = BuyOutcomesArgs { number_of_tokens: 5, side: Side::Yes, max_cost: quote.total };
+        let ix = instructions::buy_outcomes(&alice, &market, &env.market(&market), args, options);
+        env.send(ix, &[&alice]).unwrap();
+        paid.push(quote.total);
+        env.buy(&bob, &market, Side::No, 5);
+    }
+    env.resolve(&first, Side::Yes);
+    env.resolve(&second, Side::No);
+
+    let (m1, m2) = (env.market(&first), env.market(&second));
+    env.send(instructions::claim_many(&alice, &[(first, &m1), (second, &m2)]), &[&alice]).unwrap();
+    // the losing market pays nothing but its cost basis is still realized
+    for (market, pnl) in [(first, (5 * ONE_TOKEN) as i64 - paid[0] as i64), (second, -(paid[1] as i64))] {
+        let position: Position = env.svm.account(&pda::position_address(&market, &alice).0);
+        assert_eq!(position.realized_pnl, pnl);
+        assert_eq!((position.yes_quantity, position.yes_cost_basis), (0, 0));
+    }
+
+    // a later claim has nothing left to realize
+    env.send(instructions::claim_winnings(&alice, &first, true), &[&alice]).unwrap();
+    let position: Position = env.svm.account(&pda::position_address(&first, &alice).0);
+    assert_eq!(position.realized_pnl, (5 * ONE_TOKEN) as i64 - paid[0] as i64);
+}
+
+#[test]
+fn time_increasing_fee_grows_towards_end_time() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let mut args = env.market_args(&creator);
+    args.fee_model = FeeModel::TimeIncreasing;
+    args.end_time = GENESIS_TIME + 10 * DAY;
+    let market = env.create_market_with(&creator, args);
+
+    let start = env.quote(&market, Side::Yes, 10);
+    assert_eq!(start.fee, start.cost / 100);
+    env.svm.warp_to(GENESIS_TIME + 5 * DAY);
+    let half = env.quote(&market, Side::Yes, 10);
+    assert_eq!(half.fee, start.cost * 150 / 10_000);
+
+    // the last trade before end_time pays just under twice the base fee
+    env.svm.warp_to(GENESIS_TIME + 10 * DAY - 1);
+    let alice = env.wallet(20);
+    let late = env.buy(&alice, &market, Side::Yes, 10);
+    assert_eq!(late.fee, start.cost * 199 / 10_000);
+    assert_eq!(env.vault_lamports(&market), env.market(&market).total_liquidity + VAULT_RENT + late.total);
+}
+
+#[test]
+fn end_time_closes_trading_but_not_resolution() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let early = env.create_market(&creator);
+    let late = env.create_market(&creator);
+
+    // the oracle may resolve before end_time ...
+    env.resolve(&early, Side::No);
+
+    // ... but nobody may trade from end_time on, while the outcome may be known
+    let alice = env.wallet(20);
+    let end_time = env.market(&late).end_time;
+    env.svm.warp_to(end_time - 1);
+    env.buy(&alice, &late, Side::Yes, 1);
+    env.svm.warp_to(end_time);
+    let ix = env.buy_ix(&alice, &late, Side::Yes, 1, u64::MAX);
+    assert_error(env.send(ix, &[&alice]), eventum::error::ErrorCode::MarketExpired);
+    env.resolve(&late, Side::Yes);
+}
+
+#[test]
+fn close_market_waits_out_grace_period_for_unclaimed_winnings() {
+    let mut env = Env::new();
+    let creator = env.creator();
+    let market = env.create_market(&creator);
+    let alice = env.wallet(20);
+    let bob = env.wallet(20);
+    env.buy(&alice, &market, Side::Yes, 10);
+    env.buy(&bob, &market, Side::No, 10);
+    env.resolve(&market, Side::No);
+
+    let close = instructions::close_market(&creator, &market);
+    let end_time = env.market(&market).end_time;
+    assert_error(env.send(close.clone(), &[&creator]), eventum::error::ErrorCode::WinningsOutstanding);
+    env.svm.warp_to(end_time + CLOSE_GRACE_PERIOD - 1);
+    assert_error(env.send(close.clone(), &[&creator]), eventum::error::ErrorCode::WinningsOutstanding);
+
+    env.svm.warp_to(end_time + CLOSE_GRACE_PERIOD);
+    let swept = env.vault_lamports(&market) + env.svm.lamports(&market);
+    let before = env.svm.lamports(&creator);
+    env.send(close, &[&creator]).unwrap();
+    assert_eq!(env.svm.lamports(&creator), before + swept);
+    assert!(env.svm.get_account(&market).is_none());
+}
@@ -0,0 +1,217 @@
+//! Property tests for the vault's solvency. Random markets take random
+//! sequences of trades through the real handlers, resolve to a random
+//! outcome and pay out every holder; after each step the vault must still
+//! cover whichever side could win, and the fee ledger must add up.
+//!
+//! `Op` lists the trades the program supports; new trade instructions
+//! (sells, merges, ...) get a variant here and a branch in `Run::apply`.
+
+mod common;
+
+use anchor_lang::solana_program::pubkey::Pubkey;
+use common::*;
+use eventum::states::Referrer;
+use eventum_client::instructions::{self, BuyOptions};
+use eventum_client::{pda, BuyOutcomesArgs, FeeModel, PricingMode, Side};
+use proptest::prelude::*;
+
+const TRADERS: usize = 3;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Buy { trader: usize, side: Side, tokens: u64, referred: bool },
+    /// Lets time pass, which moves `TimeIncreasing` fees.
+    Wait { hours: i64 },
+}
+
+#[derive(Clone, Debug)]
+struct Scenario {
+    pricing_mode: PricingMode,
+    fee: u32,
+    fee_model: FeeModel,
+    referral_share_bps: u16,
+    ops: Vec<Op>,
+    outcome: Side,
+}
+
+fn side() -> impl Strategy<Value = Side> {
+    prop_oneof![Just(Side::Yes), Just(Side::No)]
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        8 => (0..TRADERS, side(), 1..=500u64, any::<bool>())
+            .prop_map(|(trader, side, tokens, referred)| Op::Buy { trader, side, tokens, referred }),
+        1 => (1..=48i64).prop_map(|hours| Op::Wait { hours }),
+    ]
+}
+
+fn scenario() -> impl Strategy<Value = Scenario> {
+    let pricing_mode = prop_oneof![
+        (1..=1_000u64).prop_map(|b| PricingMode::Lmsr { b }),
+        (1..=10_000u16, 1..=1_000u64).prop_map(|(alpha_bps, min_b)| PricingMode::LsLmsr { alpha_bps, min_b }),
+        (1..=1_000u64).prop_map(|seed| PricingMode::Cpmm { seed_liquidity: seed * ONE_TOKEN }),
+    ];
+    let fee_model = prop_oneof![
+        Just(FeeModel::Flat),
+        Just(FeeModel::PriceDependent),
+        Just(FeeModel::TimeIncreasing),
+    ];
+    (
+        pricing_mode,
+        0..=1_000u32,
+        fee_model,
+        0..=10_000u16,
+        prop::collection::vec(op(), 1..24),
+        side(),
+    )
+        .prop_map(|(pricing_mode, fee, fee_model, referral_share_bps, ops, outcome)| Scenario {
+            pricing_mode,
+            fee,
+            fee_model,
+            referral_share_bps,
+            ops,
+            outcome,
+        })
+}
+
+/// A market under test and the ledger it is checked against.
+struct Run {
+    env: Env,
+    creator: Pubkey,
+    market: Pubkey,
+    traders: Vec<Pubkey>,
+    partner: Pubkey,
+    /// The creator's deposit beyond the vault's rent.
+    subsidy: u64,
+    /// Lamports paid into the pool, fees excluded.
+    costs: u64,
+    /// Fees the vault kept after referrers took their cut.
+    fees: u64,
+    referral: u64,
+}
+
+impl Run {
+    fn new(scenario: &Scenario) -> Self {
+        let mut env = Env::new();
+        let creator = env.creator();
+        let mut args = env.market_args(&creator);
+        args.pricing_mode = scenario.pricing_mode;
+        args.fee = scenario.fee;
+        args.fee_model = scenario.fee_model;
+        args.referral_share_bps = scenario.referral_share_bps;
+        args.end_time = env.now() + 30 * DAY;
+        let market = env.create_market_with(&creator, args);
+        let traders = (0..TRADERS).map(|_| env.wallet(1_000_000)).collect();
+        let partner = env.wallet(1);
+        env.send(instructions::register_referrer(&partner), &[&partner])
+            .expect("register_referrer");
+        let subsidy = env.market(&market).total_liquidity;
+        assert_eq!(env.vault_lamports(&market), subsidy + VAULT_RENT);
+        Self { env, creator, market, traders, partner, subsidy, costs: 0, fees: 0, referral: 0 }
+    }
+
+    fn supplies(&self) -> (u64, u64) {
+        let state = self.env.state(&self.market);
+        (state.yes_supply, state.no_supply)
+    }
+
+    fn referrer_pending(&self) -> u64 {
+        self.env
+            .svm
+            .account::<Referrer>(&pda::referrer_address(&self.partner).0)
+            .pending
+    }
+
+    fn apply(&mut self, op: &Op) -> Result<(), TestCaseError> {
+        match *op {
+            Op::Buy { trader, side, tokens, referred } => {
+                let user = self.traders[trader];
+                let quote = self.env.quote(&self.market, side, tokens);
+                prop_assert_eq!(quote.total, quote.cost + quote.fee);
+                let args = BuyOutcomesArgs { number_of_tokens: tokens, side, max_cost: quote.total };
+                let options = BuyOptions { position: false, referrer: referred.then_some(self.partner) };
+                let ix = instructions::buy_outcomes(&user, &self.market, &self.env.market(&self.market), args, options);
+                let (vault_before, pending_before) = (self.env.vault_lamports(&self.market), self.referrer_pending());
+                self.env.send(ix, &[&user]).expect("buy_outcomes");
+
+                let referral = self.referrer_pending() - pending_before;
+                prop_assert!(referral <= quote.fee, "referral {} above fee {}", referral, quote.fee);
+                prop_assert!(referred || referral == 0);
+                prop_assert_eq!(self.env.vault_lamports(&self.market) - vault_before, quote.total - referral);
+                self.costs += quote.cost;
+                self.fees += quote.fee - referral;
+                self.referral += referral;
+            }
+            Op::Wait { hours } => self.env.svm.warp_by(hours * 60 * 60),
+        }
+        Ok(())
+    }
+
+    /// Before resolution: the ledger adds up, and the vault could pay
+    /// either side out in full without touching the fees.
+    fn check_open(&self) -> Result<(), TestCaseError> {
+        let vault = self.env.vault_lamports(&self.market);
+        prop_assert_eq!(vault, VAULT_RENT + self.subsidy + self.costs + self.fees);
+        prop_assert_eq!(self.referrer_pending(), self.referral);
+        let (yes, no) = self.supplies();
+        prop_assert!(
+            self.subsidy + self.costs >= yes.max(no),
+            "pool {} cannot cover yes {} / no {}",
+            self.subsidy + self.costs,
+            yes,
+            no
+        );
+        Ok(())
+    }
+}
+
+fn run(scenario: Scenario) -> Result<(), TestCaseError> {
+    let mut run = Run::new(&scenario);
+    run.check_open()?;
+    for op in &scenario.ops {
+        run.apply(op)?;
+        run.check_open()?;
+    }
+
+    run.env.resolve(&run.market, scenario.outcome);
+    let market = run.env.market(&run.market);
+    let winning_mint = if market.winning_outcome { market.yes_mint } else { market.no_mint };
+    for trader in run.traders.clone() {
+        let owed = run.env.token_balance(&trader, &winning_mint);
+        if owed == 0 {
+            continue;
+        }
+        let (vault, before) = (run.env.vault_lamports(&run.market), run.env.svm.lamports(&trader));
+        // payouts never reach into the vault's rent
+        prop_assert!(vault >= owed + VAULT_RENT, "vault {} cannot pay {}", vault, owed);
+        run.env
+            .send(instructions::claim_winnings(&trader, &run.market, false), &[&trader])
+            .expect("claim_winnings");
+        prop_assert_eq!(run.env.svm.lamports(&trader), before + owed);
+        prop_assert_eq!(run.env.vault_lamports(&run.market), vault - owed);
+    }
+    let (yes, no) = run.supplies();
+    prop_assert_eq!(if market.winning_outcome { yes } else { no }, 0);
+
+    // what is left is the creator's: the fees, plus whatever of the subsidy
+    // the maker did not lose
+    let left = run.env.vault_lamports(&run.market);
+    prop_assert!(left >= run.fees, "vault {} below retained fees {}", left, run.fees);
+    let before = run.env.svm.lamports(&run.creator);
+    let rent = run.env.svm.lamports(&run.market);
+    run.env
+        .send(instructions::close_market(&run.creator, &run.market), &[&run.creator])
+        .expect("close_market");
+    prop_assert_eq!(run.env.svm.lamports(&run.creator), before + left + rent);
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig { cases: 128, ..ProptestConfig::default() })]
+
+    #[test]
+    fn vault_covers_every_payout(scenario in scenario()) {
+        run(scenario)?;
+    }
+}
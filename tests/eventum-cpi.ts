@@ -0,0 +1,128 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Eventum } from "../target/types/eventum";
+import { EventumCpiExample } from "../target/types/eventum_cpi_example";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+
+describe("eventum-cpi-example", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const connection = provider.connection;
+  const eventum = anchor.workspace.eventum as Program<Eventum>;
+  const example = anchor.workspace
+    .eventumCpiExample as Program<EventumCpiExample>;
+
+  const creator = Keypair.generate();
+  const user = Keypair.generate();
+  let marketPda: PublicKey;
+  let yesMint: PublicKey;
+  let noMint: PublicKey;
+  let vaultPda: PublicKey;
+
+  before(async () => {
+    // the creator subsidises the LMSR market, b · ln 2 ≈ 6.9 SOL
+    for (const [kp, sol] of [
+      [creator, 10],
+      [user, 5],
+    ] as const) {
+      const sig = await connection.requestAirdrop(
+        kp.publicKey,
+        sol * LAMPORTS_PER_SOL
+      );
+      await connection.confirmTransaction(sig, "confirmed");
+    }
+
+    // a fresh creator's first market has id 0
+    [marketPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("Market"),
+        creator.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+      ],
+      eventum.programId
+    );
+    [yesMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("yes_mint"), marketPda.toBuffer()],
+      eventum.programId
+    );
+    [noMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("no_mint"), marketPda.toBuffer()],
+      eventum.programId
+    );
+    [vaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("market-vault"), marketPda.toBuffer()],
+      eventum.programId
+    );
+
+    await eventum.methods
+      .initializeMarket({
+        uniqueMarketId: new anchor.BN(0),
+        endTime: new anchor.BN(Math.floor(Date.now() / 1000) + 24 * 60 * 60),
+        fee: 100,
+        feeModel: { flat: {} },
+        referralShareBps: 0,
+        pricingMode: { lmsr: { b: new anchor.BN(10) } },
+        limits: {
+          minTokens: new anchor.BN(0),
+          maxTokens: new anchor.BN(0),
+          maxPriceMoveBps: 0,
+        },
+        question: "Does the CPI example work?",
+      })
+      .accounts({
+        creatorApproval: null,
+        market: marketPda,
+        creator: creator.publicKey,
+        oracleAuthority: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+  });
+
+  const proxyAccounts = () => ({
+    user: user.publicKey,
+    market: marketPda,
+    yesMint,
+    noMint,
+    vault: vaultPda,
+    userYesAta: getAssociatedTokenAddressSync(yesMint, user.publicKey),
+    userNoAta: getAssociatedTokenAddressSync(noMint, user.publicKey),
+    priceOracle: null,
+    eventumProgram: eventum.programId,
+  });
+
+  it("buys through CPI", async () => {
+    await example.methods
+      .proxyBuy({
+        numberOfTokens: new anchor.BN(3),
+        side: { yes: {} },
+        maxCost: new anchor.BN(LAMPORTS_PER_SOL),
+      })
+      .accounts(proxyAccounts())
+      .signers([user])
+      .rpc();
+
+    const balance = await connection.getTokenAccountBalance(
+      proxyAccounts().userYesAta
+    );
+    console.log("YES bought via CPI:", balance.value.uiAmount);
+  });
+
+  it("claims through CPI", async () => {
+    await eventum.methods
+      .resolveMarket({ yes: {} })
+      .accounts({ market: marketPda, oracleAuthority: creator.publicKey })
+      .signers([creator])
+      .rpc();
+
+    const before = await connection.getBalance(user.publicKey);
+    await example.methods
+      .proxyClaim()
+      .accounts(proxyAccounts())
+      .signers([user])
+      .rpc();
+    const after = await connection.getBalance(user.publicKey);
+    console.log("Claimed via CPI:", (after - before) / LAMPORTS_PER_SOL, "SOL");
+  });
+});
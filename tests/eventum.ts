@@ -5,9 +5,6 @@ import {
   Keypair,
   LAMPORTS_PER_SOL,
   PublicKey,
-  sendAndConfirmTransaction,
-  SystemProgram,
-  Transaction,
 } from "@solana/web3.js";
 import fs from "fs";
 import {
@@ -15,6 +12,7 @@ import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
   createTransferCheckedInstruction,
+  approve,
 } from "@solana/spl-token";
 import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
 import { create } from "domain";
@@ -44,11 +42,46 @@ describe("eventum", () => {
   console.log("harsit : ", HARSHIT_KEYPAIR.publicKey.toString());
   console.log("test : ", TEST_KEYPAIR.publicKey.toString());
 
+  it("initialize config", async () => {
+    // the program's upgrade authority (the deploying wallet) becomes admin
+    const admin = (program.provider as anchor.AnchorProvider).wallet;
+    const [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+    );
+    const [programData] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+
+    await program.methods
+      .initializeConfig(false)
+      .accounts({
+        admin: admin.publicKey,
+        config: configPda,
+        program: program.programId,
+        programData,
+      })
+      .rpc();
+
+    const config = await program.account.config.fetch(configPda);
+    console.log("Admin:", config.admin.toString());
+    console.log("Permissioned:", config.permissioned);
+  });
+
   it("Initialize Market", async () => {
     const signer = HARSHIT_KEYPAIR;
     const creator = signer.publicKey;
     const oracleAuthority = creator;
-    const unique_market_id = 1104;
+    const [creatorProfilePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("creator"), creator.toBuffer()],
+      program.programId
+    );
+    // ids are allocated sequentially per creator, starting at 0
+    const profile = await program.account.creatorProfile.fetchNullable(
+      creatorProfilePda
+    );
+    const unique_market_id = profile ? profile.marketCount.toNumber() : 0;
     const fee = 5000 ;
     const question = "Will virat kohli hit a century today ??";
     const uniqueIdBuf = new anchor.BN(unique_market_id).toArrayLike(
@@ -62,13 +95,23 @@ describe("eventum", () => {
     );
     const end_time = Math.floor(Date.now() / 1000) + 3 * 24 * 60 * 60;
     await program.methods
-      .initializeMarket(
-        new anchor.BN(unique_market_id),
-        new anchor.BN(end_time),
+      .initializeMarket({
+        uniqueMarketId: new anchor.BN(unique_market_id),
+        endTime: new anchor.BN(end_time),
         fee,
-        question
-      )
+        feeModel: { priceDependent: {} },
+        referralShareBps: 2000, // 20% of fees go to the referrer, when there is one
+        pricingMode: { lsLmsr: { alphaBps: 500, minB: new anchor.BN(20) } },
+        limits: {
+          minTokens: new anchor.BN(LAMPORTS_PER_SOL), // at least 1 token
+          maxTokens: new anchor.BN(0), // no cap
+          maxPriceMoveBps: 3000, // no single trade moves YES by 30+ points
+        },
+        question,
+      })
       .accounts({
+        creatorApproval: null,
+        creatorProfile: creatorProfilePda,
         market: marketPda,
         creator,
         oracleAuthority,
@@ -91,7 +134,10 @@ describe("eventum", () => {
       new Date(market.endTime.toNumber() * 1000).toLocaleString()
     );
     console.log("Fee:", market.fee);
+    console.log("Fee model:", Object.keys(market.feeModel)[0]);
+    console.log("Pricing mode:", Object.keys(market.pricingMode)[0]);
     console.log("Question:", market.question);
+    console.log("Approved creator:", market.approvedCreator);
   });
 
   // // it("Add liquidity", async () => {
@@ -174,13 +220,161 @@ describe("eventum", () => {
   // //   );
   // // });
 
+  it("init price oracle", async () => {
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const creator = HARSHIT_KEYPAIR;
+    const unique_market_id = 0;
+
+    const uniqueIdBuffer = new anchor.BN(unique_market_id).toArrayLike(
+      Buffer,
+      "le",
+      8
+    );
+
+    const [marketPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("Market"), creator.publicKey.toBuffer(), uniqueIdBuffer],
+      program.programId
+    );
+
+    const [priceOraclePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("price-oracle"), marketPda.toBuffer()],
+      program.programId
+    );
+
+    const marketState = await program.account.market.fetch(marketPda);
+
+    await program.methods
+      .initPriceOracle()
+      .accounts({
+        creator: creator.publicKey,
+        market: marketPda,
+        yesMint: marketState.yesMint,
+        noMint: marketState.noMint,
+        priceOracle: priceOraclePda,
+      })
+      .signers([creator])
+      .rpc();
+
+    const oracle = await program.account.priceOracle.fetch(priceOraclePda);
+    console.log("Price oracle PDA:", priceOraclePda.toString());
+    console.log("Starting YES price:", oracle.lastPrice.toNumber() / 1e9);
+  });
+
+  it("set market metadata", async () => {
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const creator = HARSHIT_KEYPAIR;
+    const unique_market_id = 0;
+
+    const uniqueIdBuffer = new anchor.BN(unique_market_id).toArrayLike(
+      Buffer,
+      "le",
+      8
+    );
+
+    const [marketPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("Market"), creator.publicKey.toBuffer(), uniqueIdBuffer],
+      program.programId
+    );
+
+    const [metadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("metadata"), marketPda.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .setMarketMetadata({
+        question:
+          "Will Virat Kohli score 100 or more runs in his next ODI innings?",
+        resolutionRules:
+          "Resolves YES if the official ESPNcricinfo scorecard shows 100+ runs.",
+        category: { sports: {} },
+        tags: ["cricket", "odi"],
+        uri: "https://example.com/markets/0.json",
+        contentHash: Array(32).fill(0),
+      })
+      .accounts({
+        creator: creator.publicKey,
+        market: marketPda,
+        metadata: metadataPda,
+      })
+      .signers([creator])
+      .rpc();
+
+    const metadata = await program.account.marketMetadata.fetch(metadataPda);
+    console.log("Metadata PDA:", metadataPda.toString());
+    console.log("Question:", metadata.question);
+    console.log("Rules:", metadata.resolutionRules);
+    console.log("Tags:", metadata.tags.join(", "));
+  });
+
+  it("open position", async () => {
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const creator = HARSHIT_KEYPAIR.publicKey;
+    const user = TEST_KEYPAIR;
+    const unique_market_id = 0;
+
+    const uniqueIdBuffer = new anchor.BN(unique_market_id).toArrayLike(
+      Buffer,
+      "le",
+      8
+    );
+
+    const [marketPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("Market"), creator.toBuffer(), uniqueIdBuffer],
+      program.programId
+    );
+
+    const [positionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("position"), marketPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .openPosition()
+      .accounts({
+        user: user.publicKey,
+        market: marketPda,
+        position: positionPda,
+      })
+      .signers([user])
+      .rpc();
+
+    const position = await program.account.position.fetch(positionPda);
+    console.log("Position PDA:", positionPda.toString());
+    console.log("Owner:", position.owner.toString());
+  });
+
+  it("register referrer", async () => {
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const partner = HARSHIT_KEYPAIR;
+    const [referrerPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("referrer"), partner.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .registerReferrer()
+      .accounts({
+        authority: partner.publicKey,
+        referrer: referrerPda,
+      })
+      .signers([partner])
+      .rpc();
+
+    console.log("Referrer PDA:", referrerPda.toString());
+  });
+
   it("buy outcomes", async () => {
     // Wait for previous transaction to settle
     await new Promise((resolve) => setTimeout(resolve, 2000));
 
     const creator = HARSHIT_KEYPAIR;
     const user = TEST_KEYPAIR;
-    const unique_market_id = 1104;
+    const unique_market_id = 0;
 
     // Derive PDAs
     const uniqueIdBuffer = new anchor.BN(unique_market_id).toArrayLike(
@@ -199,22 +393,30 @@ describe("eventum", () => {
       program.programId
     );
 
+    const [positionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("position"), marketPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const [referrerPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("referrer"), creator.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const [priceOraclePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("price-oracle"), marketPda.toBuffer()],
+      program.programId
+    );
+
     console.log("Market PDA:", marketPda.toString());
     console.log("Vault PDA:", vaultPda.toString());
 
-    // Fund vault with initial liquidity
-    const vaultFundingAmount = 100 * LAMPORTS_PER_SOL;
-    const fundVaultIx = SystemProgram.transfer({
-      fromPubkey: creator.publicKey,
-      toPubkey: vaultPda,
-      lamports: vaultFundingAmount,
-    });
-
-    const fundTx = new Transaction().add(fundVaultIx);
-    const fundSig = await sendAndConfirmTransaction(connection, fundTx, [
-      creator,
-    ]);
-    console.log("Vault funded. Signature:", fundSig);
+    // initialize_market already put the creator's subsidy in the vault
+    console.log(
+      "Vault subsidy:",
+      (await connection.getBalance(vaultPda)) / LAMPORTS_PER_SOL,
+      "SOL"
+    );
 
     // Fetch market state and mint accounts
     const marketState = await program.account.market.fetch(marketPda);
@@ -270,13 +472,12 @@ describe("eventum", () => {
 
     // Execute buy transaction
     const buySig = await program.methods
-      .buyOutcomes(
-        new anchor.BN(unique_market_id),
-        new anchor.BN(numberOfTokens),
-        buyYes
-      )
+      .buyOutcomes({
+        numberOfTokens: new anchor.BN(numberOfTokens),
+        side: buyYes ? { yes: {} } : { no: {} },
+        maxCost: new anchor.BN(LAMPORTS_PER_SOL), // refuse to pay more than 1 SOL
+      })
       .accounts({
-        creator: creator.publicKey,
         user: user.publicKey,
         market: marketPda,
         yesMint,
@@ -284,6 +485,9 @@ describe("eventum", () => {
         vault: vaultPda,
         userYesAta,
         userNoAta,
+        position: positionPda,
+        referrer: referrerPda,
+        priceOracle: priceOraclePda,
       })
       .signers([user])
       .rpc();
@@ -320,17 +524,166 @@ describe("eventum", () => {
       ? userYesBalance.value.uiAmount
       : userNoBalance.value.uiAmount;
     console.log("Expected tokens received:", expectedTokens);
+    const position = await program.account.position.fetch(positionPda);
+    console.log(
+      "Position cost basis:",
+      (position.lamportsSpent.toNumber() / LAMPORTS_PER_SOL).toFixed(6),
+      "SOL, fees:",
+      (position.feesPaid.toNumber() / LAMPORTS_PER_SOL).toFixed(6)
+    );
+    const twap = await program.methods
+      .getTwap(new anchor.BN(1))
+      .accounts({ priceOracle: priceOraclePda })
+      .view();
+    console.log("YES TWAP (last second):", twap.toNumber() / 1e9);
     console.log("Transaction confirmed successfully");
   });
 
+  it("claim referral fees", async () => {
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const partner = HARSHIT_KEYPAIR;
+    const [referrerPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("referrer"), partner.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const referrerState = await program.account.referrer.fetch(referrerPda);
+    console.log(
+      "Referral fees pending:",
+      (referrerState.pending.toNumber() / LAMPORTS_PER_SOL).toFixed(9)
+    );
+
+    const claimSig = await program.methods
+      .claimReferralFees()
+      .accounts({
+        authority: partner.publicKey,
+        referrer: referrerPda,
+      })
+      .signers([partner])
+      .rpc();
+
+    const after = await program.account.referrer.fetch(referrerPda);
+    console.log(
+      "Total earned:",
+      (after.totalEarned.toNumber() / LAMPORTS_PER_SOL).toFixed(9)
+    );
+    console.log("Transaction:", claimSig);
+  });
+
+  it("batch trade", async () => {
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const creator = HARSHIT_KEYPAIR.publicKey;
+    const user = TEST_KEYPAIR;
+    const unique_market_id = 0;
+
+    const uniqueIdBuffer = new anchor.BN(unique_market_id).toArrayLike(
+      Buffer,
+      "le",
+      8
+    );
+
+    const [marketPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("Market"), creator.toBuffer(), uniqueIdBuffer],
+      program.programId
+    );
+
+    const [vaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("market-vault"), marketPda.toBuffer()],
+      program.programId
+    );
+
+    const marketState = await program.account.market.fetch(marketPda);
+    const yesMint = marketState.yesMint;
+    const noMint = marketState.noMint;
+
+    const userYesAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      user,
+      yesMint,
+      user.publicKey
+    );
+    const userNoAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      user,
+      noMint,
+      user.publicKey
+    );
+
+    const [priceOraclePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("price-oracle"), marketPda.toBuffer()],
+      program.programId
+    );
+
+    const [positionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("position"), marketPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // one leg per side of the same market; each leg is
+    // [market, yesMint, noMint, vault, user ATA for that side, price oracle, position]
+    const legAccounts = (ata: PublicKey) =>
+      [marketPda, yesMint, noMint, vaultPda, ata, priceOraclePda, positionPda].map((pubkey) => ({
+        pubkey,
+        isSigner: false,
+        isWritable: true,
+      }));
+
+    const userSolBefore = await connection.getBalance(user.publicKey);
+
+    const batchSig = await program.methods
+      .batchTrade([
+        {
+          side: { yes: {} },
+          numberOfTokens: new anchor.BN(2),
+          maxCost: new anchor.BN(5 * LAMPORTS_PER_SOL),
+        },
+        {
+          side: { no: {} },
+          numberOfTokens: new anchor.BN(3),
+          maxCost: new anchor.BN(5 * LAMPORTS_PER_SOL),
+        },
+      ])
+      .accounts({
+        user: user.publicKey,
+        referrer: null,
+      })
+      .remainingAccounts([
+        ...legAccounts(userYesAta.address),
+        ...legAccounts(userNoAta.address),
+      ])
+      .signers([user])
+      .rpc();
+
+    await connection.confirmTransaction(batchSig, "confirmed");
+
+    const userSolAfter = await connection.getBalance(user.publicKey);
+    console.log(
+      "SOL spent on batch:",
+      ((userSolBefore - userSolAfter) / LAMPORTS_PER_SOL).toFixed(6)
+    );
+    console.log(
+      "User YES tokens:",
+      (await connection.getTokenAccountBalance(userYesAta.address)).value
+        .uiAmount
+    );
+    console.log(
+      "User NO tokens:",
+      (await connection.getTokenAccountBalance(userNoAta.address)).value
+        .uiAmount
+    );
+    console.log("Transaction:", batchSig);
+  });
+
   it("resolve market ", async () => {
     await new Promise((resolve) => setTimeout(resolve, 2000));
     let marketPda: PublicKey;
     let bump: number;
     let oracle_authority = HARSHIT_KEYPAIR.publicKey;
     let creator = HARSHIT_KEYPAIR.publicKey;
-    let outcome = true; // yes wins
-    let unique_market_id = 1104;
+    let outcome = { yes: {} }; // yes wins
+    let unique_market_id = 0;
 
     const uniqueIdBuf = new anchor.BN(unique_market_id).toArrayLike(
       Buffer,
@@ -344,9 +697,8 @@ describe("eventum", () => {
     );
 
     await program.methods
-      .resolveMarket(new anchor.BN(unique_market_id), outcome)
+      .resolveMarket(outcome)
       .accounts({
-        creator,
         market: marketPda,
         oracle_authority,
       })
@@ -359,12 +711,90 @@ describe("eventum", () => {
     console.log("Winning outcome : ", marketState.winningOutcome);
   });
 
+  it("claim for", async () => {
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const creator = HARSHIT_KEYPAIR.publicKey;
+    const cranker = HARSHIT_KEYPAIR;
+    const holder = TEST_KEYPAIR;
+    const unique_market_id = 0;
+    const tipBps = 25;
+
+    const uniqueIdBuffer = new anchor.BN(unique_market_id).toArrayLike(
+      Buffer,
+      "le",
+      8
+    );
+
+    const [marketPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("Market"), creator.toBuffer(), uniqueIdBuffer],
+      program.programId
+    );
+
+    const [vaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("market-vault"), marketPda.toBuffer()],
+      program.programId
+    );
+
+    const [positionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("position"), marketPda.toBuffer(), holder.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const marketState = await program.account.market.fetch(marketPda);
+    const winningMint = marketState.winningOutcome
+      ? marketState.yesMint
+      : marketState.noMint;
+    const holderAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      holder,
+      winningMint,
+      holder.publicKey
+    );
+
+    // holder lets the market PDA burn one winning token on their behalf
+    await approve(
+      connection,
+      holder,
+      holderAta.address,
+      marketPda,
+      holder,
+      LAMPORTS_PER_SOL
+    );
+
+    const holderSolBefore = await connection.getBalance(holder.publicKey);
+
+    const claimSig = await program.methods
+      .claimFor(tipBps)
+      .accounts({
+        cranker: cranker.publicKey,
+        holder: holder.publicKey,
+        market: marketPda,
+        winningMint,
+        holderAta: holderAta.address,
+        vault: vaultPda,
+        position: positionPda,
+      })
+      .signers([cranker])
+      .rpc();
+
+    await connection.confirmTransaction(claimSig, "confirmed");
+
+    const holderSolAfter = await connection.getBalance(holder.publicKey);
+    console.log(
+      "Holder SOL received:",
+      ((holderSolAfter - holderSolBefore) / LAMPORTS_PER_SOL).toFixed(6)
+    );
+    console.log("Cranker tip (bps):", tipBps);
+    console.log("Transaction:", claimSig);
+  });
+
   it("claim winnings", async () => {
     await new Promise((resolve) => setTimeout(resolve, 2000));
 
     const creator = HARSHIT_KEYPAIR.publicKey;
     const user = TEST_KEYPAIR;
-    const unique_market_id = 1104;
+    const unique_market_id = 0;
 
     const uniqueIdBuffer = new anchor.BN(unique_market_id).toArrayLike(
       Buffer,
@@ -382,6 +812,11 @@ describe("eventum", () => {
       program.programId
     );
 
+    const [positionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("position"), marketPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+
     const marketState = await program.account.market.fetch(marketPda);
     const yesMint = marketState.yesMint;
     const noMint = marketState.noMint;
@@ -420,16 +855,16 @@ describe("eventum", () => {
     );
 
     const claimSig = await program.methods
-      .claimWinnings(new anchor.BN(unique_market_id))
+      .claimWinnings()
       .accounts({
-        creator,
         market: marketPda,
         user: user.publicKey,
         userYesAta: userYesAta.address,
         userNoAta: userNoAta.address,
-        poolVault: vaultPda,
+        vault: vaultPda,
         yesMint,
         noMint,
+        position: positionPda,
       })
       .signers([user])
       .rpc();
@@ -457,8 +892,136 @@ describe("eventum", () => {
     );
     console.log("\nSOL received:", solReceived.toFixed(6));
     console.log("Tokens burned:", tokensBurned);
+    const position = await program.account.position.fetch(positionPda);
+    console.log(
+      "Realized PnL:",
+      (position.realizedPnl.toNumber() / LAMPORTS_PER_SOL).toFixed(6)
+    );
+    console.log("Transaction:", claimSig);
+  });
+
+
+  it("claim many", async () => {
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const creator = HARSHIT_KEYPAIR.publicKey;
+    const user = TEST_KEYPAIR;
+    const market_ids = [0];
+
+    // one group per market: [market, winning mint, user ATA, vault]
+    const groups = [];
+    for (const unique_market_id of market_ids) {
+      const uniqueIdBuffer = new anchor.BN(unique_market_id).toArrayLike(
+        Buffer,
+        "le",
+        8
+      );
+      const [marketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("Market"), creator.toBuffer(), uniqueIdBuffer],
+        program.programId
+      );
+      const [vaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market-vault"), marketPda.toBuffer()],
+        program.programId
+      );
+      const [positionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position"), marketPda.toBuffer(), user.publicKey.toBuffer()],
+        program.programId
+      );
+      const marketState = await program.account.market.fetch(marketPda);
+      const winningMint = marketState.winningOutcome
+        ? marketState.yesMint
+        : marketState.noMint;
+      const userAta = await getOrCreateAssociatedTokenAccount(
+        connection,
+        user,
+        winningMint,
+        user.publicKey
+      );
+      groups.push(
+        { pubkey: marketPda, isSigner: false, isWritable: false },
+        { pubkey: winningMint, isSigner: false, isWritable: true },
+        { pubkey: userAta.address, isSigner: false, isWritable: true },
+        { pubkey: vaultPda, isSigner: false, isWritable: true },
+        { pubkey: positionPda, isSigner: false, isWritable: true }
+      );
+    }
+
+    const userSolBefore = await connection.getBalance(user.publicKey);
+
+    const claimSig = await program.methods
+      .claimMany()
+      .accounts({
+        user: user.publicKey,
+      })
+      .remainingAccounts(groups)
+      .signers([user])
+      .rpc();
+
+    await connection.confirmTransaction(claimSig, "confirmed");
+
+    const userSolAfter = await connection.getBalance(user.publicKey);
+    console.log("Markets claimed:", market_ids.length);
+    console.log(
+      "SOL received:",
+      ((userSolAfter - userSolBefore) / LAMPORTS_PER_SOL).toFixed(6)
+    );
     console.log("Transaction:", claimSig);
   });
 
+  it("close market", async () => {
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const creator = HARSHIT_KEYPAIR;
+    const unique_market_id = 0;
+
+    const uniqueIdBuffer = new anchor.BN(unique_market_id).toArrayLike(
+      Buffer,
+      "le",
+      8
+    );
+
+    const [marketPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("Market"), creator.publicKey.toBuffer(), uniqueIdBuffer],
+      program.programId
+    );
+
+    const [vaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("market-vault"), marketPda.toBuffer()],
+      program.programId
+    );
+
+    const marketState = await program.account.market.fetch(marketPda);
+    const creatorSolBefore = await connection.getBalance(creator.publicKey);
+    const vaultBalanceBefore = await connection.getBalance(vaultPda);
+
+    const closeSig = await program.methods
+      .closeMarket()
+      .accounts({
+        creator: creator.publicKey,
+        market: marketPda,
+        yesMint: marketState.yesMint,
+        noMint: marketState.noMint,
+        vault: vaultPda,
+      })
+      .signers([creator])
+      .rpc();
+
+    await connection.confirmTransaction(closeSig, "confirmed");
+
+    const creatorSolAfter = await connection.getBalance(creator.publicKey);
+    const marketInfo = await connection.getAccountInfo(marketPda);
+
+    console.log(
+      "Vault swept:",
+      (vaultBalanceBefore / LAMPORTS_PER_SOL).toFixed(6)
+    );
+    console.log(
+      "Creator SOL reclaimed:",
+      ((creatorSolAfter - creatorSolBefore) / LAMPORTS_PER_SOL).toFixed(6)
+    );
+    console.log("Market closed:", marketInfo === null);
+    console.log("Transaction:", closeSig);
+  });
 
 });